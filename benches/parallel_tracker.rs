@@ -0,0 +1,76 @@
+// Compares proving independent sub-queries one after another on a single tracker
+// against proving them on their own threads with `ProverTrackerRef::prove_branches`.
+// Run with `cargo bench --bench parallel_tracker`
+
+use std::time::{Duration, Instant};
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_poly::DenseMultilinearExtension;
+use ark_std::test_rng;
+
+use zk_sql::subroutines::{MultilinearKzgPCS, PolynomialCommitmentScheme};
+use zk_sql::tracker::prelude::*;
+
+type Kzg = MultilinearKzgPCS<Bls12_381>;
+
+const NUM_BRANCHES: usize = 4;
+const POLYS_PER_BRANCH: usize = 4;
+const REPETITIONS: u32 = 3;
+
+// commits to a few columns and proves each one is the square of the previous one
+fn sub_query<T: IOPTracker<Bls12_381>>(tracker: &mut T, input: &T::Poly, nv: usize) -> Result<TrackerID, PolyIOPErrors> {
+    let mut prev = input.clone();
+    for _ in 0..POLYS_PER_BRANCH {
+        let square = tracker.commit_witness(|token| {
+            let evals = prev.witness_evaluations(token).iter().map(|x| *x * x).collect();
+            Ok(DenseMultilinearExtension::from_evaluations_vec(nv, evals))
+        })?;
+        tracker.add_zerocheck(&square.sub_poly(&prev.mul_poly(&prev)));
+        prev = square;
+    }
+    Ok(prev.id())
+}
+
+fn prove(pcs_param: &<Kzg as PolynomialCommitmentScheme<Bls12_381>>::ProverParam, inputs: &[DenseMultilinearExtension<Fr>], parallel: bool) -> Duration {
+    let nv = inputs[0].num_vars;
+    let mut tracker: ProverTrackerRef<Bls12_381, Kzg> = ProverTrackerRef::new_from_pcs_params(pcs_param.clone());
+    let input_ids: Vec<TrackerID> = inputs.iter().map(|mle| tracker.track_mat_poly(mle.clone()).id).collect();
+
+    let start = Instant::now();
+    if parallel {
+        let branches: Vec<ProverBranch<Bls12_381, Kzg, TrackerID>> = input_ids.iter().map(|id| {
+            let id = *id;
+            Box::new(move |tracker: &mut ProverTrackerRef<Bls12_381, Kzg>| {
+                let input = tracker.get_tracked_poly(id);
+                sub_query(tracker, &input, nv)
+            }) as ProverBranch<Bls12_381, Kzg, TrackerID>
+        }).collect();
+        tracker.prove_branches(branches).unwrap();
+    } else {
+        for id in input_ids {
+            let input = tracker.get_tracked_poly(id);
+            sub_query(&mut tracker, &input, nv).unwrap();
+        }
+    }
+    let branch_time = start.elapsed();
+    tracker.compile_proof().unwrap();
+    branch_time
+}
+
+fn main() {
+    let mut rng = test_rng();
+    for nv in [10, 12, 14] {
+        let srs = Kzg::gen_srs_for_testing(&mut rng, nv).unwrap();
+        let (pcs_param, _) = Kzg::trim(&srs, None, Some(nv)).unwrap();
+        let inputs: Vec<DenseMultilinearExtension<Fr>> = (0..NUM_BRANCHES)
+            .map(|i| DenseMultilinearExtension::from_evaluations_vec(nv, (0..1_u64 << nv).map(|x| Fr::from(x + i as u64)).collect()))
+            .collect();
+
+        let serial: Duration = (0..REPETITIONS).map(|_| prove(&pcs_param, &inputs, false)).sum::<Duration>() / REPETITIONS;
+        let parallel: Duration = (0..REPETITIONS).map(|_| prove(&pcs_param, &inputs, true)).sum::<Duration>() / REPETITIONS;
+        println!(
+            "nv {:>2}, {} sub-queries: single tracker {:>8.1?}, prove_branches {:>8.1?}, speedup {:.2}x",
+            nv, NUM_BRANCHES, serial, parallel, serial.as_secs_f64() / parallel.as_secs_f64(),
+        );
+    }
+}
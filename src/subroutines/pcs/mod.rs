@@ -35,7 +35,9 @@ pub trait PolynomialCommitmentScheme<E: Pairing> {
     /// Proofs
     type Proof: Clone + CanonicalSerialize + CanonicalDeserialize + Debug + PartialEq + Eq;
     /// Batch proofs
-    type BatchProof: Clone + PartialEq + Eq + Debug;
+    type BatchProof: Clone + CanonicalSerialize + CanonicalDeserialize + Debug + PartialEq + Eq;
+    /// Identifies the scheme in serialized proofs
+    const PCS_ID: u8;
 
     /// Build SRS for testing.
     ///
@@ -118,6 +120,7 @@ pub trait PolynomialCommitmentScheme<E: Pairing> {
         _verifier_param: &Self::VerifierParam,
         _commitments: &[Self::Commitment],
         _points: &[Vec::<E::ScalarField>],
+        _values: &[E::ScalarField],
         _batch_proof: &Self::BatchProof,
         _transcript: &mut IOPTranscript<E::ScalarField>,
     ) -> Result<bool, PCSError> {
@@ -21,12 +21,13 @@ use crate::{
 };
 use crate::arithmetic::{build_eq_x_r_vec, DenseMultilinearExtension, VPAuxInfo, VirtualPolynomial};
 use ark_ec::{pairing::Pairing, scalar_mul::variable_base::VariableBaseMSM, CurveGroup};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 use ark_std::{end_timer, log2, start_timer, One, Zero};
 use std::{collections::BTreeMap, iter, marker::PhantomData, ops::Deref, sync::Arc};
 use crate::transcript::IOPTranscript;
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct BatchProof<E, PCS>
 where
     E: Pairing,
@@ -71,8 +72,8 @@ where
     // challenge point t
     let t = transcript.get_and_append_challenge_vectors("t".as_ref(), ell)?;
     
-    // eq(t, i) for i in [0..k]
-    let eq_t_i_list = build_eq_x_r_vec(t.as_ref())?;
+    // eq(t, i) for i in [0..k], t is empty when opening a single polynomial
+    let eq_t_i_list = if t.is_empty() { vec![E::ScalarField::one()] } else { build_eq_x_r_vec(t.as_ref())? };
 
     // \tilde g_i(b) = eq(t, i) * f_i(b)
     let timer = start_timer!(|| format!("compute tilde g for {} points", points.len()));
@@ -181,6 +182,7 @@ pub(crate) fn batch_verify_internal<E, PCS>(
     verifier_param: &PCS::VerifierParam,
     f_i_commitments: &[Commitment<E>],
     points: &[Vec<E::ScalarField>],
+    values: &[E::ScalarField],
     proof: &BatchProof<E, PCS>,
     transcript: &mut IOPTranscript<E::ScalarField>,
 ) -> Result<bool, PCSError>
@@ -193,9 +195,18 @@ where
 {
     let open_timer = start_timer!(|| "batch verification");
 
-    // TODO: sanity checks
-
     let k = f_i_commitments.len();
+    if points.len() != k || values.len() != k || proof.f_i_eval_at_point_i.len() != k {
+        return Err(PCSError::InvalidParameters(
+            "commitments, points, values and proof evaluations have different lengths".to_string(),
+        ));
+    }
+
+    // the evaluations the proof is checked against must be the claimed values
+    if proof.f_i_eval_at_point_i != values {
+        return Ok(false);
+    }
+
     let ell = log2(k) as usize;
     let num_var = proof.sum_check_proof.point.len();
 
@@ -207,7 +218,7 @@ where
 
     // build g' commitment
     let step = start_timer!(|| "build homomorphic commitment");
-    let eq_t_list = build_eq_x_r_vec(t.as_ref())?;
+    let eq_t_list = if t.is_empty() { vec![E::ScalarField::one()] } else { build_eq_x_r_vec(t.as_ref())? };
 
     let mut scalars = vec![];
     let mut bases = vec![];
@@ -319,10 +330,40 @@ mod tests {
             &ml_vk,
             &commitments,
             &points,
+            &evals,
+            &batch_proof,
+            &mut transcript
+        )?);
+
+        // bad path: claimed values differ from the opened evaluations
+        let mut bad_evals = evals.clone();
+        bad_evals[0] += Fr::one();
+        let mut transcript = IOPTranscript::new("test transcript".as_ref());
+        transcript.append_field_element("init".as_ref(), &Fr::zero())?;
+        assert!(!batch_verify_internal::<E, MultilinearKzgPCS<E>>(
+            &ml_vk,
+            &commitments,
+            &points,
+            &bad_evals,
             &batch_proof,
             &mut transcript
         )?);
 
+        // bad path: the proof carries evaluations that do not match the polynomials
+        let mut bad_proof = batch_proof.clone();
+        bad_proof.f_i_eval_at_point_i = bad_evals.clone();
+        let mut transcript = IOPTranscript::new("test transcript".as_ref());
+        transcript.append_field_element("init".as_ref(), &Fr::zero())?;
+        let bad_result = batch_verify_internal::<E, MultilinearKzgPCS<E>>(
+            &ml_vk,
+            &commitments,
+            &points,
+            &bad_evals,
+            &bad_proof,
+            &mut transcript
+        );
+        assert!(!matches!(bad_result, Ok(true)));
+
         Ok(())
     }
 
@@ -331,7 +372,7 @@ mod tests {
         let mut rng = test_rng();
 
         let ml_params = MultilinearUniversalParams::<E>::gen_srs_for_testing(&mut rng, 20)?;
-        for num_poly in 5..6 {
+        for num_poly in [1, 5] {
             for nv in 15..16 {
                 let polys1: Vec<_> = (0..num_poly)
                     .map(|_| Arc::new(DenseMultilinearExtension::rand(nv, &mut rng)))
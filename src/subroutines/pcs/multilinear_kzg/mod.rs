@@ -57,6 +57,7 @@ impl<E: Pairing> PolynomialCommitmentScheme<E> for MultilinearKzgPCS<E> {
     type Commitment = Commitment<E>;
     type Proof = MultilinearKzgProof<E>;
     type BatchProof = BatchProof<E, Self>;
+    const PCS_ID: u8 = 1;
 
     /// Build SRS for testing.
     ///
@@ -180,10 +181,11 @@ impl<E: Pairing> PolynomialCommitmentScheme<E> for MultilinearKzgPCS<E> {
         verifier_param: &Self::VerifierParam,
         commitments: &[Self::Commitment],
         points: &[Vec<E::ScalarField>],
+        values: &[E::ScalarField],
         batch_proof: &Self::BatchProof,
         transcript: &mut IOPTranscript<E::ScalarField>,
     ) -> Result<bool, PCSError> {
-        batch_verify_internal(verifier_param, commitments, points, batch_proof, transcript)
+        batch_verify_internal(verifier_param, commitments, points, values, batch_proof, transcript)
     }
 }
 
@@ -282,6 +284,12 @@ fn verify_internal<E: Pairing>(
             num_var, verifier_param.num_vars
         )));
     }
+    if proof.proofs.len() != num_var {
+        return Err(PCSError::InvalidProof(format!(
+            "number of opening proofs ({}) does not match point length ({})",
+            proof.proofs.len(), num_var
+        )));
+    }
 
     let prepare_inputs_timer = start_timer!(|| "prepare pairing inputs");
 
@@ -10,15 +10,19 @@ use crate::subroutines::pcs::{
     prelude::PCSError,
     StructuredReferenceString,
 };
-use ark_ec::{pairing::Pairing, scalar_mul::fixed_base::FixedBase, AffineRepr, CurveGroup};
+use ark_ec::{
+    pairing::Pairing, scalar_mul::fixed_base::FixedBase, AffineRepr, CurveGroup, VariableBaseMSM,
+};
 use ark_ff::{Field, PrimeField, Zero};
 use ark_poly::DenseMultilinearExtension;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use ark_std::{
     collections::LinkedList, end_timer, format, rand::Rng, start_timer, string::ToString, vec::Vec,
     UniformRand,
 };
 use core::iter::FromIterator;
+use merlin::Transcript;
+use std::{fs, path::Path};
 
 /// Evaluations over {0,1}^n for G1 or G2
 #[derive(CanonicalSerialize, CanonicalDeserialize, Clone, Debug)]
@@ -220,6 +224,157 @@ impl<E: Pairing> StructuredReferenceString<E> for MultilinearUniversalParams<E>
     }
 }
 
+impl<E: Pairing> MultilinearUniversalParams<E> {
+    /// Trim the universal parameters to a smaller universal SRS supporting
+    /// `supported_num_vars`, e.g. to store a smaller copy of a ceremony output.
+    pub fn trim_universal(&self, supported_num_vars: usize) -> Result<Self, PCSError> {
+        if supported_num_vars == 0 || supported_num_vars > self.prover_param.num_vars {
+            return Err(PCSError::InvalidParameters(format!(
+                "SRS does not support target number of vars {}",
+                supported_num_vars
+            )));
+        }
+
+        let to_reduce = self.prover_param.num_vars - supported_num_vars;
+        Ok(Self {
+            prover_param: self.extract_prover_param(supported_num_vars),
+            h_mask: self.h_mask[to_reduce..].to_vec(),
+        })
+    }
+
+    /// Load a stored SRS and trim it to `supported_num_vars`.
+    pub fn load_trimmed<P: AsRef<Path>>(
+        path: P,
+        supported_num_vars: usize,
+    ) -> Result<(MultilinearProverParam<E>, MultilinearVerifierParam<E>), PCSError> {
+        let srs: Self = read_params_from_file(path)?;
+        srs.trim(supported_num_vars)
+    }
+
+    /// Check that the SRS is well formed, i.e. for secret `t_1, ..., t_nv`
+    /// - `powers_of_g[i]` holds `g^{eq((t_{i+1},..,t_nv), x)}` for x in {0,1}^{nv-i},
+    ///   and `powers_of_g[nv]` is `[g]`
+    /// - `h_mask[i]` is `h^{t_{i+1}}`
+    ///
+    /// Writing `P_i` for `powers_of_g[i]`, this holds iff for every `i` and `y`
+    /// - `P_i[2y] + P_i[2y+1] = P_{i+1}[y]`
+    /// - `e(P_i[2y+1], h) = e(P_{i+1}[y], h_mask[i])`
+    ///
+    /// Both are checked for a random linear combination over `y`, so this takes
+    /// 2 MSMs and 2 pairings per variable.
+    pub fn check_consistency<R: Rng>(&self, rng: &mut R) -> Result<(), PCSError> {
+        let check_timer = start_timer!(|| "SRS consistency check");
+        let nv = self.prover_param.num_vars;
+        let powers_of_g = &self.prover_param.powers_of_g;
+        let g = self.prover_param.g;
+        let h = self.prover_param.h;
+
+        if nv == 0 || powers_of_g.len() != nv + 1 || self.h_mask.len() != nv {
+            return Err(PCSError::InvalidParameters(format!(
+                "SRS with {} vars has {} levels and {} masks",
+                nv,
+                powers_of_g.len(),
+                self.h_mask.len()
+            )));
+        }
+        for (i, level) in powers_of_g.iter().enumerate() {
+            if level.evals.len() != 1 << (nv - i) {
+                return Err(PCSError::InvalidParameters(format!(
+                    "SRS level {} has {} elements, expected {}",
+                    i,
+                    level.evals.len(),
+                    1 << (nv - i)
+                )));
+            }
+        }
+        if g.is_zero() || h.is_zero() || powers_of_g[nv].evals[0] != g {
+            return Err(PCSError::InvalidParameters(
+                "SRS generators are inconsistent".to_string(),
+            ));
+        }
+
+        for i in 0..nv {
+            let cur = &powers_of_g[i].evals;
+            let next = &powers_of_g[i + 1].evals;
+            let r: Vec<E::ScalarField> = (0..next.len()).map(|_| E::ScalarField::rand(rng)).collect();
+            let odd: Vec<E::G1Affine> = cur.iter().skip(1).step_by(2).cloned().collect();
+            let even: Vec<E::G1Affine> = cur.iter().step_by(2).cloned().collect();
+
+            let odd_comb = E::G1::msm_unchecked(&odd, &r);
+            let even_comb = E::G1::msm_unchecked(&even, &r);
+            let next_comb = E::G1::msm_unchecked(next, &r);
+
+            if even_comb + odd_comb != next_comb
+                || E::pairing(odd_comb, h) != E::pairing(next_comb, self.h_mask[i])
+            {
+                return Err(PCSError::InvalidParameters(format!(
+                    "SRS level {} is inconsistent",
+                    i
+                )));
+            }
+        }
+        end_timer!(check_timer);
+        Ok(())
+    }
+}
+
+/// Write parameters to `path`, prefixed with a checksum of the serialized bytes.
+pub fn write_params_to_file<T: CanonicalSerialize, P: AsRef<Path>>(
+    params: &T,
+    path: P,
+) -> Result<(), PCSError> {
+    let mut bytes = Vec::with_capacity(PARAMS_CHECKSUM_LEN + params.compressed_size());
+    bytes.extend_from_slice(&[0u8; PARAMS_CHECKSUM_LEN]);
+    params.serialize_compressed(&mut bytes)?;
+    let checksum = params_checksum(&bytes[PARAMS_CHECKSUM_LEN..]);
+    bytes[..PARAMS_CHECKSUM_LEN].copy_from_slice(&checksum);
+    fs::write(path, bytes).map_err(SerializationError::IoError)?;
+    Ok(())
+}
+
+/// Read parameters written by [`write_params_to_file`], rejecting the file if
+/// the checksum does not match or there are trailing bytes.
+pub fn read_params_from_file<T: CanonicalDeserialize, P: AsRef<Path>>(
+    path: P,
+) -> Result<T, PCSError> {
+    let bytes = fs::read(path).map_err(SerializationError::IoError)?;
+    if bytes.len() < PARAMS_CHECKSUM_LEN {
+        return Err(PCSError::InvalidParameters(
+            "params file is too short".to_string(),
+        ));
+    }
+    let (checksum, payload) = bytes.split_at(PARAMS_CHECKSUM_LEN);
+    if checksum != params_checksum(payload) {
+        return Err(PCSError::InvalidParameters(
+            "params file checksum mismatch".to_string(),
+        ));
+    }
+
+    let mut reader = payload;
+    let params = T::deserialize_compressed(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(PCSError::InvalidParameters(format!(
+            "params file has {} trailing bytes",
+            reader.len()
+        )));
+    }
+    Ok(params)
+}
+
+const PARAMS_CHECKSUM_LEN: usize = 32;
+
+fn params_checksum(payload: &[u8]) -> [u8; PARAMS_CHECKSUM_LEN] {
+    let mut transcript = Transcript::new(b"zksql params checksum");
+    transcript.append_u64(b"len", payload.len() as u64);
+    // merlin frames messages with a u32 length, so large params are absorbed in chunks
+    for chunk in payload.chunks(1 << 20) {
+        transcript.append_message(b"chunk", chunk);
+    }
+    let mut checksum = [0u8; PARAMS_CHECKSUM_LEN];
+    transcript.challenge_bytes(b"checksum", &mut checksum);
+    checksum
+}
+
 /// fix first `pad` variables of `poly` represented in evaluation form to zero
 fn remove_dummy_variable<F: Field>(poly: &[F], pad: usize) -> Result<Vec<F>, PCSError> {
     if pad == 0 {
@@ -241,6 +396,12 @@ mod tests {
     use ark_std::test_rng;
     type E = Bls12_381;
 
+    fn to_bytes<T: CanonicalSerialize>(params: &T) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        params.serialize_compressed(&mut bytes).unwrap();
+        bytes
+    }
+
     #[test]
     fn test_srs_gen() -> Result<(), PCSError> {
         let mut rng = test_rng();
@@ -250,4 +411,82 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_srs_consistency() -> Result<(), PCSError> {
+        let mut rng = test_rng();
+        let nv = 5;
+        let srs = MultilinearUniversalParams::<E>::gen_srs_for_testing(&mut rng, nv)?;
+        srs.check_consistency(&mut rng)?;
+        srs.trim_universal(3)?.check_consistency(&mut rng)?;
+        assert!(srs.trim_universal(nv + 1).is_err());
+
+        // bad path 1: an element of the eq powers is changed
+        let mut bad_srs = srs.clone();
+        bad_srs.prover_param.powers_of_g[1].evals[3] = (bad_srs.prover_param.powers_of_g[1].evals[3] + bad_srs.prover_param.g).into_affine();
+        assert!(bad_srs.check_consistency(&mut rng).is_err());
+
+        // bad path 2: a mask is changed
+        let mut bad_srs = srs.clone();
+        bad_srs.h_mask[2] = (bad_srs.h_mask[2] + bad_srs.prover_param.h).into_affine();
+        assert!(bad_srs.check_consistency(&mut rng).is_err());
+
+        // bad path 3: the SRS is generated from a different secret for the masks
+        let other_srs = MultilinearUniversalParams::<E>::gen_srs_for_testing(&mut rng, nv)?;
+        let mut bad_srs = srs.clone();
+        bad_srs.h_mask = other_srs.h_mask;
+        assert!(bad_srs.check_consistency(&mut rng).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_srs_file_round_trip() -> Result<(), PCSError> {
+        let mut rng = test_rng();
+        let nv = 5;
+        let srs = MultilinearUniversalParams::<E>::gen_srs_for_testing(&mut rng, nv)?;
+        let dir = std::env::temp_dir().join(format!("zksql_srs_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let srs_path = dir.join("srs.bin");
+
+        // universal params
+        write_params_to_file(&srs, &srs_path)?;
+        let loaded: MultilinearUniversalParams<E> = read_params_from_file(&srs_path)?;
+        loaded.check_consistency(&mut rng)?;
+        assert_eq!(to_bytes(&loaded), to_bytes(&srs));
+
+        // trimmed params from the stored SRS match trimming in memory
+        let (ck, vk) = MultilinearUniversalParams::<E>::load_trimmed(&srs_path, 3)?;
+        let (expected_ck, expected_vk) = srs.trim(3)?;
+        assert_eq!(to_bytes(&ck), to_bytes(&expected_ck));
+        assert_eq!(to_bytes(&vk), to_bytes(&expected_vk));
+
+        // prover and verifier params
+        let ck_path = dir.join("ck.bin");
+        let vk_path = dir.join("vk.bin");
+        write_params_to_file(&ck, &ck_path)?;
+        write_params_to_file(&vk, &vk_path)?;
+        let loaded_ck: MultilinearProverParam<E> = read_params_from_file(&ck_path)?;
+        let loaded_vk: MultilinearVerifierParam<E> = read_params_from_file(&vk_path)?;
+        assert_eq!(to_bytes(&loaded_ck), to_bytes(&expected_ck));
+        assert_eq!(to_bytes(&loaded_vk), to_bytes(&expected_vk));
+
+        // bad path 1: a corrupted byte fails the checksum
+        let mut bytes = fs::read(&srs_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&srs_path, &bytes).unwrap();
+        assert!(read_params_from_file::<MultilinearUniversalParams<E>, _>(&srs_path).is_err());
+
+        // bad path 2: truncated file
+        bytes[last] ^= 1;
+        fs::write(&srs_path, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(read_params_from_file::<MultilinearUniversalParams<E>, _>(&srs_path).is_err());
+
+        // bad path 3: params of another type
+        assert!(read_params_from_file::<MultilinearUniversalParams<E>, _>(&vk_path).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+        Ok(())
+    }
 }
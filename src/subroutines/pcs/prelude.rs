@@ -9,7 +9,10 @@ pub use crate::subroutines::pcs::{
     errors::PCSError,
     multilinear_kzg::{
         batching::BatchProof,
-        srs::{MultilinearProverParam, MultilinearUniversalParams, MultilinearVerifierParam},
+        srs::{
+            read_params_from_file, write_params_to_file, MultilinearProverParam,
+            MultilinearUniversalParams, MultilinearVerifierParam,
+        },
         MultilinearKzgPCS, MultilinearKzgProof,
     },
     structs::Commitment,
@@ -189,10 +189,20 @@ impl<F: PrimeField> SumCheck<F> for PolyIOP<F> {
     ) -> Result<Self::SumCheckSubClaim, PolyIOPErrors> {
         let start = start_timer!(|| "sum check verify");
 
+        if proof.proofs.len() != aux_info.num_variables {
+            return Err(PolyIOPErrors::InvalidProof(format!(
+                "incorrect number of prover messages: {} vs {}",
+                proof.proofs.len(),
+                aux_info.num_variables
+            )));
+        }
+        if aux_info.max_degree == 0 || aux_info.max_degree == usize::MAX {
+            return Err(PolyIOPErrors::InvalidProof(format!("invalid max degree {}", aux_info.max_degree)));
+        }
+
         transcript.append_serializable_element(b"aux info", aux_info)?;
         let mut verifier_state = IOPVerifierState::verifier_init(aux_info);
-        for i in 0..aux_info.num_variables {
-            let prover_msg = proof.proofs.get(i).expect("proof is incomplete");
+        for prover_msg in proof.proofs.iter() {
             transcript.append_serializable_element(b"prover msg", prover_msg)?;
             IOPVerifierState::verify_round_and_update_state(
                 &mut verifier_state,
@@ -1,6 +1,7 @@
 use derivative::Derivative;
 use crate::subroutines::PolynomialCommitmentScheme;
 use ark_ec::pairing::Pairing;
+use ark_std::One;
 use crate::tracker::prelude::*;
 
 #[derive(Derivative)]
@@ -26,6 +27,13 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> Bag<E, PCS> {
         }
     }
 
+    /// The public bag of the range 0..2^num_vars, with every element selected
+    pub fn range(tracker: &mut ProverTrackerRef<E, PCS>, num_vars: usize) -> Self {
+        let poly = tracker.track_public_poly(PublicPoly::Identity { num_vars });
+        let selector = tracker.track_public_poly(PublicPoly::Constant { num_vars, value: E::ScalarField::one() });
+        Self::new(poly, selector)
+    }
+
     pub fn num_vars(&self) -> usize {
         self.poly.num_vars()
     }
@@ -53,6 +61,14 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> BagComm<E, PCS> {
             num_vars
         }
     }
+
+    /// The public bag of the range 0..2^num_vars, with every element selected
+    pub fn range(tracker: &mut VerifierTrackerRef<E, PCS>, num_vars: usize) -> Self {
+        let poly = tracker.track_public_poly(PublicPoly::Identity { num_vars });
+        let selector = tracker.track_public_poly(PublicPoly::Constant { num_vars, value: E::ScalarField::one() });
+        Self::new(poly, selector, num_vars)
+    }
+
     pub fn num_vars(&self) -> usize {
         self.num_vars
     }
@@ -63,26 +79,42 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> BagComm<E, PCS> {
     Clone(bound = "PCS: PolynomialCommitmentScheme<E>"),
     PartialEq(bound = "PCS: PolynomialCommitmentScheme<E>"),
 )]
+/// A table of columns with a row selector.
+/// NULLs are encoded with a validity selector per column, which is 1 on the rows where the column has a value
+/// and 0 on the rows where it is NULL, where the column itself is 0. A column without a validity selector has no NULLs
 pub struct Table<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
     pub col_vals: Vec<TrackedPoly<E, PCS>>,
+    pub col_validity: Vec<Option<TrackedPoly<E, PCS>>>,
     pub selector: TrackedPoly<E, PCS>,
 }
 
 impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> Table<E, PCS> {
     pub fn new(col_vals: Vec<TrackedPoly<E, PCS>>, selector: TrackedPoly<E, PCS>) -> Self {
+        let col_validity = vec![None; col_vals.len()];
+        Self::new_with_validity(col_vals, col_validity, selector)
+    }
+
+    pub fn new_with_validity(col_vals: Vec<TrackedPoly<E, PCS>>, col_validity: Vec<Option<TrackedPoly<E, PCS>>>, selector: TrackedPoly<E, PCS>) -> Self {
         #[cfg(debug_assertions)]
         {
-            for poly in col_vals.iter() {
+            assert_eq!(col_vals.len(), col_validity.len());
+            for poly in col_vals.iter().chain(col_validity.iter().flatten()) {
                 assert_eq!(poly.num_vars, selector.num_vars);
                 assert!(poly.same_tracker(&selector));
             }
         }
         Self {
             col_vals,
+            col_validity,
             selector,
         }
     }
 
+    /// Whether any column of the table can be NULL
+    pub fn has_nulls(&self) -> bool {
+        self.col_validity.iter().any(|validity| validity.is_some())
+    }
+
     pub fn num_vars(&self) -> usize {
         self.selector.num_vars
     }
@@ -99,19 +131,72 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> Table<E, PCS> {
 )]
 pub struct TableComm<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
     pub col_vals: Vec<TrackedComm<E, PCS>>,
+    pub col_validity: Vec<Option<TrackedComm<E, PCS>>>,
     pub selector: TrackedComm<E, PCS>,
     pub num_vars: usize,
 }
 
 impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> TableComm<E, PCS> {
     pub fn new(col_vals: Vec<TrackedComm<E, PCS>>, selector: TrackedComm<E, PCS>, num_vars: usize) -> Self {
+        let col_validity = vec![None; col_vals.len()];
+        Self::new_with_validity(col_vals, col_validity, selector, num_vars)
+    }
+
+    pub fn new_with_validity(col_vals: Vec<TrackedComm<E, PCS>>, col_validity: Vec<Option<TrackedComm<E, PCS>>>, selector: TrackedComm<E, PCS>, num_vars: usize) -> Self {
+        #[cfg(debug_assertions)]
+        {
+            assert_eq!(col_vals.len(), col_validity.len());
+        }
         Self {
             col_vals,
+            col_validity,
             selector,
             num_vars
         }
     }
+
+    /// Whether any column of the table can be NULL
+    pub fn has_nulls(&self) -> bool {
+        self.col_validity.iter().any(|validity| validity.is_some())
+    }
+
     pub fn num_vars(&self) -> usize {
         self.num_vars
     }
-}
\ No newline at end of file
+}
+
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> IOPBag<TrackedPoly<E, PCS>> for Bag<E, PCS> {
+    fn new_bag(poly: TrackedPoly<E, PCS>, selector: TrackedPoly<E, PCS>, _num_vars: usize) -> Self {
+        Self::new(poly, selector)
+    }
+
+    fn poly(&self) -> &TrackedPoly<E, PCS> {
+        &self.poly
+    }
+
+    fn selector(&self) -> &TrackedPoly<E, PCS> {
+        &self.selector
+    }
+
+    fn num_vars(&self) -> usize {
+        Bag::num_vars(self)
+    }
+}
+
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> IOPBag<TrackedComm<E, PCS>> for BagComm<E, PCS> {
+    fn new_bag(poly: TrackedComm<E, PCS>, selector: TrackedComm<E, PCS>, num_vars: usize) -> Self {
+        Self::new(poly, selector, num_vars)
+    }
+
+    fn poly(&self) -> &TrackedComm<E, PCS> {
+        &self.poly
+    }
+
+    fn selector(&self) -> &TrackedComm<E, PCS> {
+        &self.selector
+    }
+
+    fn num_vars(&self) -> usize {
+        BagComm::num_vars(self)
+    }
+}
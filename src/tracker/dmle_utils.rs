@@ -68,6 +68,39 @@ pub fn dmle_increase_nv_back<F: Field>(
     Arc::new(DenseMultilinearExtension::from_evaluations_vec(new_nv, evals))
 }
 
+/// Restrict an evaluation point of the aggregated sumcheck polynomial to a polynomial with `nv` variables.
+/// When a proof is compiled, smaller polynomials are padded with dmle_increase_nv_front,
+/// so they only depend on the last `nv` coordinates of the point
+pub fn point_suffix<F: Field>(
+    pt: &[F],
+    nv: usize,
+) -> Result<&[F], ArithErrors> {
+    if pt.len() < nv {
+        return Err(ArithErrors::InvalidParameters(
+            format!("point has {} coordinates but the polynomial has {} variables", pt.len(), nv),
+        ));
+    }
+    Ok(&pt[pt.len() - nv..])
+}
+
+/// Map an evaluation point of a polynomial created with dmle_increase_nv_back to the matching point of its base polynomial,
+/// assuming both were padded to the length of the point with dmle_increase_nv_front
+/// Ex. the point (a, b, c, d) of P'(X, Y, Z) = P(X, Y) with one added var maps to (d, a, b, c)
+pub fn rotate_point_right<F: Field>(
+    pt: &[F],
+    added_nv: usize,
+) -> Result<Vec<F>, ArithErrors> {
+    if pt.len() < added_nv {
+        return Err(ArithErrors::InvalidParameters(
+            format!("cannot rotate a point with {} coordinates by {}", pt.len(), added_nv),
+        ));
+    }
+    let split = pt.len() - added_nv;
+    let mut rotated = pt[split..].to_vec();
+    rotated.extend_from_slice(&pt[..split]);
+    Ok(rotated)
+}
+
 /// This function build the eq(x, r) polynomial for any given r.
 /// Used in ZeroCheck when converting from zerocheck to sumcheck
 /// 
@@ -128,4 +161,22 @@ mod test {
         assert_eq!(large_mle.evaluations[0..2_usize.pow(small_nv as u32)], small_mle.evaluations);
         assert_eq!(large_mle_rand_eval, small_mle_rand_eval);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rotate_point_right() {
+        let mut rng = test_rng();
+        let base_nv = 3;
+        let added_nv = 2;
+        let padded_nv = 7;
+
+        let base_mle = Arc::new(crate::arithmetic::random_permutation_mles::<Fr, _>(base_nv, 1, &mut rng)[0].clone());
+        let resized_mle = dmle_increase_nv_back(&base_mle, base_nv + added_nv);
+        let padded_base = dmle_increase_nv_front(&base_mle, padded_nv);
+        let padded_resized = dmle_increase_nv_front(&resized_mle, padded_nv);
+
+        let pt: Vec<Fr> = (0..padded_nv).map(|_| Fr::rand(&mut rng)).collect();
+        let rotated_pt = rotate_point_right(&pt, added_nv).unwrap();
+        assert_eq!(padded_resized.evaluate(&pt).unwrap(), padded_base.evaluate(&rotated_pt).unwrap());
+        assert_eq!(padded_base.evaluate(&pt).unwrap(), base_mle.evaluate(point_suffix(&pt, base_nv).unwrap()).unwrap());
+    }
+}
@@ -0,0 +1,71 @@
+// Common interface over the prover and verifier trackers, so an IOP can be written once as a generic "circuit"
+// and run by both sides. Since both sides run the same code, they create the same polys and claims in the same order
+
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+
+use crate::tracker::{
+    errors::PolyIOPErrors,
+    public_poly::PublicPoly,
+    tracker_structs::TrackerID,
+};
+
+/// Handed to witness closures, which only the prover runs.
+/// It can only be created by the prover tracker, so witness evaluations can never be requested on the verifier side
+pub struct WitnessToken {
+    _private: (),
+}
+
+impl WitnessToken {
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+/// A polynomial tracked by either side: a `TrackedPoly` for the prover or a `TrackedComm` for the verifier
+pub trait IOPPoly<F>: Clone {
+    fn id(&self) -> TrackerID;
+    fn add_poly(&self, other: &Self) -> Self;
+    fn sub_poly(&self, other: &Self) -> Self;
+    fn mul_poly(&self, other: &Self) -> Self;
+    fn add_scalar(&self, c: F) -> Self;
+    fn mul_scalar(&self, c: F) -> Self;
+    fn increase_nv_front(&self, added_nv: usize) -> Self;
+    fn increase_nv_back(&self, added_nv: usize) -> Self;
+    /// The evaluations over the boolean hypercube, for computing witnesses inside `IOPTracker::commit_witness`
+    fn witness_evaluations(&self, token: &WitnessToken) -> Vec<F>;
+}
+
+/// A bag of either side: a `Bag` for the prover or a `BagComm` for the verifier
+pub trait IOPBag<P>: Clone {
+    fn new_bag(poly: P, selector: P, num_vars: usize) -> Self;
+    fn poly(&self) -> &P;
+    fn selector(&self) -> &P;
+    fn num_vars(&self) -> usize;
+}
+
+pub trait IOPTracker<E: Pairing> {
+    type Poly: IOPPoly<E::ScalarField>;
+    type Bag: IOPBag<Self::Poly>;
+
+    fn get_and_append_challenge(&mut self, label: &'static [u8]) -> Result<E::ScalarField, PolyIOPErrors>;
+
+    fn track_public_poly(&mut self, polynomial: PublicPoly<E::ScalarField>) -> Self::Poly;
+
+    /// The prover computes the witness and commits to it, the verifier takes the next commitment from the proof
+    fn commit_witness<W>(&mut self, witness: W) -> Result<Self::Poly, PolyIOPErrors>
+    where
+        W: FnOnce(&WitnessToken) -> Result<DenseMultilinearExtension<E::ScalarField>, PolyIOPErrors>;
+
+    fn add_sumcheck(&mut self, poly: &Self::Poly, claimed_sum: E::ScalarField);
+
+    fn add_zerocheck(&mut self, poly: &Self::Poly);
+
+    /// Add a sumcheck claim for a sum only the prover knows and return it.
+    /// The prover computes the sum over the poly's hypercube, the verifier reads the sum the proof claims,
+    /// so the returned values should only be compared through `check`
+    fn add_prover_sumcheck(&mut self, poly: &Self::Poly) -> Result<E::ScalarField, PolyIOPErrors>;
+
+    /// A check on values claimed by the prover. The verifier rejects when it fails, the prover trusts its own values
+    fn check(&self, ok: bool, err_msg: impl FnOnce() -> String) -> Result<(), PolyIOPErrors>;
+}
@@ -1,9 +1,11 @@
 mod bag;
 mod dmle_utils;
 mod errors;
+mod iop_tracker;
 mod pcs_accumulator;
 mod prover_tracker;
 mod prover_wrapper;
+mod public_poly;
 mod test;
 mod tracker_structs;
 mod verifier_tracker;
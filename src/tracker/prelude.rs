@@ -3,7 +3,9 @@ pub use crate::tracker::{
     bag::{Bag, BagComm, Table, TableComm},
     dmle_utils::*,
     errors::PolyIOPErrors,
-    prover_wrapper::{ProverTrackerRef, TrackedPoly}, 
-    tracker_structs::TrackerID,
-    verifier_wrapper::{TrackedComm, VerifierTrackerRef},
+    iop_tracker::{IOPBag, IOPPoly, IOPTracker, WitnessToken},
+    prover_wrapper::{ProverBranch, ProverTrackerRef, TrackedPoly}, 
+    public_poly::PublicPoly,
+    tracker_structs::{CompiledZKSQLProof, TrackerID, PROOF_FORMAT_VERSION},
+    verifier_wrapper::{TrackedComm, VerifierBranch, VerifierTrackerRef},
 };
@@ -7,7 +7,7 @@
 /// 
 /// 
 use std::{
-    collections::HashMap, 
+    collections::{BTreeSet, HashMap}, 
     ops::Neg, 
     panic, 
     sync::Arc,
@@ -21,8 +21,9 @@ use ark_std::{One, Zero};
 use crate::arithmetic::VirtualPolynomial;
 
 use crate::tracker::{
-    dmle_utils::{dmle_increase_nv_back, dmle_increase_nv_front, build_eq_x_r},
-    tracker_structs::{TrackerID, TrackerSumcheckClaim, TrackerZerocheckClaim, CompiledZKSQLProof},
+    dmle_utils::{dmle_increase_nv_back, dmle_increase_nv_front, rotate_point_right},
+    public_poly::PublicPoly,
+    tracker_structs::{branch_id_ranges, TrackerID, TrackerSumcheckClaim, TrackerZerocheckClaim, CompiledZKSQLProof},
     errors::PolyIOPErrors,
     // pcs_accumulator::PcsAccumulator;
 };
@@ -45,32 +46,55 @@ use crate::transcript::{IOPTranscript, TranscriptError};
 
 
 
+// a fork that has not been joined yet: the tracker's polys at the time of the fork, read by every branch
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "PCS: PolynomialCommitmentScheme<E>"),
+)]
+struct TrackerFork<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    base: Arc<ProverTracker<E, PCS>>,
+    base_id: usize,
+    num_branches: usize,
+}
+
 #[derive(Derivative, Display)]
 #[derivative(
     Clone(bound = "PCS: PolynomialCommitmentScheme<E>"),
 )]
 pub struct ProverTracker<E: Pairing, PCS: PolynomialCommitmentScheme<E>>{
-    pub pcs_param: PCS::ProverParam,
+    pub pcs_param: Arc<PCS::ProverParam>, // shared with forked branches
     pub transcript: IOPTranscript<E::ScalarField>,
     pub id_counter: usize,
+    pub id_limit: usize, // ids at or past the limit belong to another branch
     pub materialized_polys: HashMap<TrackerID, Arc<DenseMultilinearExtension<E::ScalarField>>>, // underlying materialized polynomials, keyed by label
     pub virtual_polys: HashMap<TrackerID, Vec<(E::ScalarField, Vec<TrackerID>)>>, // virtual polynomials, keyed by label. Invariant: a virt poly contains only material TrackerIDs
     pub materialized_comms: HashMap<TrackerID, PCS::Commitment>,
+    pub virtual_poly_evals: HashMap<TrackerID, Arc<DenseMultilinearExtension<E::ScalarField>>>, // cached hypercube evaluations of virtual polys. Virtual polys stay virtual so they evaluate off the hypercube the same way the verifier's closures do
+    pub resized_polys: HashMap<TrackerID, (TrackerID, usize)>, // resized poly id -> (base poly id, number of coordinates to rotate the query point by)
     pub sum_check_claims: Vec<TrackerSumcheckClaim<E::ScalarField>>,
     pub zero_check_claims: Vec<TrackerZerocheckClaim<E::ScalarField>>,
+    fork: Option<TrackerFork<E, PCS>>, // set between fork and join
+    fork_base: Option<Arc<ProverTracker<E, PCS>>>, // for a branch, the polys of the tracker it was forked from
+    branch_index: usize,
 }
 
 impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
     pub fn new(pcs_param: PCS::ProverParam) -> Self {
         Self {
-            pcs_param: pcs_param,
+            pcs_param: Arc::new(pcs_param),
             transcript: IOPTranscript::<E::ScalarField>::new(b"Initializing Tracnscript"),
             id_counter: 0,
+            id_limit: usize::MAX,
             virtual_polys: HashMap::new(),
             materialized_polys: HashMap::new(),
             materialized_comms: HashMap::new(),
+            virtual_poly_evals: HashMap::new(),
+            resized_polys: HashMap::new(),
             sum_check_claims: Vec::new(),
             zero_check_claims: Vec::new(),
+            fork: None,
+            fork_base: None,
+            branch_index: 0,
         }
     }
 
@@ -88,6 +112,119 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
     pub fn get_next_id(&mut self) -> TrackerID {
         TrackerID(self.id_counter)
     }
+
+    /// Splits off one tracker per branch, each with its own range of ids and its own transcript,
+    /// so the branches can be proven on different threads without depending on which thread runs first.
+    /// The polys tracked so far move behind an `Arc` that every branch reads from, so a branch only owns what it creates.
+    /// The branches must be given back to `join` in the same order
+    pub fn fork(&mut self, num_branches: usize) -> Result<Vec<Self>, PolyIOPErrors> {
+        if self.fork.is_some() {
+            return Err(PolyIOPErrors::InvalidProver("fork Error: the previous fork has not been joined".to_string()));
+        }
+        let ranges = branch_id_ranges(self.id_counter, self.id_limit, num_branches)?;
+        let base = Arc::new(Self {
+            pcs_param: self.pcs_param.clone(),
+            transcript: self.transcript.clone(),
+            id_counter: self.id_counter,
+            id_limit: self.id_limit,
+            materialized_polys: std::mem::take(&mut self.materialized_polys),
+            virtual_polys: std::mem::take(&mut self.virtual_polys),
+            materialized_comms: std::mem::take(&mut self.materialized_comms),
+            virtual_poly_evals: std::mem::take(&mut self.virtual_poly_evals),
+            resized_polys: std::mem::take(&mut self.resized_polys),
+            sum_check_claims: Vec::new(),
+            zero_check_claims: Vec::new(),
+            fork: None,
+            fork_base: self.fork_base.clone(),
+            branch_index: self.branch_index,
+        });
+
+        let mut branches = Vec::with_capacity(num_branches);
+        for (i, (start, end)) in ranges.into_iter().enumerate() {
+            let mut transcript = self.transcript.clone();
+            transcript.append_serializable_element(b"branch", &(i as u64))?;
+            branches.push(Self {
+                pcs_param: self.pcs_param.clone(),
+                transcript,
+                id_counter: start,
+                id_limit: end,
+                materialized_polys: HashMap::new(),
+                virtual_polys: HashMap::new(),
+                materialized_comms: HashMap::new(),
+                virtual_poly_evals: HashMap::new(),
+                resized_polys: HashMap::new(),
+                sum_check_claims: Vec::new(),
+                zero_check_claims: Vec::new(),
+                fork: None,
+                fork_base: Some(base.clone()),
+                branch_index: i,
+            });
+        }
+        self.fork = Some(TrackerFork { base, base_id: self.id_counter, num_branches });
+        Ok(branches)
+    }
+
+    /// Merges the polys and claims of forked branches back in branch order,
+    /// and absorbs the commitments each branch made into the transcript in id order.
+    /// Errors if the branches are not exactly the ones the last `fork` made, in the order it made them
+    pub fn join(&mut self, mut branches: Vec<Self>) -> Result<(), PolyIOPErrors> {
+        let fork = self.fork.as_ref().ok_or_else(|| PolyIOPErrors::InvalidProver("join Error: the tracker has not forked".to_string()))?;
+        if branches.len() != fork.num_branches {
+            return Err(PolyIOPErrors::InvalidProver(format!("join Error: expected {} branches, got {}", fork.num_branches, branches.len())));
+        }
+        let ranges = branch_id_ranges(fork.base_id, self.id_limit, fork.num_branches)?;
+        for (i, (branch, (_, end))) in branches.iter().zip(ranges.iter().copied()).enumerate() {
+            let from_this_fork = branch.fork_base.as_ref().is_some_and(|base| Arc::ptr_eq(base, &fork.base));
+            if !from_this_fork || branch.branch_index != i {
+                return Err(PolyIOPErrors::InvalidProver(format!("join Error: branch {} was not forked as branch {} of this tracker", branch.branch_index, i)));
+            }
+            if branch.id_counter > end {
+                return Err(PolyIOPErrors::InvalidProver(format!("branch used ids past its limit {}", end)));
+            }
+        }
+
+        // take the polys tracked before the fork back once the branches no longer read them
+        branches.iter_mut().for_each(|branch| branch.fork_base = None);
+        let fork = self.fork.take().unwrap();
+        let base = match Arc::try_unwrap(fork.base) {
+            Ok(base) => base,
+            Err(base) => {
+                self.fork = Some(TrackerFork { base, ..fork });
+                return Err(PolyIOPErrors::InvalidProver("join Error: a copy of a branch is still in use".to_string()));
+            },
+        };
+        self.materialized_polys = base.materialized_polys;
+        self.virtual_polys = base.virtual_polys;
+        self.materialized_comms = base.materialized_comms;
+        self.virtual_poly_evals = base.virtual_poly_evals;
+        self.resized_polys = base.resized_polys;
+
+        for (branch, (start, end)) in branches.into_iter().zip(ranges.iter().copied()) {
+            let in_branch = |id: &TrackerID| id.to_int() >= start && id.to_int() < end;
+            self.materialized_polys.extend(branch.materialized_polys);
+            self.virtual_polys.extend(branch.virtual_polys);
+            self.virtual_poly_evals.extend(branch.virtual_poly_evals.into_iter().filter(|(id, _)| in_branch(id)));
+            self.resized_polys.extend(branch.resized_polys);
+
+            let mut comms: Vec<_> = branch.materialized_comms.into_iter().collect();
+            comms.sort_by_key(|(id, _)| *id);
+            for (id, comm) in comms {
+                self.transcript.append_serializable_element(b"branch comm", &comm)?;
+                self.materialized_comms.insert(id, comm);
+            }
+
+            self.sum_check_claims.extend(branch.sum_check_claims);
+            self.zero_check_claims.extend(branch.zero_check_claims);
+        }
+        self.id_counter = ranges.last().unwrap().1;
+        Ok(())
+    }
+
+    // the tracker that polys not created by this tracker are looked up in:
+    // the polys moved aside by an unjoined fork, or for a branch, the tracker it was forked from
+    fn shared_polys(&self) -> Option<&Self> {
+        self.fork.as_ref().map(|fork| fork.base.as_ref()).or(self.fork_base.as_deref())
+    }
     
     pub fn track_mat_poly(
         &mut self,
@@ -97,6 +234,14 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
         self.track_mat_arc_poly(polynomial)
     }
 
+    pub fn track_public_poly(
+        &mut self,
+        polynomial: &PublicPoly<E::ScalarField>,
+    ) -> TrackerID {
+        // the verifier evaluates public polys itself, so they are not committed
+        self.track_mat_poly(polynomial.to_mle())
+    }
+
     fn track_mat_arc_poly(
         &mut self,
         polynomial: Arc<DenseMultilinearExtension<E::ScalarField>>,
@@ -116,7 +261,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
         polynomial: DenseMultilinearExtension<E::ScalarField>,
     ) -> Result<TrackerID, PCSError> {
         // commit to the p[olynomial
-        let commitment = PCS::commit(self.pcs_param.clone(), &polynomial)?;
+        let commitment = PCS::commit(self.pcs_param.as_ref(), &polynomial)?;
 
         // track the polynomial and get its id
         let poly_id = self.track_mat_poly(polynomial);
@@ -140,21 +285,33 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
     }
        
     pub fn get_mat_poly(&self, id: TrackerID) -> Option<&Arc<DenseMultilinearExtension<E::ScalarField>>> {
-        self.materialized_polys.get(&id)
+        self.materialized_polys.get(&id).or_else(|| self.shared_polys()?.get_mat_poly(id))
     }
 
     pub fn get_virt_poly(&self, id: TrackerID) -> Option<&Vec<(E::ScalarField, Vec<TrackerID>)>> {
-        self.virtual_polys.get(&id)
+        self.virtual_polys.get(&id).or_else(|| self.shared_polys()?.get_virt_poly(id))
+    }
+
+    fn get_virt_poly_evals(&self, id: TrackerID) -> Option<&Arc<DenseMultilinearExtension<E::ScalarField>>> {
+        self.virtual_poly_evals.get(&id).or_else(|| self.shared_polys()?.get_virt_poly_evals(id))
+    }
+
+    fn get_resized_poly(&self, id: TrackerID) -> Option<&(TrackerID, usize)> {
+        self.resized_polys.get(&id).or_else(|| self.shared_polys()?.get_resized_poly(id))
+    }
+
+    fn is_committed(&self, id: TrackerID) -> bool {
+        self.materialized_comms.contains_key(&id) || self.shared_polys().is_some_and(|shared| shared.is_committed(id))
     }
 
     pub fn get_poly_nv(&self, id: TrackerID) -> usize {
-        let mat_poly = self.materialized_polys.get(&id);
+        let mat_poly = self.get_mat_poly(id);
         if mat_poly.is_some() {
             return mat_poly.unwrap().num_vars();
         }
 
         // look up the virtual polynomial
-        let virt_poly = self.virtual_polys.get(&id);
+        let virt_poly = self.get_virt_poly(id);
         if virt_poly.is_none() {
             panic!("Unknown poly id: {:?}", id);
         }
@@ -343,30 +500,45 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
     }
 
     pub fn increase_nv_front(&mut self, poly_id: TrackerID, added_nv: usize) -> TrackerID {
-        self.materialize_poly(poly_id);
-        let base_mle = self.get_mat_poly(poly_id).unwrap();
+        let base_mle = &self.resizable_mle(poly_id);
         let base_nv = base_mle.num_vars();
         let res_mle = dmle_increase_nv_front(base_mle, base_nv + added_nv);
         let res_id = self.track_mat_arc_poly(res_mle);
+        self.resized_polys.insert(res_id, (poly_id, 0)); // padding at the front does not move the query point
         res_id
     }
 
     pub fn increase_nv_back(&mut self, poly_id: TrackerID, added_nv: usize) -> TrackerID {
-        self.materialize_poly(poly_id);
-        let base_mle = self.get_mat_poly(poly_id).unwrap();
+        let base_mle = &self.resizable_mle(poly_id);
         let base_nv = base_mle.num_vars();
         let res_mle = dmle_increase_nv_back(base_mle, base_nv + added_nv);
         let res_id = self.track_mat_arc_poly(res_mle);
+        self.resized_polys.insert(res_id, (poly_id, added_nv));
         res_id
     }
 
+    // returns an mle that agrees with the poly everywhere, not just on the boolean hypercube,
+    // so it can be resized consistently with how the verifier evaluates the poly
+    fn resizable_mle(&mut self, id: TrackerID) -> Arc<DenseMultilinearExtension<E::ScalarField>> {
+        if let Some(mat_poly) = self.get_mat_poly(id) {
+            return mat_poly.clone();
+        }
+        let virt_poly = self.get_virt_poly(id).unwrap_or_else(|| panic!("Unknown poly id: {:?}", id));
+        if virt_poly.iter().any(|(_, prod)| prod.len() > 1) {
+            panic!("Cannot resize poly {:?}: it is a product of polynomials and not multilinear", id);
+        }
+        self.materialize_poly(id);
+        self.get_virt_poly_evals(id).unwrap().clone()
+    }
+
+    // caches the hypercube evaluations of a virtual poly
     fn materialize_poly(&mut self, id: TrackerID) {
         // look up the virtual polynomial
-        let mat_poly = self.materialized_polys.get(&id);
-        if mat_poly.is_some() {
+        let mat_poly = self.get_mat_poly(id);
+        if mat_poly.is_some() || self.get_virt_poly_evals(id).is_some() {
             return // already materialized
         }
-        let virt_poly = self.virtual_polys.get(&id);
+        let virt_poly = self.get_virt_poly(id);
         if virt_poly.is_none() {
             panic!("Unknown poly id: {:?}", id);
         }
@@ -398,21 +570,20 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
                 .collect()
         });
 
-        // cache the result by updating the materialized poly map
+        // cache the result, keeping the virtual poly so later products are not flattened into a single mle
         let mle = Arc::new(DenseMultilinearExtension::from_evaluations_vec(nv, evals));
-        self.materialized_polys.insert(id.clone(), mle);
-        self.virtual_polys.remove(&id);
+        self.virtual_poly_evals.insert(id.clone(), mle);
     }
 
     pub fn evaluate(&self, id: TrackerID, pt: &[E::ScalarField]) -> Option<E::ScalarField>{
         // if the poly is materialized, return the evaluation
-        let mat_poly = self.materialized_polys.get(&id);
+        let mat_poly = self.get_mat_poly(id);
         if mat_poly.is_some() {
             return mat_poly.unwrap().evaluate(pt);
         }
 
         // look up the virtual polynomial
-        let virt_poly = self.virtual_polys.get(&id);
+        let virt_poly = self.get_virt_poly(id);
         if virt_poly.is_none() {
             panic!("Unknown poly id: {:?}", id);
         }
@@ -439,7 +610,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
 
     pub fn evaluations(&mut self, id: TrackerID) -> &Vec<E::ScalarField> {
         self.materialize_poly(id);
-        let mat_poly = self.materialized_polys.get(&id).unwrap();
+        let mat_poly = self.get_mat_poly(id).or(self.get_virt_poly_evals(id)).unwrap();
         return &mat_poly.evaluations;
     }
 
@@ -463,12 +634,12 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
     }
 
     pub fn to_arithmatic_virtual_poly(&self, id: TrackerID) -> VirtualPolynomial<E::ScalarField> {
-        let mat_poly = self.materialized_polys.get(&id);
+        let mat_poly = self.get_mat_poly(id);
         if mat_poly.is_some() {
             return VirtualPolynomial::new_from_mle(mat_poly.unwrap(), E::ScalarField::one());
         }
 
-        let poly = self.virtual_polys.get(&id);
+        let poly = self.get_virt_poly(id);
         if poly.is_none() {
             panic!("Unknown poly id: {:?}", id);
         }
@@ -496,9 +667,10 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
         for (_, poly) in self.materialized_polys.iter_mut() {
             let old_nv = poly.num_vars();
             if old_nv != nv {
-                *poly = dmle_increase_nv_back(poly, nv); // increase_nv_back and increase_nv_front are equivalent, they cause the same sums, just be consistent
+                *poly = dmle_increase_nv_front(poly, nv); // must match how the verifier evaluates smaller polys on the suffix of the point
             }
         }
+        self.virtual_poly_evals.clear(); // cached evaluations are for the old nv
 
         // need to update the claims because resizing messes stuff up
         let old_sumcheck_claims = self.sum_check_claims.clone();
@@ -514,6 +686,71 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
         nv
     }
 
+    // finds every (committed poly, point) pair the verifier needs to evaluate poly_id at point,
+    // following the structure of virtual and resized polynomials down to the committed ones
+    fn collect_comm_queries(
+        &self,
+        poly_id: TrackerID,
+        point: Vec<E::ScalarField>,
+        visited: &mut BTreeSet<(TrackerID, Vec<E::ScalarField>)>,
+        queries: &mut BTreeSet<(TrackerID, Vec<E::ScalarField>)>,
+    ) -> Result<(), PolyIOPErrors> {
+        if !visited.insert((poly_id, point.clone())) {
+            return Ok(());
+        }
+
+        if let Some((base_id, rotation)) = self.get_resized_poly(poly_id) {
+            let base_point = rotate_point_right(&point, *rotation)?;
+            return self.collect_comm_queries(*base_id, base_point, visited, queries);
+        }
+        if self.is_committed(poly_id) {
+            queries.insert((poly_id, point));
+            return Ok(());
+        }
+        if let Some(virt_poly) = self.get_virt_poly(poly_id) {
+            for (_, prod) in virt_poly.iter() {
+                for id in prod.iter() {
+                    self.collect_comm_queries(*id, point.clone(), visited, queries)?;
+                }
+            }
+        }
+        // otherwise the poly is public and the verifier evaluates it on their own
+        Ok(())
+    }
+
+    // the degree of a tracked poly: one for a materialized poly, otherwise the length of its longest product
+    fn poly_degree(&self, id: TrackerID) -> usize {
+        match self.get_virt_poly(id) {
+            Some(virt_poly) => virt_poly.iter().map(|(_, prod)| prod.len()).max().unwrap_or(1),
+            None => 1,
+        }
+    }
+
+    // the max degree of the aggregated sumcheck poly, known before any batching challenge is drawn.
+    // the zerocheck claims are multiplied by eq(x, r), and the aggregation starts from zero polys, so it is at least 2
+    fn aggregated_max_degree(&self) -> usize {
+        let sumcheck_degree = self.sum_check_claims.iter().map(|claim| self.poly_degree(claim.label)).max().unwrap_or(1);
+        let zerocheck_degree = self.zero_check_claims.iter().map(|claim| self.poly_degree(claim.label) + 1).max().unwrap_or(2);
+        sumcheck_degree.max(zerocheck_degree).max(2)
+    }
+
+    // absorbs the aggregation nv and max degree, the claim ordering and the claimed sums into the transcript
+    // so the batching challenges are bound to the claims and to the sumcheck aux info
+    fn append_claims_to_transcript(&mut self, nv: usize, max_degree: usize) -> Result<(), PolyIOPErrors> {
+        self.transcript.append_serializable_element(b"aggregation nv", &(nv as u64))?;
+        self.transcript.append_serializable_element(b"aggregation max degree", &(max_degree as u64))?;
+        self.transcript.append_serializable_element(b"num sumcheck claims", &(self.sum_check_claims.len() as u64))?;
+        for claim in self.sum_check_claims.iter() {
+            self.transcript.append_serializable_element(b"sumcheck claim id", &(claim.label.to_int() as u64))?;
+            self.transcript.append_field_element(b"sumcheck claimed sum", &claim.claimed_sum)?;
+        }
+        self.transcript.append_serializable_element(b"num zerocheck claims", &(self.zero_check_claims.len() as u64))?;
+        for claim in self.zero_check_claims.iter() {
+            self.transcript.append_serializable_element(b"zerocheck claim id", &(claim.label.to_int() as u64))?;
+        }
+        Ok(())
+    }
+
     fn convert_zerocheck_claims_to_sumcheck_claim(&mut self, nv: usize) {
         // 1)   aggregate the zerocheck claims into a single MLE
         let mut zerocheck_agg_poly = self.track_mat_poly(DenseMultilinearExtension::<E::ScalarField>::from_evaluations_vec(nv, vec![E::ScalarField::zero(); 2_usize.pow(nv as u32)]));
@@ -528,8 +765,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
         let r = self.transcript.get_and_append_challenge_vectors(b"0check r", nv).unwrap();
         
         // build the eq(x, r) polynomial
-        let eq_x_r = build_eq_x_r(r.as_ref()).unwrap();
-        let eq_x_r_id = self.track_mat_poly(eq_x_r); // do not need to commit, verifier builds their own
+        let eq_x_r_id = self.track_public_poly(&PublicPoly::Eq { r });
 
         // create the relevant sumcheck claim
         let new_sc_claim_poly = self.mul_polys(zerocheck_agg_poly, eq_x_r_id); // Note: SumCheck val should be zero
@@ -544,7 +780,12 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
         // 3) create a batch opening proofs for the sumcheck point
         // 4) takes all relevant stuff and returns a CompiledProof
 
+        if self.fork.is_some() || self.fork_base.is_some() {
+            return Err(PolyIOPErrors::InvalidProver("compile_proof Error: a fork has not been joined".to_string()));
+        }
         let nv = self.equalize_materialized_poly_nv();
+        let max_degree = self.aggregated_max_degree();
+        self.append_claims_to_transcript(nv, max_degree)?;
 
         // 1)   aggregate the zerocheck claims into a single MLE
         //      Then convert the zerocheck_agg_poly to a sumcheck
@@ -571,47 +812,45 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
         }
         let sc_avp = self.to_arithmatic_virtual_poly(sumcheck_poly);
         let sc_aux_info = sc_avp.aux_info.clone();
+        if sc_aux_info.max_degree != max_degree {
+            let err_msg = "prover_tracker::compile_proof error: the aggregated poly has a different degree than was put in the transcript";
+            return Err(PolyIOPErrors::InvalidProver(err_msg.to_string()));
+        }
         let sc_proof = <PolyIOP<E::ScalarField> as SumCheck<E::ScalarField>>::prove(&sc_avp, &mut self.transcript).unwrap();
         
         // 3) create a batch opening proofs for the sumcheck point
-        //      iterate over sc_avp, grab comms, run PCS batch open, get the proofs
+        //      find every (comm, point) the verifier needs to evaluate the aggregated poly, run PCS batch open, get the proofs
         let sumcheck_point = sc_proof.point.clone();
-        let mut mat_comm_ids = self.materialized_comms.keys().cloned().collect::<Vec<TrackerID>>();
-        mat_comm_ids.sort(); // sort so the transcript is generated consistently
-        let mut mat_polys = Vec::with_capacity(mat_comm_ids.len());
-        let mut points = Vec::with_capacity(mat_comm_ids.len());
-        let mut evals = Vec::with_capacity(mat_comm_ids.len());
-        mat_comm_ids.iter().for_each(|id| {
+        let mut queries = BTreeSet::new(); // sorted so the transcript is generated consistently
+        self.collect_comm_queries(sumcheck_poly, sumcheck_point.clone(), &mut BTreeSet::new(), &mut queries)?;
+        let mut mat_polys = Vec::with_capacity(queries.len());
+        let mut points = Vec::with_capacity(queries.len());
+        let mut evals = Vec::with_capacity(queries.len());
+        for (id, point) in queries.iter() {
             let poly = self.get_mat_poly(id.clone()).unwrap().clone();
-            let eval = poly.evaluate(&sumcheck_point).unwrap();
+            let eval = poly.evaluate(point).unwrap();
             mat_polys.push(poly);
-            points.push(sumcheck_point.clone());
+            points.push(point.clone());
             evals.push(eval);
-        });
-        // println!("mat_polys: {:?}\n", mat_polys);
-        // println!("points: {:?}\n", points);
-        // println!("evals: {:?}\n", evals);
-        // println!("mat_polys.len(): {}\n", mat_polys.len());
-        // println!("points.len(): {}\n", points.len());
-        // println!("evals.len(): {}\n", evals.len());
-        let pcs_proof_res = PCS::multi_open(&self.pcs_param, mat_polys.as_slice(), &points.as_slice(), &evals.as_slice(), &mut self.transcript);
-        let pcs_proof = match pcs_proof_res {
-            Ok(p) => p,
-            Err(e) => {
-                println!("complile_proof errored during PCS multi_open proof");
-                return Err(PolyIOPErrors::InvalidProver(e.to_string()));
-            },
-        };
+        }
+        let mut pcs_proof = Vec::new();
+        if !queries.is_empty() {
+            let pcs_proof_res = PCS::multi_open(self.pcs_param.as_ref(), mat_polys.as_slice(), &points.as_slice(), &evals.as_slice(), &mut self.transcript);
+            match pcs_proof_res {
+                Ok(p) => pcs_proof.push(p),
+                Err(e) => {
+                    println!("complile_proof errored during PCS multi_open proof");
+                    return Err(PolyIOPErrors::InvalidProver(e.to_string()));
+                },
+            };
+        }
 
         // 4) create the CompiledProof
         let mut sumcheck_val_map: HashMap<TrackerID, E::ScalarField> = HashMap::new();
         for claim in self.sum_check_claims.iter() {
             sumcheck_val_map.insert(claim.label.clone(), claim.claimed_sum);
         }
-        let mut query_map: HashMap<(TrackerID, Vec<E::ScalarField>), E::ScalarField> = HashMap::new();
-        mat_comm_ids.iter().for_each(|id| {
-            query_map.insert((id.clone(), sumcheck_point.clone()), E::ScalarField::zero());
-        });
+        let query_map: HashMap<(TrackerID, Vec<E::ScalarField>), E::ScalarField> = queries.into_iter().zip(evals).collect();
 
         Ok(CompiledZKSQLProof {
             sumcheck_claims: sumcheck_val_map, // need to tell verifier sum values from before aggregation
@@ -619,7 +858,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTracker<E, PCS> {
             sc_aux_info,
             query_map,
             comms: self.materialized_comms.clone(),
-            pcs_proof: vec![pcs_proof],
+            pcs_proof,
         })
     }
 }
\ No newline at end of file
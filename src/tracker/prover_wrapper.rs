@@ -12,7 +12,10 @@ use ark_serialize::CanonicalSerialize;
 use crate::arithmetic::VirtualPolynomial;
 
 use crate::tracker::{
+    bag::Bag,
+    iop_tracker::{IOPPoly, IOPTracker, WitnessToken},
     prover_tracker::ProverTracker,
+    public_poly::PublicPoly,
     tracker_structs::{CompiledZKSQLProof, TrackerID},
     errors::PolyIOPErrors,
 };
@@ -22,6 +25,10 @@ use crate::subroutines::{pcs::PolynomialCommitmentScheme, PCSError};
 use crate::transcript::TranscriptError;
 
 
+/// One independent part of a proof, run on its own thread by `ProverTrackerRef::prove_branches`.
+/// Tracked polys can not cross threads, so branches take and return polys by `TrackerID`
+pub type ProverBranch<'a, E, PCS, R> = Box<dyn FnOnce(&mut ProverTrackerRef<E, PCS>) -> Result<R, PolyIOPErrors> + Send + 'a>;
+
 #[derive(Derivative)]
 #[derivative(
     Clone(bound = "PCS: PolynomialCommitmentScheme<E>"),
@@ -57,6 +64,15 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTrackerRef<E, PCS> {
        TrackedPoly::new(res_id, num_vars, self.tracker_rc.clone())
     }
 
+    pub fn track_public_poly(
+        &mut self,
+        polynomial: PublicPoly<E::ScalarField>,
+    ) -> TrackedPoly<E, PCS> {
+        let tracker_ref_cell: &RefCell<ProverTracker<E, PCS>> = self.tracker_rc.borrow();
+        let res_id = tracker_ref_cell.borrow_mut().track_public_poly(&polynomial);
+        TrackedPoly::new(res_id, polynomial.num_vars(), self.tracker_rc.clone())
+    }
+
     pub fn track_and_commit_poly(
         &mut self,
         polynomial: DenseMultilinearExtension<E::ScalarField>,
@@ -77,6 +93,12 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTrackerRef<E, PCS> {
         tracker_ref_cell.borrow().get_virt_poly(id).unwrap().clone()
     }
 
+    pub fn get_tracked_poly(&self, id: TrackerID) -> TrackedPoly<E, PCS> {
+        let tracker_ref_cell: &RefCell<ProverTracker<E, PCS>> = self.tracker_rc.borrow();
+        let num_vars = tracker_ref_cell.borrow().get_poly_nv(id);
+        TrackedPoly::new(id, num_vars, self.tracker_rc.clone())
+    }
+
     pub fn get_and_append_challenge(&mut self, label: &'static [u8]) -> Result<E::ScalarField, TranscriptError> {
         let tracker_ref_cell: &RefCell<ProverTracker<E, PCS>> = self.tracker_rc.borrow();
         tracker_ref_cell.borrow_mut().get_and_append_challenge(label)
@@ -118,6 +140,53 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> ProverTrackerRef<E, PCS> {
         tracker_ref_cell.borrow_mut().compile_proof()
     }
 
+    /// Proves independent parts of a query in parallel, one thread per branch.
+    /// Each branch gets a forked tracker with its own ids and transcript, and the branches are joined back in order,
+    /// so the proof does not depend on thread scheduling. The verifier runs the same branches with `verify_branches`
+    pub fn prove_branches<R: Send>(
+        &mut self,
+        branches: Vec<ProverBranch<'_, E, PCS, R>>,
+    ) -> Result<Vec<R>, PolyIOPErrors>
+    where
+        PCS::ProverParam: Send + Sync,
+        PCS::Commitment: Send + Sync,
+    {
+        let forked = {
+            let tracker_ref_cell: &RefCell<ProverTracker<E, PCS>> = self.tracker_rc.borrow();
+            tracker_ref_cell.borrow_mut().fork(branches.len())?
+        };
+
+        let branch_results: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = forked.into_iter().zip(branches).map(|(branch_tracker, branch)| {
+                scope.spawn(move || {
+                    let mut branch_tracker_ref = ProverTrackerRef::new_from_tracker(branch_tracker);
+                    let res = branch(&mut branch_tracker_ref)?;
+                    Ok::<_, PolyIOPErrors>((branch_tracker_ref.into_tracker()?, res))
+                })
+            }).collect();
+            handles.into_iter().map(|handle| handle.join().unwrap_or_else(|e| std::panic::resume_unwind(e))).collect()
+        });
+
+        let mut branch_trackers = Vec::with_capacity(branch_results.len());
+        let mut results = Vec::with_capacity(branch_results.len());
+        for branch_result in branch_results {
+            let (branch_tracker, res) = branch_result?;
+            branch_trackers.push(branch_tracker);
+            results.push(res);
+        }
+        let tracker_ref_cell: &RefCell<ProverTracker<E, PCS>> = self.tracker_rc.borrow();
+        tracker_ref_cell.borrow_mut().join(branch_trackers)?;
+        Ok(results)
+    }
+
+    // takes the tracker back out once no tracked polys refer to it
+    fn into_tracker(self) -> Result<ProverTracker<E, PCS>, PolyIOPErrors> {
+        match Rc::try_unwrap(self.tracker_rc) {
+            Ok(tracker_ref_cell) => Ok(tracker_ref_cell.into_inner()),
+            Err(_) => Err(PolyIOPErrors::InvalidProver("branch tracker is still in use after the branch returned".to_string())),
+        }
+    }
+
     // used for testing
     pub fn clone_underlying_tracker(&self) -> ProverTracker<E, PCS> {
         let tracker_ref_cell: &RefCell<ProverTracker<E, PCS>> = self.tracker_rc.borrow();
@@ -229,3 +298,79 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> TrackedPoly<E, PCS> {
     }
 }
 
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> IOPTracker<E> for ProverTrackerRef<E, PCS> {
+    type Poly = TrackedPoly<E, PCS>;
+    type Bag = Bag<E, PCS>;
+
+    fn get_and_append_challenge(&mut self, label: &'static [u8]) -> Result<E::ScalarField, PolyIOPErrors> {
+        Ok(ProverTrackerRef::get_and_append_challenge(self, label)?)
+    }
+
+    fn track_public_poly(&mut self, polynomial: PublicPoly<E::ScalarField>) -> Self::Poly {
+        ProverTrackerRef::track_public_poly(self, polynomial)
+    }
+
+    fn commit_witness<W>(&mut self, witness: W) -> Result<Self::Poly, PolyIOPErrors>
+    where
+        W: FnOnce(&WitnessToken) -> Result<DenseMultilinearExtension<E::ScalarField>, PolyIOPErrors>,
+    {
+        let mle = witness(&WitnessToken::new())?;
+        Ok(self.track_and_commit_poly(mle)?)
+    }
+
+    fn add_sumcheck(&mut self, poly: &Self::Poly, claimed_sum: E::ScalarField) {
+        self.add_sumcheck_claim(poly.id, claimed_sum);
+    }
+
+    fn add_zerocheck(&mut self, poly: &Self::Poly) {
+        self.add_zerocheck_claim(poly.id);
+    }
+
+    fn add_prover_sumcheck(&mut self, poly: &Self::Poly) -> Result<E::ScalarField, PolyIOPErrors> {
+        let sum = poly.evaluations().iter().sum::<E::ScalarField>();
+        self.add_sumcheck_claim(poly.id, sum);
+        Ok(sum)
+    }
+
+    fn check(&self, _ok: bool, _err_msg: impl FnOnce() -> String) -> Result<(), PolyIOPErrors> {
+        Ok(())
+    }
+}
+
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> IOPPoly<E::ScalarField> for TrackedPoly<E, PCS> {
+    fn id(&self) -> TrackerID {
+        self.id
+    }
+
+    fn add_poly(&self, other: &Self) -> Self {
+        TrackedPoly::add_poly(self, other)
+    }
+
+    fn sub_poly(&self, other: &Self) -> Self {
+        TrackedPoly::sub_poly(self, other)
+    }
+
+    fn mul_poly(&self, other: &Self) -> Self {
+        TrackedPoly::mul_poly(self, other)
+    }
+
+    fn add_scalar(&self, c: E::ScalarField) -> Self {
+        TrackedPoly::add_scalar(self, c)
+    }
+
+    fn mul_scalar(&self, c: E::ScalarField) -> Self {
+        TrackedPoly::mul_scalar(self, c)
+    }
+
+    fn increase_nv_front(&self, added_nv: usize) -> Self {
+        TrackedPoly::increase_nv_front(self, added_nv)
+    }
+
+    fn increase_nv_back(&self, added_nv: usize) -> Self {
+        TrackedPoly::increase_nv_back(self, added_nv)
+    }
+
+    fn witness_evaluations(&self, _token: &WitnessToken) -> Vec<E::ScalarField> {
+        self.evaluations()
+    }
+}
@@ -0,0 +1,111 @@
+// Public polynomials are known to both the prover and the verifier, so they never need to be committed or opened.
+// The prover materializes them, the verifier evaluates them in O(nv) at the opening point
+
+use ark_ff::PrimeField;
+use ark_poly::DenseMultilinearExtension;
+
+use crate::tracker::{
+    dmle_utils::{build_eq_x_r, eq_eval, point_suffix},
+    errors::PolyIOPErrors,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PublicPoly<F: PrimeField> {
+    /// `value` at every index
+    Constant { num_vars: usize, value: F },
+    /// `i` at index `i`, which is also the range `0..2^num_vars`
+    Identity { num_vars: usize },
+    /// `(i + shift) mod 2^num_vars` at index `i`
+    ShiftedIdentity { num_vars: usize, shift: usize },
+    /// eq(x, r), which is one at the index with the bits of r (when r is boolean) and zero elsewhere
+    Eq { r: Vec<F> },
+    /// one at the first `len` indices and zero elsewhere, e.g. the rows kept by a LIMIT
+    Prefix { num_vars: usize, len: usize },
+}
+
+impl<F: PrimeField> PublicPoly<F> {
+    pub fn num_vars(&self) -> usize {
+        match self {
+            PublicPoly::Constant { num_vars, .. } => *num_vars,
+            PublicPoly::Identity { num_vars } => *num_vars,
+            PublicPoly::ShiftedIdentity { num_vars, .. } => *num_vars,
+            PublicPoly::Eq { r } => r.len(),
+            PublicPoly::Prefix { num_vars, .. } => *num_vars,
+        }
+    }
+
+    pub fn to_mle(&self) -> DenseMultilinearExtension<F> {
+        let nv = self.num_vars();
+        let len = 1_usize << nv;
+        match self {
+            PublicPoly::Constant { value, .. } => DenseMultilinearExtension::from_evaluations_vec(nv, vec![*value; len]),
+            PublicPoly::Identity { .. } => {
+                DenseMultilinearExtension::from_evaluations_vec(nv, (0..len).map(|i| F::from(i as u64)).collect())
+            },
+            PublicPoly::ShiftedIdentity { shift, .. } => {
+                let evals = (0..len).map(|i| F::from(((i + shift) % len) as u64)).collect();
+                DenseMultilinearExtension::from_evaluations_vec(nv, evals)
+            },
+            PublicPoly::Eq { r } => build_eq_x_r(r).unwrap(),
+            PublicPoly::Prefix { len: prefix_len, .. } => {
+                DenseMultilinearExtension::from_evaluations_vec(nv, (0..len).map(|i| if i < *prefix_len { F::one() } else { F::zero() }).collect())
+            },
+        }
+    }
+
+    /// Evaluate at a point of at least `num_vars` variables.
+    /// Following the trackers' padding convention, the poly is evaluated at the last `num_vars` coordinates
+    pub fn evaluate(&self, pt: &[F]) -> Result<F, PolyIOPErrors> {
+        let nv = self.num_vars();
+        let pt = point_suffix(pt, nv)?;
+        let res = match self {
+            PublicPoly::Constant { value, .. } => *value,
+            PublicPoly::Identity { .. } => identity_eval(pt),
+            PublicPoly::ShiftedIdentity { shift, .. } => {
+                // (i + shift) mod 2^nv = i + shift - 2^nv * [i >= 2^nv - shift]
+                let shift = shift % (1 << nv);
+                if shift == 0 {
+                    identity_eval(pt)
+                } else {
+                    let wrap = F::from(2_u64).pow([nv as u64]);
+                    identity_eval(pt) + F::from(shift as u64) - wrap * geq_const_eval(pt, (1 << nv) - shift)
+                }
+            },
+            PublicPoly::Eq { r } => eq_eval(pt, r)?,
+            PublicPoly::Prefix { len, .. } => {
+                // [i < len] = 1 - [i >= len], and every index is below a len of at least 2^nv
+                if *len >= (1 << nv) {
+                    F::one()
+                } else {
+                    F::one() - geq_const_eval(pt, *len)
+                }
+            },
+        };
+        Ok(res)
+    }
+}
+
+// the MLE of i -> i, where the first variable is the least significant bit
+fn identity_eval<F: PrimeField>(pt: &[F]) -> F {
+    let mut res = F::zero();
+    let mut pow = F::one();
+    for x in pt.iter() {
+        res += pow * x;
+        pow.double_in_place();
+    }
+    res
+}
+
+// the MLE of i -> [i >= c], compared from the most significant bit down
+// built up from the least significant bit, g_j = [i mod 2^j >= c mod 2^j]
+fn geq_const_eval<F: PrimeField>(pt: &[F], c: usize) -> F {
+    let mut res = F::one();
+    for (j, x) in pt.iter().enumerate() {
+        if (c >> j) & 1 == 1 {
+            res *= x;
+        } else {
+            res = *x + (F::one() - x) * res;
+        }
+    }
+    res
+}
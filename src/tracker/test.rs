@@ -1,16 +1,21 @@
 #[cfg(test)]
 mod test {
     use std::{
+        cell::Cell,
         ops::Neg,
+        panic::{catch_unwind, AssertUnwindSafe},
         sync::Arc,
     };
     
     use ark_bls12_381::{Bls12_381, Fr};
     use ark_ff::UniformRand;
     use ark_poly::{DenseMultilinearExtension, MultilinearExtension};
-    use ark_std::{One, test_rng, Zero};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::{One, rand::Rng, test_rng, Zero};
     
     use crate::tracker::prelude::*;
+    use crate::tracker::tracker_structs::CompiledZKSQLProof;
+    use crate::tracker::prover_tracker::ProverTracker;
     
     use crate::subroutines::{
         MultilinearKzgPCS,
@@ -401,13 +406,12 @@ mod test {
         // set up to check that an IOP passes
         let resized_sum = resized_poly.evaluations().iter().sum::<Fr>();
         prover_tracker.add_sumcheck_claim(resized_poly.id, resized_sum);
-        let mut proof = prover_tracker.compile_proof()?;
-        proof.query_map.insert((TrackerID(1), resized_point.clone()), eval.clone());
+        let proof = prover_tracker.compile_proof()?;
 
         let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
         verifier_tracker.set_compiled_proof(proof);
-        let og_comm = verifier_tracker.transfer_prover_comm(TrackerID(0));
-        let _ = verifier_tracker.transfer_prover_comm(TrackerID(1)); // to match extra poly above
+        let og_comm = verifier_tracker.transfer_prover_comm(TrackerID(0))?;
+        let _ = verifier_tracker.transfer_prover_comm(TrackerID(1))?; // to match extra poly above
         let resized_comm = og_comm.increase_nv_front(added_nv);
         verifier_tracker.add_sumcheck_claim(resized_comm.id, resized_sum);
 
@@ -452,13 +456,12 @@ mod test {
         // set up to check that an IOP passes
         let resized_sum = resized_poly.evaluations().iter().sum::<Fr>();
         prover_tracker.add_sumcheck_claim(resized_poly.id, resized_sum);
-        let mut proof = prover_tracker.compile_proof()?;
-        proof.query_map.insert((TrackerID(1), resized_point.clone()), eval.clone());
+        let proof = prover_tracker.compile_proof()?;
 
         let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
         verifier_tracker.set_compiled_proof(proof);
-        let og_comm = verifier_tracker.transfer_prover_comm(TrackerID(0));
-        let _ = verifier_tracker.transfer_prover_comm(TrackerID(1)); // to match extra poly above
+        let og_comm = verifier_tracker.transfer_prover_comm(TrackerID(0))?;
+        let _ = verifier_tracker.transfer_prover_comm(TrackerID(1))?; // to match extra poly above
         let resized_comm = og_comm.increase_nv_back(added_nv);
         verifier_tracker.add_sumcheck_claim(resized_comm.id, resized_sum);
 
@@ -472,4 +475,715 @@ mod test {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    // a cheating prover commits to `committed_mle`, then swaps in `tampered_mle` before building the proof.
+    // If `patch_openings` is set, the query map is patched to hold evaluations of the committed column, 
+    // which is what an honest opening of the commitment would reveal
+    fn tampered_column_proof_helper(
+        committed_mle: DenseMultilinearExtension<Fr>,
+        tampered_mle: Option<DenseMultilinearExtension<Fr>>,
+        other_mle: DenseMultilinearExtension<Fr>,
+        use_zerocheck: bool,
+        patch_openings: bool,
+    ) -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = committed_mle.num_vars;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+
+        // prove that col - other is zero everywhere, or sums to zero
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let col = prover_tracker.track_and_commit_poly(committed_mle.clone())?;
+        let other = prover_tracker.track_and_commit_poly(other_mle.clone())?;
+        if let Some(tampered_mle) = tampered_mle {
+            col.tracker.borrow_mut().materialized_polys.insert(col.id, Arc::new(tampered_mle));
+        }
+        let diff = col.sub_poly(&other);
+        if use_zerocheck {
+            prover_tracker.add_zerocheck_claim(diff.id);
+        } else {
+            prover_tracker.add_sumcheck_claim(diff.id, Fr::zero());
+        }
+        let mut proof = prover_tracker.compile_proof()?;
+        if patch_openings {
+            for ((id, point), eval) in proof.query_map.iter_mut() {
+                if *id == col.id {
+                    *eval = committed_mle.evaluate(point).unwrap();
+                }
+            }
+        }
+
+        // verify with the commitments from the proof
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+        verifier_tracker.set_compiled_proof(proof);
+        let col_comm = verifier_tracker.transfer_prover_comm(col.id)?;
+        let other_comm = verifier_tracker.transfer_prover_comm(other.id)?;
+        let diff_comm = col_comm.sub_comms(&other_comm);
+        if use_zerocheck {
+            verifier_tracker.add_zerocheck_claim(diff_comm.id);
+        } else {
+            verifier_tracker.add_sumcheck_claim(diff_comm.id, Fr::zero());
+        }
+        verifier_tracker.verify_claims()
+    }
+
+    #[test]
+    fn test_verify_claims_rejects_tampered_column() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let other_mle = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+
+        // the committed column differs from other_mle in a single entry, 
+        // the prover swaps in a copy of other_mle so the claims hold over the polys it proves with
+        let mut committed_evals = other_mle.evaluations.clone();
+        committed_evals[3] += Fr::one();
+        let committed_mle = DenseMultilinearExtension::from_evaluations_vec(nv, committed_evals);
+
+        // good path: nothing is tampered with
+        tampered_column_proof_helper(other_mle.clone(), None, other_mle.clone(), true, false)?;
+        tampered_column_proof_helper(other_mle.clone(), None, other_mle.clone(), false, false)?;
+
+        // bad path 1: zerocheck claim over the tampered column
+        let bad_result1 = tampered_column_proof_helper(committed_mle.clone(), Some(other_mle.clone()), other_mle.clone(), true, true);
+        assert!(bad_result1.is_err());
+
+        // bad path 2: sumcheck claim over the tampered column
+        let bad_result2 = tampered_column_proof_helper(committed_mle.clone(), Some(other_mle.clone()), other_mle.clone(), false, true);
+        assert!(bad_result2.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_claims_rejects_unauthenticated_evals() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let other_mle = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mut committed_evals = other_mle.evaluations.clone();
+        committed_evals[3] += Fr::one();
+        let committed_mle = DenseMultilinearExtension::from_evaluations_vec(nv, committed_evals);
+
+        // the query map holds evaluations of the tampered column, which are consistent with the sumcheck
+        // but are not openings of the committed column
+        let bad_result1 = tampered_column_proof_helper(committed_mle.clone(), Some(other_mle.clone()), other_mle.clone(), true, false);
+        assert!(bad_result1.is_err());
+        let bad_result2 = tampered_column_proof_helper(committed_mle.clone(), Some(other_mle.clone()), other_mle.clone(), false, false);
+        assert!(bad_result2.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_claims_rejects_mutated_claimed_sum() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+
+        // prove the sums of two committed polys
+        let mle1 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mle2 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let poly1 = prover_tracker.track_and_commit_poly(mle1.clone())?;
+        let poly2 = prover_tracker.track_and_commit_poly(mle2.clone())?;
+        prover_tracker.add_sumcheck_claim(poly1.id, mle1.evaluations.iter().sum::<Fr>());
+        prover_tracker.add_sumcheck_claim(poly2.id, mle2.evaluations.iter().sum::<Fr>());
+        let proof = prover_tracker.compile_proof()?;
+
+        // the verifier reads the claimed sums from the proof
+        let verify = |proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>| -> Result<(), PolyIOPErrors> {
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            verifier_tracker.set_compiled_proof(proof);
+            let comm1 = verifier_tracker.transfer_prover_comm(poly1.id)?;
+            let comm2 = verifier_tracker.transfer_prover_comm(poly2.id)?;
+            let sum1 = verifier_tracker.get_prover_claimed_sum(comm1.id)?;
+            let sum2 = verifier_tracker.get_prover_claimed_sum(comm2.id)?;
+            verifier_tracker.add_sumcheck_claim(comm1.id, sum1);
+            verifier_tracker.add_sumcheck_claim(comm2.id, sum2);
+            verifier_tracker.verify_claims()
+        };
+
+        // good path
+        verify(proof.clone())?;
+
+        // bad path 1: a claimed sum is changed after the proof was compiled
+        let mut bad_proof = proof.clone();
+        *bad_proof.sumcheck_claims.get_mut(&poly1.id).unwrap() += Fr::one();
+        assert!(verify(bad_proof).is_err());
+
+        // bad path 2: both claimed sums are changed so the aggregated sum is the same
+        // under the batching challenges the verifier would draw if the claims were not in the transcript
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+        verifier_tracker.set_compiled_proof(proof.clone());
+        verifier_tracker.transfer_prover_comm(poly1.id)?;
+        verifier_tracker.transfer_prover_comm(poly2.id)?;
+        let mut unbound_transcript = verifier_tracker.clone_underlying_tracker().transcript;
+        unbound_transcript.get_and_append_challenge_vectors(b"0check r", nv)?;
+        let c1 = unbound_transcript.get_and_append_challenge(b"sumcheck challenge")?;
+        let c2 = unbound_transcript.get_and_append_challenge(b"sumcheck challenge")?;
+        let mut bad_proof = proof.clone();
+        *bad_proof.sumcheck_claims.get_mut(&poly1.id).unwrap() += c2;
+        *bad_proof.sumcheck_claims.get_mut(&poly2.id).unwrap() -= c1;
+        assert!(verify(bad_proof).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_claims_rejects_mutated_max_degree() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+
+        // prove the sum of a committed poly
+        let mle = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let poly = prover_tracker.track_and_commit_poly(mle.clone())?;
+        prover_tracker.add_sumcheck_claim(poly.id, mle.evaluations.iter().sum::<Fr>());
+        let proof = prover_tracker.compile_proof()?;
+
+        let verify = |proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>| -> Result<(), PolyIOPErrors> {
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            verifier_tracker.set_compiled_proof(proof);
+            let comm = verifier_tracker.transfer_prover_comm(poly.id)?;
+            let sum = verifier_tracker.get_prover_claimed_sum(comm.id)?;
+            verifier_tracker.add_sumcheck_claim(comm.id, sum);
+            verifier_tracker.verify_claims()
+        };
+
+        // good path
+        verify(proof.clone())?;
+
+        // bad path: the max degree is raised by one, and each round gets the extra evaluation of its univariate poly at the next point,
+        // so the rounds are still consistent with the new degree and only the transcript can catch the change
+        let mut bad_proof = proof.clone();
+        let degree = bad_proof.sc_aux_info.max_degree;
+        bad_proof.sc_aux_info.max_degree = degree + 1;
+        for round in bad_proof.sc_proof.proofs.iter_mut() {
+            // lagrange interpolation of the evaluations at 0..=degree, evaluated at degree + 1
+            let x = Fr::from((degree + 1) as u64);
+            let mut next_eval = Fr::zero();
+            for (i, eval) in round.evaluations.iter().enumerate() {
+                let mut basis = Fr::one();
+                for j in (0..=degree).filter(|j| *j != i) {
+                    basis *= (x - Fr::from(j as u64)) / (Fr::from(i as u64) - Fr::from(j as u64));
+                }
+                next_eval += basis * eval;
+            }
+            round.evaluations.push(next_eval);
+        }
+        assert!(verify(bad_proof).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_proof_serialization() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+
+        // prove a sum and that a committed column is the product of two others
+        let mle1 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mle2 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let prod_evals = mle1.evaluations.iter().zip(mle2.evaluations.iter()).map(|(a, b)| *a * b).collect();
+        let prod_mle = DenseMultilinearExtension::from_evaluations_vec(nv, prod_evals);
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let poly1 = prover_tracker.track_and_commit_poly(mle1.clone())?;
+        let poly2 = prover_tracker.track_and_commit_poly(mle2.clone())?;
+        let prod = prover_tracker.track_and_commit_poly(prod_mle)?;
+        prover_tracker.add_sumcheck_claim(poly1.id, mle1.evaluations.iter().sum::<Fr>());
+        let diff = poly1.mul_poly(&poly2).sub_poly(&prod);
+        prover_tracker.add_zerocheck_claim(diff.id);
+        let proof = prover_tracker.compile_proof()?;
+
+        let verify = |proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>| -> Result<(), PolyIOPErrors> {
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            verifier_tracker.set_compiled_proof(proof);
+            let comm1 = verifier_tracker.transfer_prover_comm(poly1.id)?;
+            let comm2 = verifier_tracker.transfer_prover_comm(poly2.id)?;
+            let prod_comm = verifier_tracker.transfer_prover_comm(prod.id)?;
+            let sum1 = verifier_tracker.get_prover_claimed_sum(comm1.id)?;
+            verifier_tracker.add_sumcheck_claim(comm1.id, sum1);
+            let diff_comm = comm1.mul_comms(&comm2).sub_comms(&prod_comm);
+            verifier_tracker.add_zerocheck_claim(diff_comm.id);
+            verifier_tracker.verify_claims()
+        };
+        let deserialize = |bytes: &[u8]| {
+            CompiledZKSQLProof::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::deserialize_compressed(bytes)
+        };
+
+        // round trip in both modes
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), proof.compressed_size());
+        let decoded = deserialize(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        verify(decoded)?;
+
+        let mut uncompressed_bytes = Vec::new();
+        proof.serialize_uncompressed(&mut uncompressed_bytes).unwrap();
+        assert_eq!(uncompressed_bytes.len(), proof.uncompressed_size());
+        let decoded = CompiledZKSQLProof::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::deserialize_uncompressed(&uncompressed_bytes[..]).unwrap();
+        assert_eq!(decoded, proof);
+
+        // the bytes do not depend on the iteration order of the maps
+        let mut reordered = proof.clone();
+        reordered.comms = proof.comms.clone().into_iter().collect::<Vec<_>>().into_iter().rev().collect();
+        reordered.query_map = proof.query_map.clone().into_iter().collect::<Vec<_>>().into_iter().rev().collect();
+        let mut reordered_bytes = Vec::new();
+        reordered.serialize_compressed(&mut reordered_bytes).unwrap();
+        assert_eq!(reordered_bytes, bytes);
+
+        // truncated bytes
+        for len in [0, 1, 9, bytes.len() / 2, bytes.len() - 1] {
+            assert!(deserialize(&bytes[..len]).is_err());
+        }
+
+        // corrupted header: version, curve id and pcs id
+        for pos in [0, 1, 9] {
+            let mut bad_bytes = bytes.clone();
+            bad_bytes[pos] ^= 1;
+            assert!(deserialize(&bad_bytes).is_err());
+        }
+
+        // corrupted body: either the bytes no longer decode or the decoded proof no longer verifies
+        for pos in (10..bytes.len()).step_by(37) {
+            let mut bad_bytes = bytes.clone();
+            bad_bytes[pos] ^= 1;
+            if let Ok(bad_proof) = deserialize(&bad_bytes) {
+                assert!(verify(bad_proof).is_err(), "corrupted byte {} was accepted", pos);
+            }
+        }
+
+        // commitments out of order
+        let mut sorted_ids: Vec<_> = proof.comms.keys().copied().collect();
+        sorted_ids.sort();
+        let first = 10 + 8;
+        let second = first + 8 + proof.comms[&sorted_ids[0]].compressed_size();
+        let mut bad_bytes = bytes.clone();
+        bad_bytes[first..first + 8].copy_from_slice(&(sorted_ids[1].to_int() as u64).to_le_bytes());
+        bad_bytes[second..second + 8].copy_from_slice(&(sorted_ids[0].to_int() as u64).to_le_bytes());
+        assert!(deserialize(&bad_bytes).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_poly_evaluate() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let r = (0..nv).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let mut polys = vec![
+            PublicPoly::Constant { num_vars: nv, value: Fr::rand(&mut rng) },
+            PublicPoly::Identity { num_vars: nv },
+            PublicPoly::Eq { r },
+        ];
+        for shift in [0, 1, 5, 15, 16, 17] {
+            polys.push(PublicPoly::ShiftedIdentity { num_vars: nv, shift });
+        }
+        for len in [0, 1, 5, 15, 16, 17] {
+            polys.push(PublicPoly::Prefix { num_vars: nv, len });
+        }
+
+        // the succinct evaluation matches the materialized poly on the suffix of a longer point
+        let pt = (0..nv + 2).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        for poly in polys.iter() {
+            let mle = poly.to_mle();
+            assert_eq!(mle.num_vars, nv);
+            assert_eq!(poly.evaluate(&pt)?, mle.evaluate(&pt[2..]).unwrap());
+            assert_eq!(poly.evaluate(&pt[2..])?, mle.evaluate(&pt[2..]).unwrap());
+        }
+        assert_eq!(PublicPoly::<Fr>::ShiftedIdentity { num_vars: 2, shift: 1 }.to_mle().evaluations, vec![Fr::from(1u64), Fr::from(2u64), Fr::from(3u64), Fr::zero()]);
+        assert_eq!(PublicPoly::<Fr>::Prefix { num_vars: 2, len: 3 }.to_mle().evaluations, vec![Fr::one(), Fr::one(), Fr::one(), Fr::zero()]);
+
+        // the point must have at least num_vars coordinates
+        assert!(PublicPoly::<Fr>::Identity { num_vars: nv }.evaluate(&pt[..nv - 1]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_poly_in_proof() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 5;
+        let public_nv = 3;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+        // sums are over the hypercube of the largest poly, on which the smaller polys are repeated
+        let id_sum = Fr::from((0..2_u64.pow(public_nv as u32)).sum::<u64>()) * Fr::from(2_u64.pow((nv - public_nv) as u32));
+
+        // a committed column equals the index poly, and the public polys have the expected sums
+        let ordered_mle = PublicPoly::<Fr>::Identity { num_vars: public_nv }.to_mle();
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let big_poly = prover_tracker.track_and_commit_poly(DenseMultilinearExtension::<Fr>::rand(nv, &mut rng))?;
+        let ordered_poly = prover_tracker.track_and_commit_poly(ordered_mle)?;
+        let id_poly = prover_tracker.track_public_poly(PublicPoly::Identity { num_vars: public_nv });
+        let shifted_poly = prover_tracker.track_public_poly(PublicPoly::ShiftedIdentity { num_vars: public_nv, shift: 3 });
+        let range_bag = Bag::range(&mut prover_tracker, public_nv);
+        let big_sum = big_poly.evaluations().iter().sum::<Fr>();
+        prover_tracker.add_sumcheck_claim(big_poly.id, big_sum);
+        prover_tracker.add_zerocheck_claim(ordered_poly.sub_poly(&id_poly).id);
+        prover_tracker.add_sumcheck_claim(shifted_poly.id, id_sum);
+        prover_tracker.add_sumcheck_claim(range_bag.poly.mul_poly(&range_bag.selector).id, id_sum);
+        let proof = prover_tracker.compile_proof()?;
+
+        // public polys are neither committed nor opened
+        assert_eq!(proof.comms.len(), 2);
+        for (id, _) in proof.query_map.keys() {
+            assert!(*id == big_poly.id || *id == ordered_poly.id);
+        }
+
+        let verify = |proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>, shift: usize| -> Result<(), PolyIOPErrors> {
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            verifier_tracker.set_compiled_proof(proof);
+            let big_comm = verifier_tracker.transfer_prover_comm(big_poly.id)?;
+            let ordered_comm = verifier_tracker.transfer_prover_comm(ordered_poly.id)?;
+            let id_comm = verifier_tracker.track_public_poly(PublicPoly::Identity { num_vars: public_nv });
+            let shifted_comm = verifier_tracker.track_public_poly(PublicPoly::ShiftedIdentity { num_vars: public_nv, shift });
+            let range_bag_comm = BagComm::range(&mut verifier_tracker, public_nv);
+            let big_sum = verifier_tracker.get_prover_claimed_sum(big_comm.id)?;
+            verifier_tracker.add_sumcheck_claim(big_comm.id, big_sum);
+            verifier_tracker.add_zerocheck_claim(ordered_comm.sub_comms(&id_comm).id);
+            verifier_tracker.add_sumcheck_claim(shifted_comm.id, id_sum);
+            verifier_tracker.add_sumcheck_claim(range_bag_comm.poly.mul_comms(&range_bag_comm.selector).id, id_sum);
+            verifier_tracker.verify_claims()
+        };
+
+        // good path
+        verify(proof.clone(), 3)?;
+
+        // bad path: the verifier's public poly differs from the prover's
+        assert!(verify(proof.clone(), 2).is_err());
+
+        Ok(())
+    }
+
+    // commits to the squares of the index poly and checks them, written once for both trackers
+    fn square_circuit<T: IOPTracker<Bls12_381>>(
+        tracker: &mut T,
+        nv: usize,
+        witness_ran: &Cell<bool>,
+    ) -> Result<TrackerID, PolyIOPErrors> {
+        let id_poly = tracker.track_public_poly(PublicPoly::Identity { num_vars: nv });
+        let square = tracker.commit_witness(|token| {
+            witness_ran.set(true);
+            let evals = id_poly.witness_evaluations(token).iter().map(|x| *x * x).collect();
+            Ok(DenseMultilinearExtension::from_evaluations_vec(nv, evals))
+        })?;
+        tracker.add_zerocheck(&square.sub_poly(&id_poly.mul_poly(&id_poly)));
+        let sum = tracker.add_prover_sumcheck(&square)?;
+        let expected_sum = Fr::from((0..2_u64.pow(nv as u32)).map(|i| i * i).sum::<u64>());
+        tracker.check(sum == expected_sum, || format!("square_circuit Error: wrong sum {}", sum))?;
+        Ok(square.id())
+    }
+
+    #[test]
+    fn test_iop_tracker_circuit() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+
+        let prover_witness_ran = Cell::new(false);
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let prover_id = square_circuit(&mut prover_tracker, nv, &prover_witness_ran)?;
+        let proof = prover_tracker.compile_proof()?;
+        assert!(prover_witness_ran.get());
+
+        let verify = |proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>| -> Result<(), PolyIOPErrors> {
+            let verifier_witness_ran = Cell::new(false);
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            verifier_tracker.set_compiled_proof(proof);
+            let verifier_id = square_circuit(&mut verifier_tracker, nv, &verifier_witness_ran)?;
+            assert_eq!(verifier_id, prover_id);
+            assert!(!verifier_witness_ran.get());
+            verifier_tracker.verify_claims()
+        };
+        verify(proof.clone())?;
+
+        // a wrong claimed sum is caught by the circuit's check
+        let mut bad_proof = proof.clone();
+        for sum in bad_proof.sumcheck_claims.values_mut() {
+            *sum += Fr::one();
+        }
+        assert!(verify(bad_proof).is_err());
+
+        // a proof without the witness commitment is rejected instead of panicking
+        let mut bad_proof = proof.clone();
+        bad_proof.comms.clear();
+        assert!(verify(bad_proof).is_err());
+
+        Ok(())
+    }
+
+    // a sub-query for test_prove_branches: commits to the square of its input and checks it against a challenge
+    fn square_branch<T: IOPTracker<Bls12_381>>(tracker: &mut T, input: &T::Poly, nv: usize) -> Result<TrackerID, PolyIOPErrors> {
+        let square = tracker.commit_witness(|token| {
+            let evals = input.witness_evaluations(token).iter().map(|x| *x * x).collect();
+            Ok(DenseMultilinearExtension::from_evaluations_vec(nv, evals))
+        })?;
+        let r = tracker.get_and_append_challenge(b"r")?;
+        tracker.add_zerocheck(&square.sub_poly(&input.mul_poly(input)).mul_scalar(r));
+        Ok(square.id())
+    }
+
+    #[test]
+    fn test_prove_branches() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 4;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+        let mle1 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mle2 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+
+        // two independent sub-queries on their own threads, whose outputs are used after the join
+        let prove = || -> Result<CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>, PolyIOPErrors> {
+            let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param.clone());
+            let poly1_id = prover_tracker.track_and_commit_poly(mle1.clone())?.id;
+            let poly2_id = prover_tracker.track_and_commit_poly(mle2.clone())?.id;
+            let branches: Vec<ProverBranch<Bls12_381, MultilinearKzgPCS<Bls12_381>, TrackerID>> = vec![
+                Box::new(move |tracker| { let input = tracker.get_tracked_poly(poly1_id); square_branch(tracker, &input, nv) }),
+                Box::new(move |tracker| { let input = tracker.get_tracked_poly(poly2_id); square_branch(tracker, &input, nv) }),
+            ];
+            let square_ids = prover_tracker.prove_branches(branches)?;
+            let squares_sum = prover_tracker.get_tracked_poly(square_ids[0]).add_poly(&prover_tracker.get_tracked_poly(square_ids[1]));
+            prover_tracker.add_sumcheck_claim(squares_sum.id, squares_sum.evaluations().iter().sum::<Fr>());
+            prover_tracker.compile_proof()
+        };
+        let proof = prove()?;
+
+        // the proof does not depend on how the threads were scheduled
+        for _ in 0..3 {
+            assert_eq!(prove()?, proof);
+        }
+
+        let verify = |proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>, swap_branches: bool| -> Result<(), PolyIOPErrors> {
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            verifier_tracker.set_compiled_proof(proof);
+            let mut comm_ids = [verifier_tracker.transfer_prover_comm(TrackerID(0))?.id, verifier_tracker.transfer_prover_comm(TrackerID(1))?.id];
+            if swap_branches {
+                comm_ids.swap(0, 1);
+            }
+            let branches: Vec<VerifierBranch<Bls12_381, MultilinearKzgPCS<Bls12_381>, TrackerID>> = vec![
+                Box::new(|tracker| { let input = tracker.get_tracked_comm(comm_ids[0]); square_branch(tracker, &input, nv) }),
+                Box::new(|tracker| { let input = tracker.get_tracked_comm(comm_ids[1]); square_branch(tracker, &input, nv) }),
+            ];
+            let square_ids = verifier_tracker.verify_branches(branches)?;
+            let squares_sum = verifier_tracker.get_tracked_comm(square_ids[0]).add_comms(&verifier_tracker.get_tracked_comm(square_ids[1]));
+            let sum = verifier_tracker.get_prover_claimed_sum(squares_sum.id)?;
+            verifier_tracker.add_sumcheck_claim(squares_sum.id, sum);
+            verifier_tracker.verify_claims()
+        };
+        verify(proof.clone(), false)?;
+        assert!(verify(proof, true).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_rejects_mismatched_branches() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 2;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_param, _) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+        let mle = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mut tracker = ProverTracker::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::new(pcs_param.clone());
+        let mut other_tracker = ProverTracker::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::new(pcs_param);
+        let poly_id = tracker.track_mat_poly(mle.clone());
+        other_tracker.track_mat_poly(mle.clone());
+
+        // branches read the polys tracked before the fork without owning a copy of them
+        let branches = tracker.fork(2)?;
+        assert!(branches.iter().all(|branch| branch.materialized_polys.is_empty()));
+        assert_eq!(branches[1].get_mat_poly(poly_id).map(|poly| poly.evaluations.clone()), Some(mle.evaluations.clone()));
+        let other_branches = other_tracker.fork(2)?;
+
+        // too few branches, branches out of order, and branches of another fork are rejected
+        let [branch0, branch1]: [_; 2] = branches.try_into().ok().unwrap();
+        assert!(tracker.join(vec![branch0.clone()]).is_err());
+        assert!(tracker.join(vec![branch1.clone(), branch0.clone()]).is_err());
+        assert!(tracker.join(other_branches).is_err());
+
+        // a copy of a branch still reading the fork's polys keeps them from being taken back
+        let branch0_copy = branch0.clone();
+        assert!(tracker.join(vec![branch0.clone(), branch1.clone()]).is_err());
+        drop(branch0_copy);
+        assert!(tracker.fork(2).is_err());
+
+        let mut branch0 = branch0;
+        let branch_poly_id = branch0.track_mat_poly(mle.clone());
+        tracker.join(vec![branch0, branch1])?;
+        assert!(tracker.get_mat_poly(poly_id).is_some());
+        assert!(tracker.get_mat_poly(branch_poly_id).is_some());
+        assert!(tracker.join(Vec::new()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifier_rejects_mutated_proofs() -> Result<(), PolyIOPErrors> {
+        let mut rng = test_rng();
+        let nv = 3;
+        let small_nv = 2;
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+
+        // a proof with committed, virtual, resized and public polys, and both kinds of claims
+        let mle1 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let mle2 = DenseMultilinearExtension::<Fr>::rand(nv, &mut rng);
+        let prod_evals = mle1.evaluations.iter().zip(mle2.evaluations.iter()).map(|(a, b)| *a * b).collect();
+        let prod_mle = DenseMultilinearExtension::from_evaluations_vec(nv, prod_evals);
+        let small_mle = DenseMultilinearExtension::<Fr>::rand(small_nv, &mut rng);
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let poly1 = prover_tracker.track_and_commit_poly(mle1)?;
+        let poly2 = prover_tracker.track_and_commit_poly(mle2)?;
+        let prod = prover_tracker.track_and_commit_poly(prod_mle)?;
+        let small = prover_tracker.track_and_commit_poly(small_mle)?;
+        prover_tracker.add_zerocheck_claim(poly1.mul_poly(&poly2).sub_poly(&prod).id);
+        let id_poly = prover_tracker.track_public_poly(PublicPoly::Identity { num_vars: nv });
+        let weighted = poly2.mul_poly(&id_poly);
+        prover_tracker.add_sumcheck_claim(weighted.id, weighted.evaluations().iter().sum::<Fr>());
+        let resized = small.increase_nv_back(nv - small_nv);
+        prover_tracker.add_sumcheck_claim(resized.id, resized.evaluations().iter().sum::<Fr>());
+        let proof = prover_tracker.compile_proof()?;
+
+        let verify = |proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>| -> Result<(), PolyIOPErrors> {
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            verifier_tracker.set_compiled_proof(proof);
+            let comm1 = verifier_tracker.transfer_prover_comm(poly1.id)?;
+            let comm2 = verifier_tracker.transfer_prover_comm(poly2.id)?;
+            let prod_comm = verifier_tracker.transfer_prover_comm(prod.id)?;
+            let small_comm = verifier_tracker.transfer_prover_comm(small.id)?;
+            verifier_tracker.add_zerocheck_claim(comm1.mul_comms(&comm2).sub_comms(&prod_comm).id);
+            let id_comm = verifier_tracker.track_public_poly(PublicPoly::Identity { num_vars: nv });
+            let weighted_comm = comm2.mul_comms(&id_comm);
+            let weighted_sum = verifier_tracker.get_prover_claimed_sum(weighted_comm.id)?;
+            verifier_tracker.add_sumcheck_claim(weighted_comm.id, weighted_sum);
+            let resized_comm = small_comm.increase_nv_back(nv - small_nv);
+            let resized_sum = verifier_tracker.get_prover_claimed_sum(resized_comm.id)?;
+            verifier_tracker.add_sumcheck_claim(resized_comm.id, resized_sum);
+            verifier_tracker.verify_claims()
+        };
+        verify(proof.clone())?;
+
+        // the verifier has to return an error, and must not panic
+        let assert_rejected = |bad_proof: CompiledZKSQLProof<Bls12_381, MultilinearKzgPCS<Bls12_381>>, mutation: &str| {
+            let res = catch_unwind(AssertUnwindSafe(|| verify(bad_proof)));
+            assert!(res.is_ok(), "verifier panicked on {}", mutation);
+            assert!(res.unwrap().is_err(), "verifier accepted {}", mutation);
+        };
+        let assert_bytes_rejected = |bad_bytes: &[u8], mutation: &str| {
+            let res = catch_unwind(AssertUnwindSafe(|| {
+                CompiledZKSQLProof::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::deserialize_compressed(bad_bytes)
+            }));
+            assert!(res.is_ok(), "deserialization panicked on {}", mutation);
+            if let Ok(bad_proof) = res.unwrap() {
+                if bad_proof != proof {
+                    assert_rejected(bad_proof, mutation);
+                }
+            }
+        };
+
+        // mutations of the serialized proof
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        for i in 0..256 {
+            let mut bad_bytes = bytes.clone();
+            let pos = rng.gen_range(0..bytes.len());
+            bad_bytes[pos] ^= rng.gen_range(1..=255_u8);
+            assert_bytes_rejected(&bad_bytes, &format!("byte mutation {} at {}", i, pos));
+        }
+        for i in 0..64 {
+            let mut bad_bytes = bytes.clone();
+            let start = rng.gen_range(0..bytes.len());
+            let end = (start + rng.gen_range(1..16)).min(bytes.len());
+            rng.fill(&mut bad_bytes[start..end]);
+            assert_bytes_rejected(&bad_bytes, &format!("overwrite {} of {}..{}", i, start, end));
+        }
+        for i in 0..32 {
+            let len = rng.gen_range(0..bytes.len());
+            assert_bytes_rejected(&bytes[..len], &format!("truncation {} to {}", i, len));
+        }
+        for i in 0..32 {
+            let mut bad_bytes = bytes.clone();
+            let pos = rng.gen_range(0..bytes.len());
+            if i % 2 == 0 {
+                bad_bytes.remove(pos);
+            } else {
+                bad_bytes.insert(pos, rng.gen());
+            }
+            assert_bytes_rejected(&bad_bytes, &format!("splice {} at {}", i, pos));
+        }
+
+        // structural mutations that still decode
+        let mut sorted_comm_ids: Vec<_> = proof.comms.keys().copied().collect();
+        sorted_comm_ids.sort();
+        for id in sorted_comm_ids.iter() {
+            let mut bad_proof = proof.clone();
+            bad_proof.comms.remove(id);
+            assert_rejected(bad_proof, &format!("missing comm {}", id));
+        }
+        for id in proof.sumcheck_claims.keys() {
+            let mut bad_proof = proof.clone();
+            bad_proof.sumcheck_claims.remove(id);
+            assert_rejected(bad_proof, &format!("missing claimed sum {}", id));
+        }
+        for key in proof.query_map.keys() {
+            let mut bad_proof = proof.clone();
+            bad_proof.query_map.remove(key);
+            assert_rejected(bad_proof, &format!("missing query {}", key.0));
+
+            let mut bad_proof = proof.clone();
+            let eval = bad_proof.query_map.remove(key).unwrap();
+            let mut long_point = key.1.clone();
+            long_point.push(Fr::one());
+            bad_proof.query_map.insert((key.0, long_point), eval);
+            assert_rejected(bad_proof, &format!("query {} with a longer point", key.0));
+        }
+        for max_degree in [0, 1, 100, usize::MAX] {
+            let mut bad_proof = proof.clone();
+            bad_proof.sc_aux_info.max_degree = max_degree;
+            assert_rejected(bad_proof, &format!("max degree {}", max_degree));
+        }
+        for num_variables in [0, nv - 1, nv + 1, usize::MAX] {
+            let mut bad_proof = proof.clone();
+            bad_proof.sc_aux_info.num_variables = num_variables;
+            assert_rejected(bad_proof, &format!("{} sumcheck variables", num_variables));
+        }
+        let mut bad_proof = proof.clone();
+        bad_proof.sc_proof.proofs.pop();
+        assert_rejected(bad_proof, "missing sumcheck round");
+        for round in 0..nv {
+            let mut bad_proof = proof.clone();
+            bad_proof.sc_proof.proofs[round].evaluations.clear();
+            assert_rejected(bad_proof, &format!("empty sumcheck round {}", round));
+        }
+        let mut bad_proof = proof.clone();
+        bad_proof.sc_proof.point.clear();
+        assert_rejected(bad_proof, "empty sumcheck point");
+        let mut bad_proof = proof.clone();
+        bad_proof.pcs_proof.clear();
+        assert_rejected(bad_proof, "missing PCS proof");
+        let mut bad_proof = proof.clone();
+        bad_proof.pcs_proof.push(proof.pcs_proof[0].clone());
+        assert_rejected(bad_proof, "extra PCS proof");
+        let mut bad_proof = proof.clone();
+        bad_proof.pcs_proof[0].f_i_eval_at_point_i.pop();
+        assert_rejected(bad_proof, "missing PCS evaluation");
+        let mut bad_proof = proof.clone();
+        bad_proof.pcs_proof[0].sum_check_proof.proofs.clear();
+        assert_rejected(bad_proof, "empty PCS sumcheck");
+        let mut bad_proof = proof.clone();
+        bad_proof.pcs_proof[0].sum_check_proof.point.clear();
+        assert_rejected(bad_proof, "empty PCS sumcheck point");
+        let mut bad_proof = proof.clone();
+        bad_proof.pcs_proof[0].g_prime_proof.proofs.pop();
+        assert_rejected(bad_proof, "short PCS opening proof");
+        assert_rejected(CompiledZKSQLProof::default(), "empty proof");
+
+        Ok(())
+    }
+}
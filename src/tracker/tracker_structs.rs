@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::io::{Read, Write};
 use std::marker::PhantomData;
 
-use ark_ec::pairing::Pairing;
+use ark_ec::{pairing::Pairing, AffineRepr};
 use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate};
 use derivative::Derivative;
+use merlin::Transcript;
 
 use crate::arithmetic::VPAuxInfo;
+use crate::tracker::errors::PolyIOPErrors;
 use crate::subroutines::{IOPProof, PolynomialCommitmentScheme};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd,Ord)]
@@ -52,6 +56,7 @@ impl <F: PrimeField> TrackerZerocheckClaim<F> {
     Clone(bound = "PCS: PolynomialCommitmentScheme<E>"),
     Default(bound = "PCS: PolynomialCommitmentScheme<E>"),
     Debug(bound = "PCS: PolynomialCommitmentScheme<E>"),
+    PartialEq(bound = "PCS: PolynomialCommitmentScheme<E>"),
 )]
 pub struct CompiledZKSQLProof<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
     pub comms: HashMap<TrackerID, PCS::Commitment>,
@@ -60,4 +65,206 @@ pub struct CompiledZKSQLProof<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
     pub sc_aux_info: VPAuxInfo<E::ScalarField>,
     pub query_map: HashMap<(TrackerID, Vec<E::ScalarField>), E::ScalarField>, // (id, point) -> eval, // id -> p(comm_opening_point) 
     pub pcs_proof: Vec<PCS::BatchProof>,
-}
\ No newline at end of file
+}
+
+/// Most ids a branch can reserve, so a top level fork does not use up the id space
+pub const MAX_BRANCH_IDS: usize = u32::MAX as usize;
+
+/// Splits the free ids of a tracker into one range per branch, as `(start, end)` pairs.
+/// Both trackers call this with the same counter and limit, so they agree on the ids each branch uses.
+/// One more range than needed is set aside so the parent can keep creating polys after the branches are joined
+pub fn branch_id_ranges(id_counter: usize, id_limit: usize, num_branches: usize) -> Result<Vec<(usize, usize)>, PolyIOPErrors> {
+    if num_branches == 0 {
+        return Err(PolyIOPErrors::InvalidParameters("branch_id_ranges Error: no branches".to_string()));
+    }
+    let range_len = (id_limit.saturating_sub(id_counter) / (num_branches + 1)).min(MAX_BRANCH_IDS);
+    if range_len == 0 {
+        return Err(PolyIOPErrors::InvalidParameters("branch_id_ranges Error: not enough ids left to branch".to_string()));
+    }
+    Ok((0..num_branches).map(|i| (id_counter + i * range_len, id_counter + (i + 1) * range_len)).collect())
+}
+
+/// Version of the serialized `CompiledZKSQLProof` layout, bumped on any change to it
+pub const PROOF_FORMAT_VERSION: u8 = 1;
+
+/// A fingerprint of the pairing, taken over the serialized generators of both groups
+pub fn curve_id<E: Pairing>() -> u64 {
+    let mut g1_bytes = Vec::new();
+    let mut g2_bytes = Vec::new();
+    E::G1Affine::generator().serialize_compressed(&mut g1_bytes).unwrap();
+    E::G2Affine::generator().serialize_compressed(&mut g2_bytes).unwrap();
+
+    let mut transcript = Transcript::new(b"zksql curve id");
+    transcript.append_message(b"g1", &g1_bytes);
+    transcript.append_message(b"g2", &g2_bytes);
+    let mut buf = [0u8; 8];
+    transcript.challenge_bytes(b"id", &mut buf);
+    u64::from_le_bytes(buf)
+}
+
+impl CanonicalSerialize for TrackerID {
+    fn serialize_with_mode<W: Write>(&self, writer: W, compress: Compress) -> Result<(), SerializationError> {
+        (self.0 as u64).serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        (self.0 as u64).serialized_size(compress)
+    }
+}
+
+impl Valid for TrackerID {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for TrackerID {
+    fn deserialize_with_mode<R: Read>(reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        let id = u64::deserialize_with_mode(reader, compress, validate)?;
+        usize::try_from(id).map(TrackerID).map_err(|_| SerializationError::InvalidData)
+    }
+}
+
+// Layout: version (u8) | curve id (u64) | pcs id (u8) | comms | sumcheck claims | sc proof | sc aux info | query map | pcs proofs
+// Maps are written as a u64 length followed by their entries in strictly increasing key order,
+// with tracker ids encoded as u64
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> CanonicalSerialize for CompiledZKSQLProof<E, PCS> {
+    fn serialize_with_mode<W: Write>(&self, mut writer: W, compress: Compress) -> Result<(), SerializationError> {
+        PROOF_FORMAT_VERSION.serialize_with_mode(&mut writer, compress)?;
+        curve_id::<E>().serialize_with_mode(&mut writer, compress)?;
+        PCS::PCS_ID.serialize_with_mode(&mut writer, compress)?;
+
+        let mut comms: Vec<_> = self.comms.iter().collect();
+        comms.sort_by_key(|(id, _)| **id);
+        (comms.len() as u64).serialize_with_mode(&mut writer, compress)?;
+        for (id, comm) in comms {
+            id.serialize_with_mode(&mut writer, compress)?;
+            comm.serialize_with_mode(&mut writer, compress)?;
+        }
+
+        let mut sumcheck_claims: Vec<_> = self.sumcheck_claims.iter().collect();
+        sumcheck_claims.sort_by_key(|(id, _)| **id);
+        (sumcheck_claims.len() as u64).serialize_with_mode(&mut writer, compress)?;
+        for (id, sum) in sumcheck_claims {
+            id.serialize_with_mode(&mut writer, compress)?;
+            sum.serialize_with_mode(&mut writer, compress)?;
+        }
+
+        self.sc_proof.serialize_with_mode(&mut writer, compress)?;
+        self.sc_aux_info.serialize_with_mode(&mut writer, compress)?;
+
+        let mut queries: Vec<_> = self.query_map.iter().collect();
+        queries.sort_by_key(|(key, _)| *key);
+        (queries.len() as u64).serialize_with_mode(&mut writer, compress)?;
+        for ((id, point), eval) in queries {
+            id.serialize_with_mode(&mut writer, compress)?;
+            point.serialize_with_mode(&mut writer, compress)?;
+            eval.serialize_with_mode(&mut writer, compress)?;
+        }
+
+        self.pcs_proof.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        let header_size = PROOF_FORMAT_VERSION.serialized_size(compress)
+            + curve_id::<E>().serialized_size(compress)
+            + PCS::PCS_ID.serialized_size(compress);
+        let len_size = 0u64.serialized_size(compress);
+
+        let comms_size: usize = self.comms.iter()
+            .map(|(id, comm)| id.serialized_size(compress) + comm.serialized_size(compress))
+            .sum();
+        let sumcheck_claims_size: usize = self.sumcheck_claims.iter()
+            .map(|(id, sum)| id.serialized_size(compress) + sum.serialized_size(compress))
+            .sum();
+        let queries_size: usize = self.query_map.iter()
+            .map(|((id, point), eval)| id.serialized_size(compress) + point.serialized_size(compress) + eval.serialized_size(compress))
+            .sum();
+
+        header_size
+            + len_size + comms_size
+            + len_size + sumcheck_claims_size
+            + self.sc_proof.serialized_size(compress)
+            + self.sc_aux_info.serialized_size(compress)
+            + len_size + queries_size
+            + self.pcs_proof.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> Valid for CompiledZKSQLProof<E, PCS> {
+    fn check(&self) -> Result<(), SerializationError> {
+        for comm in self.comms.values() {
+            comm.check()?;
+        }
+        self.sc_proof.check()?;
+        self.pcs_proof.check()
+    }
+}
+
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> CanonicalDeserialize for CompiledZKSQLProof<E, PCS> {
+    fn deserialize_with_mode<R: Read>(mut reader: R, compress: Compress, validate: Validate) -> Result<Self, SerializationError> {
+        // reject proofs written for another layout, curve or commitment scheme before reading the body
+        let version = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        let curve = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        let pcs_id = u8::deserialize_with_mode(&mut reader, compress, validate)?;
+        if version != PROOF_FORMAT_VERSION || curve != curve_id::<E>() || pcs_id != PCS::PCS_ID {
+            return Err(SerializationError::InvalidData);
+        }
+
+        // lengths come off the wire, so nothing is preallocated from them
+        let mut comms = HashMap::new();
+        let mut prev_id = None;
+        let num_comms = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        for _ in 0..num_comms {
+            let id = TrackerID::deserialize_with_mode(&mut reader, compress, validate)?;
+            if prev_id >= Some(id) {
+                return Err(SerializationError::InvalidData);
+            }
+            prev_id = Some(id);
+            let comm = PCS::Commitment::deserialize_with_mode(&mut reader, compress, validate)?;
+            comms.insert(id, comm);
+        }
+
+        let mut sumcheck_claims = HashMap::new();
+        let mut prev_id = None;
+        let num_sumcheck_claims = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        for _ in 0..num_sumcheck_claims {
+            let id = TrackerID::deserialize_with_mode(&mut reader, compress, validate)?;
+            if prev_id >= Some(id) {
+                return Err(SerializationError::InvalidData);
+            }
+            prev_id = Some(id);
+            let sum = E::ScalarField::deserialize_with_mode(&mut reader, compress, validate)?;
+            sumcheck_claims.insert(id, sum);
+        }
+
+        let sc_proof = IOPProof::deserialize_with_mode(&mut reader, compress, validate)?;
+        let sc_aux_info = VPAuxInfo::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        let mut query_map = HashMap::new();
+        let mut prev_key: Option<(TrackerID, Vec<E::ScalarField>)> = None;
+        let num_queries = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        for _ in 0..num_queries {
+            let id = TrackerID::deserialize_with_mode(&mut reader, compress, validate)?;
+            let point = Vec::<E::ScalarField>::deserialize_with_mode(&mut reader, compress, validate)?;
+            let key = (id, point);
+            if prev_key.as_ref() >= Some(&key) {
+                return Err(SerializationError::InvalidData);
+            }
+            let eval = E::ScalarField::deserialize_with_mode(&mut reader, compress, validate)?;
+            query_map.insert(key.clone(), eval);
+            prev_key = Some(key);
+        }
+
+        let pcs_proof = Vec::<PCS::BatchProof>::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        Ok(Self {
+            comms,
+            sumcheck_claims,
+            sc_proof,
+            sc_aux_info,
+            query_map,
+            pcs_proof,
+        })
+    }
+}
@@ -20,7 +20,8 @@ use ark_std::Zero;
 
 use crate::tracker::errors::PolyIOPErrors;
 use crate::tracker::tracker_structs::{TrackerID, CompiledZKSQLProof, TrackerSumcheckClaim, TrackerZerocheckClaim};
-use crate::tracker::dmle_utils::eq_eval;
+use crate::tracker::dmle_utils::rotate_point_right;
+use crate::tracker::public_poly::PublicPoly;
 
 use derivative::Derivative;
 use displaydoc::Display;
@@ -30,6 +31,10 @@ use crate::subroutines::poly_iop::prelude::SumCheck;
 
 use crate::transcript::{IOPTranscript, TranscriptError};
 
+fn unknown_comm_err(id: TrackerID) -> PolyIOPErrors {
+    PolyIOPErrors::InvalidVerifier(format!("unknown comm {}", id))
+}
+
 #[derive(Derivative, Display)]
 #[derivative(
     Clone(bound = "PCS: PolynomialCommitmentScheme<E>"),
@@ -38,6 +43,7 @@ pub struct VerifierTracker<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
     pub pcs_params: PCS::VerifierParam,
     pub transcript: IOPTranscript<E::ScalarField>,
     pub id_counter: usize,
+    pub id_limit: usize, // ids at or past the limit belong to another branch
     pub materialized_comms: HashMap<TrackerID, PCS::Commitment>, // map from id to Commitment
     pub virtual_comms: Rc<RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>>>, // id -> eval_fn
     pub query_map: Rc<RefCell<HashMap<(TrackerID, Vec<E::ScalarField>), E::ScalarField>>>, // (poly_id, point) -> eval
@@ -52,6 +58,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
             pcs_params,
             transcript: IOPTranscript::<E::ScalarField>::new(b"Initializing Tracnscript"),
             id_counter: 0,
+            id_limit: usize::MAX,
             materialized_comms: HashMap::new(),
             virtual_comms: Rc::new(RefCell::new(HashMap::new())),
             query_map: Rc::new(RefCell::new(HashMap::new())),
@@ -71,6 +78,44 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
         TrackerID(self.id_counter)
     }
 
+    /// Moves the tracker into branch `index` of a fork, mirroring `ProverTracker::fork`.
+    /// The verifier runs the branches one after another, each starting from the transcript at the fork
+    pub fn enter_branch(
+        &mut self,
+        fork_transcript: &IOPTranscript<E::ScalarField>,
+        index: usize,
+        (start, end): (usize, usize),
+    ) -> Result<(), PolyIOPErrors> {
+        self.transcript = fork_transcript.clone();
+        self.transcript.append_serializable_element(b"branch", &(index as u64))?;
+        self.id_counter = start;
+        self.id_limit = end;
+        Ok(())
+    }
+
+    /// Returns to the parent after all branches of a fork have run, mirroring `ProverTracker::join`
+    pub fn join_branches(
+        &mut self,
+        fork_transcript: IOPTranscript<E::ScalarField>,
+        ranges: &[(usize, usize)],
+        id_limit: usize,
+    ) -> Result<(), PolyIOPErrors> {
+        self.transcript = fork_transcript;
+        for (start, end) in ranges.iter().copied() {
+            let mut comm_ids: Vec<TrackerID> = self.materialized_comms.keys()
+                .filter(|id| id.to_int() >= start && id.to_int() < end)
+                .copied()
+                .collect();
+            comm_ids.sort();
+            for id in comm_ids {
+                self.transcript.append_serializable_element(b"branch comm", &self.materialized_comms[&id])?;
+            }
+        }
+        self.id_counter = ranges.last().map_or(self.id_counter, |range| range.1);
+        self.id_limit = id_limit;
+        Ok(())
+    }
+
     pub fn track_mat_comm(&mut self, comm: PCS::Commitment) -> Result<TrackerID, PolyIOPErrors> {
         // Create the new TrackerID
         let id = self.gen_id();
@@ -83,7 +128,9 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
             Box::new(move |point: &[E::ScalarField]| {
                 let query_map_ref_cell: &RefCell<HashMap<(TrackerID, Vec<E::ScalarField>), E::ScalarField>> = query_map_clone.borrow();
                 let query_map = query_map_ref_cell.borrow();
-                let query_res = query_map.get(&(id.clone(), point.to_vec())).unwrap();
+                let query_res = query_map.get(&(id.clone(), point.to_vec())).ok_or_else(|| {
+                    PolyIOPErrors::InvalidVerifier(format!("no evaluation of comm {} in the query map", id))
+                })?;
                 Ok(query_res.clone())
             })
         );
@@ -109,6 +156,10 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
         id
     }
 
+    pub fn track_public_poly(&mut self, polynomial: PublicPoly<E::ScalarField>) -> TrackerID {
+        self.track_virtual_comm(Box::new(move |pt: &[E::ScalarField]| polynomial.evaluate(pt)))
+    }
+
     pub fn set_compiled_proof(&mut self, proof: CompiledZKSQLProof<E, PCS>) {
         self.proof = proof;
     }
@@ -135,9 +186,9 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
                 move |point: &[E::ScalarField]| {
                     let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = virtual_comms_clone.borrow();
                     let virtual_comms = virtual_comms_ref_cell.borrow();
-                    let c1_eval_box: &Box<dyn Fn(&[<E as Pairing>::ScalarField]) -> Result<<E as Pairing>::ScalarField, PolyIOPErrors>> = virtual_comms.get(&c1_id).unwrap();
+                    let c1_eval_box: &Box<dyn Fn(&[<E as Pairing>::ScalarField]) -> Result<<E as Pairing>::ScalarField, PolyIOPErrors>> = virtual_comms.get(&c1_id).ok_or_else(|| unknown_comm_err(c1_id))?;
                     let c1_eval: <E as Pairing>::ScalarField = c1_eval_box(point)?;
-                    let c2_eval_box = virtual_comms.get(&c2_id).unwrap();
+                    let c2_eval_box = virtual_comms.get(&c2_id).ok_or_else(|| unknown_comm_err(c2_id))?;
                     let c2_eval: <E as Pairing>::ScalarField = c2_eval_box(point)?;
                     let new_eval: <E as Pairing>::ScalarField = c1_eval + c2_eval; // add the scalars
                     Ok(new_eval)
@@ -162,9 +213,9 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
                 move |point: &[E::ScalarField]| {
                     let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = virtual_comms_clone.borrow();
                     let virtual_comms = virtual_comms_ref_cell.borrow();
-                    let c1_eval_box: &Box<dyn Fn(&[<E as Pairing>::ScalarField]) -> Result<<E as Pairing>::ScalarField, PolyIOPErrors>> = virtual_comms.get(&c1_id).unwrap();
+                    let c1_eval_box: &Box<dyn Fn(&[<E as Pairing>::ScalarField]) -> Result<<E as Pairing>::ScalarField, PolyIOPErrors>> = virtual_comms.get(&c1_id).ok_or_else(|| unknown_comm_err(c1_id))?;
                     let c1_eval: <E as Pairing>::ScalarField = c1_eval_box(point)?;
-                    let c2_eval_box = virtual_comms.get(&c2_id).unwrap();
+                    let c2_eval_box = virtual_comms.get(&c2_id).ok_or_else(|| unknown_comm_err(c2_id))?;
                     let c2_eval: <E as Pairing>::ScalarField = c2_eval_box(point)?;
                     Ok(c1_eval - c2_eval)
                 }
@@ -187,9 +238,9 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
                 move |point: &[E::ScalarField]| {
                     let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = virtual_comms_clone.borrow();
                     let virtual_comms = virtual_comms_ref_cell.borrow();
-                    let c1_eval_box = virtual_comms.get(&c1_id).unwrap();
+                    let c1_eval_box = virtual_comms.get(&c1_id).ok_or_else(|| unknown_comm_err(c1_id))?;
                     let c1_eval: <E as Pairing>::ScalarField = c1_eval_box(point)?;
-                    let c2_eval_box = virtual_comms.get(&c2_id).unwrap();
+                    let c2_eval_box = virtual_comms.get(&c2_id).ok_or_else(|| unknown_comm_err(c2_id))?;
                     let c2_eval: <E as Pairing>::ScalarField = c2_eval_box(point)?;
                     let new_eval: <E as Pairing>::ScalarField = c1_eval * c2_eval; // mul the scalars
                     Ok(new_eval)
@@ -215,7 +266,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
                 move |point: &[E::ScalarField]| {
                     let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = virtual_comms_clone.borrow();
                     let virtual_comms = virtual_comms_ref_cell.borrow();
-                    let poly_eval_box = virtual_comms.get(&poly_id).unwrap();
+                    let poly_eval_box = virtual_comms.get(&poly_id).ok_or_else(|| unknown_comm_err(poly_id))?;
                     let poly_eval: <E as Pairing>::ScalarField = poly_eval_box(point)?;
                     let new_eval: <E as Pairing>::ScalarField = poly_eval + c; // c + old eval
                     Ok(new_eval)
@@ -239,7 +290,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
                 move |point: &[E::ScalarField]| {
                     let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = virtual_comms_clone.borrow();
                     let virtual_comms = virtual_comms_ref_cell.borrow();
-                    let poly_eval_box = virtual_comms.get(&poly_id).unwrap();
+                    let poly_eval_box = virtual_comms.get(&poly_id).ok_or_else(|| unknown_comm_err(poly_id))?;
                     let poly_eval: <E as Pairing>::ScalarField = poly_eval_box(point)?;
                     let new_eval: <E as Pairing>::ScalarField = c.clone() * poly_eval; // c * old eval
                     Ok(new_eval)
@@ -251,19 +302,18 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
     }
 
     /// creates a new virtual comm that evaluates on an increased nv by adding variables at the front
-    /// adds the new variables at the front of the polynomial, 
-    /// and ignores the first 'added_nv' variables when evaluating the polynomial at a point
-    pub fn increase_nv_front(&mut self, poly_id: TrackerID, added_nv: usize) -> TrackerID {
+    /// adds the new variables at the front of the polynomial. 
+    /// Smaller polys are already evaluated on the suffix of the point, so the point is passed through unchanged
+    pub fn increase_nv_front(&mut self, poly_id: TrackerID, _added_nv: usize) -> TrackerID {
         let virtual_comms_clone = self.virtual_comms.clone(); // need to clone so the new copy can be moved into the closure
         let res_closure = move |pt: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {
             // get the eval function for poly_id
             let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = virtual_comms_clone.borrow();
             let virtual_comms = virtual_comms_ref_cell.borrow();
-            let poly_eval_box = virtual_comms.get(&poly_id).unwrap();
+            let poly_eval_box = virtual_comms.get(&poly_id).ok_or_else(|| unknown_comm_err(poly_id))?;
 
-            // evaluate the polynomial at the point, ignoring the first added_nv coordinates
-            let truncated_pt = &pt[added_nv..];
-            let poly_eval: <E as Pairing>::ScalarField = poly_eval_box(truncated_pt)?;
+            // evaluate the polynomial at the point, the first added_nv coordinates are ignored by the base poly
+            let poly_eval: <E as Pairing>::ScalarField = poly_eval_box(pt)?;
             Ok(poly_eval)
         };
         // create the new res_comm that uses the res_closure
@@ -273,18 +323,19 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
 
     /// creates a new virtual comm that evaluates on an increased nv by adding variables at the back
     /// adds the new variables at the back of the polynomial, 
-    /// and ignores the last 'added_nv' variables when evaluating the polynomial at a point
+    /// and evaluates the base polynomial at the point rotated right by 'added_nv' coordinates,
+    /// which lines the coordinates it depends on up with the suffix of the point
     pub fn increase_nv_back(&mut self, poly_id: TrackerID, added_nv: usize) -> TrackerID {
         let virtual_comms_clone = self.virtual_comms.clone(); // need to clone so the new copy can be moved into the closure
         let res_closure = move |pt: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {
             // get the eval function for poly_id
             let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = virtual_comms_clone.borrow();
             let virtual_comms = virtual_comms_ref_cell.borrow();
-            let poly_eval_box = virtual_comms.get(&poly_id).unwrap();
+            let poly_eval_box = virtual_comms.get(&poly_id).ok_or_else(|| unknown_comm_err(poly_id))?;
 
-            // evaluate the polynomial at the point, ignoring the last added_nv coordinates
-            let truncated_pt = &pt[..added_nv];
-            let poly_eval: <E as Pairing>::ScalarField = poly_eval_box(truncated_pt)?;
+            // evaluate the polynomial at the rotated point, so the last added_nv coordinates are ignored
+            let rotated_pt = rotate_point_right(pt, added_nv)?;
+            let poly_eval: <E as Pairing>::ScalarField = poly_eval_box(&rotated_pt)?;
             Ok(poly_eval)
         };
         // create the new res_comm that uses the res_closure
@@ -299,7 +350,7 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
     ) -> Result<E::ScalarField, PolyIOPErrors> {
         let virtual_comms_ref_cell: &RefCell<HashMap<TrackerID, Box<dyn Fn(&[E::ScalarField]) -> Result<E::ScalarField, PolyIOPErrors>>>> = self.virtual_comms.borrow();
         let virtual_comms = virtual_comms_ref_cell.borrow();
-        let comm_box = virtual_comms.get(&comm_id).unwrap();
+        let comm_box = virtual_comms.get(&comm_id).ok_or_else(|| unknown_comm_err(comm_id))?;
         let eval = comm_box(point)?;
         Ok(eval)
     }
@@ -339,64 +390,117 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTracker<E, PCS> {
         }
     }
 
-    fn convert_zerocheck_claims_to_sumcheck_claim(&mut self, nv: usize) {
+    // absorbs the aggregation nv and max degree, the claim ordering and the claimed sums into the transcript
+    // so the batching challenges are bound to the claims and to the sumcheck aux info
+    fn append_claims_to_transcript(&mut self, nv: usize, max_degree: usize) -> Result<(), PolyIOPErrors> {
+        self.transcript.append_serializable_element(b"aggregation nv", &(nv as u64))?;
+        self.transcript.append_serializable_element(b"aggregation max degree", &(max_degree as u64))?;
+        self.transcript.append_serializable_element(b"num sumcheck claims", &(self.sum_check_claims.len() as u64))?;
+        for claim in self.sum_check_claims.iter() {
+            self.transcript.append_serializable_element(b"sumcheck claim id", &(claim.label.to_int() as u64))?;
+            self.transcript.append_field_element(b"sumcheck claimed sum", &claim.claimed_sum)?;
+        }
+        self.transcript.append_serializable_element(b"num zerocheck claims", &(self.zero_check_claims.len() as u64))?;
+        for claim in self.zero_check_claims.iter() {
+            self.transcript.append_serializable_element(b"zerocheck claim id", &(claim.label.to_int() as u64))?;
+        }
+        Ok(())
+    }
+
+    fn convert_zerocheck_claims_to_sumcheck_claim(&mut self, nv: usize) -> Result<(), PolyIOPErrors> {
         // 1)   aggregate the zerocheck claims into a single MLE
         let zero_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::zero())};
         let mut zerocheck_agg_comm = self.track_virtual_comm(Box::new(zero_closure));
         let zero_check_claims = self.zero_check_claims.clone();
         for claim in zero_check_claims {
-            let challenge = self.get_and_append_challenge(b"zerocheck challenge").unwrap();
+            let challenge = self.get_and_append_challenge(b"zerocheck challenge")?;
             let claim_poly_id = self.mul_scalar(claim.label.clone(), challenge);
             zerocheck_agg_comm = self.add_comms(zerocheck_agg_comm, claim_poly_id);
         }
 
         // sample r
-        let r = self.transcript.get_and_append_challenge_vectors(b"0check r", nv).unwrap();
+        let r = self.transcript.get_and_append_challenge_vectors(b"0check r", nv)?;
         
-        // create the succint eq(x, r) closure and virtual comm
-        let eq_x_r_closure = move |pt: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {
-            Ok(eq_eval(pt, r.as_ref())?)
-        };
-        let eq_x_r_comm = self.track_virtual_comm(Box::new(eq_x_r_closure));
+        // track eq(x, r) as a public poly, evaluated succinctly by the verifier
+        let eq_x_r_comm = self.track_public_poly(PublicPoly::Eq { r });
 
         // create the relevant sumcheck claim
         let new_sc_claim_comm = self.mul_comms(zerocheck_agg_comm, eq_x_r_comm); // Note: SumCheck val should be zero
         self.add_sumcheck_claim(new_sc_claim_comm, E::ScalarField::zero());
+        Ok(())
     }
 
     pub fn verify_claims(&mut self) -> Result<(), PolyIOPErrors> {
+        // nv is read from the proof, so bound it by the rounds actually present before using it
         let nv = self.proof.sc_aux_info.num_variables;
+        if nv != self.proof.sc_proof.proofs.len() {
+            return Err(PolyIOPErrors::InvalidVerifier("sumcheck aux info does not match the number of rounds in the proof".to_string()));
+        }
+        self.append_claims_to_transcript(nv, self.proof.sc_aux_info.max_degree)?;
 
         // aggregate zerocheck claims into a single sumcheck claim
-        self.convert_zerocheck_claims_to_sumcheck_claim(nv); // Note: SumCheck val should be zero
+        self.convert_zerocheck_claims_to_sumcheck_claim(nv)?; // Note: SumCheck val should be zero
+
+        // every claimed sum shipped with the proof must be one that is checked here
+        let unchecked_sums = self.proof.sumcheck_claims.len() != self.sum_check_claims.len()
+            || self.sum_check_claims.iter().any(|claim| self.proof.sumcheck_claims.get(&claim.label) != Some(&claim.claimed_sum));
+        if unchecked_sums {
+            return Err(PolyIOPErrors::InvalidVerifier("proof claimed sums do not match the verifier's sumcheck claims".to_string()));
+        }
 
         // aggregate the sumcheck claims
         let zero_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::zero())};
         let mut sumcheck_comm = self.track_virtual_comm(Box::new(zero_closure));
         let mut sc_sum = E::ScalarField::zero();
         for claim in self.sum_check_claims.clone().iter() {
-            let challenge = self.get_and_append_challenge(b"sumcheck challenge").unwrap();
+            let challenge = self.get_and_append_challenge(b"sumcheck challenge")?;
             let claim_times_challenge_id = self.mul_scalar(claim.label.clone(), challenge);
             sumcheck_comm = self.add_comms(sumcheck_comm, claim_times_challenge_id);
             sc_sum += claim.claimed_sum * challenge;
         };
 
         // verify the sumcheck proof
-        let iop_verify_res = <PolyIOP<E::ScalarField> as SumCheck<E::ScalarField>>::verify(sc_sum, &self.proof.sc_proof, &self.proof.sc_aux_info, &mut self.transcript);
-        if iop_verify_res.is_err() {
-            return Err(PolyIOPErrors::InvalidVerifier(iop_verify_res.err().unwrap().to_string()));
+        let iop_verify_subclaim = <PolyIOP<E::ScalarField> as SumCheck<E::ScalarField>>::verify(sc_sum, &self.proof.sc_proof, &self.proof.sc_aux_info, &mut self.transcript)
+            .map_err(|e| PolyIOPErrors::InvalidVerifier(format!("sumcheck proof is invalid: {}", e)))?;
+        if self.proof.sc_proof.point != iop_verify_subclaim.point {
+            return Err(PolyIOPErrors::InvalidVerifier("sumcheck proof point does not match the verifier challenges".to_string()));
+        }
+
+        // check the aggregated poly, evaluated from the committed polys, matches the sumcheck subclaim
+        self.transfer_proof_poly_evals();
+        let sumcheck_eval = self.eval_virtual_comm(sumcheck_comm, &iop_verify_subclaim.point)?;
+        if sumcheck_eval != iop_verify_subclaim.expected_evaluation {
+            return Err(PolyIOPErrors::InvalidVerifier(
+                "aggregated polynomial evaluation does not match the sumcheck subclaim".to_string(),
+            ));
+        }
+
+        // verify the batch pcs proof, which authenticates every evaluation in the query map
+        let mut queries = self.proof.query_map.iter().collect::<Vec<_>>();
+        queries.sort_by(|a, b| a.0.cmp(b.0)); // sort so the transcript is generated consistently
+        if queries.is_empty() {
+            if !self.proof.pcs_proof.is_empty() {
+                return Err(PolyIOPErrors::InvalidVerifier("unexpected PCS proof for an empty query map".to_string()));
+            }
+            return Ok(());
+        }
+        if self.proof.pcs_proof.len() != 1 {
+            return Err(PolyIOPErrors::InvalidVerifier("expected exactly one batch PCS proof".to_string()));
+        }
+        let mut comms = Vec::with_capacity(queries.len());
+        let mut points = Vec::with_capacity(queries.len());
+        let mut evals = Vec::with_capacity(queries.len());
+        for ((id, point), eval) in queries {
+            let comm = self.get_mat_comm(*id).ok_or(PolyIOPErrors::InvalidVerifier(format!("query for unknown commitment {}", id)))?;
+            comms.push(comm.clone());
+            points.push(point.clone());
+            evals.push(*eval);
+        }
+        let batch_proof = self.proof.pcs_proof.first().ok_or_else(|| PolyIOPErrors::InvalidVerifier("missing batch PCS proof".to_string()))?;
+        let pcs_verify_res = PCS::batch_verify(&self.pcs_params, &comms, points.as_slice(), evals.as_slice(), batch_proof, &mut self.transcript)?;
+        if !pcs_verify_res {
+            return Err(PolyIOPErrors::InvalidVerifier("batch PCS opening proof is invalid".to_string()));
         }
-        let iop_verify_subclaim = iop_verify_res.unwrap();
-
-        // verify the batch pcs proof
-        let sumcheck_point = iop_verify_subclaim.point.clone();
-        let mut comm_ids = self.proof.comms.keys().cloned().collect::<Vec<TrackerID>>();
-        comm_ids.sort(); // sort so the transcript is generated consistently
-        let comms = comm_ids.iter().map(|id| self.get_mat_comm(*id).unwrap().clone()).collect::<Vec<PCS::Commitment>>();
-        let points = vec![sumcheck_point.clone(); comm_ids.len()];
-        let batch_proof = self.proof.pcs_proof[0].clone();
-        let pcs_verify_res = PCS::batch_verify(&self.pcs_params, &comms, points.as_slice(), &batch_proof, &mut self.transcript);
-        pcs_verify_res?;
 
         Ok(())
     }
@@ -5,11 +5,15 @@ use std::{
 };
 
 use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
 use ark_serialize::CanonicalSerialize;
 
 use crate::tracker::{
+    bag::BagComm,
     errors::PolyIOPErrors,
-    tracker_structs::{TrackerID, CompiledZKSQLProof},
+    iop_tracker::{IOPPoly, IOPTracker, WitnessToken},
+    public_poly::PublicPoly,
+    tracker_structs::{branch_id_ranges, TrackerID, CompiledZKSQLProof},
     verifier_tracker::VerifierTracker,
 };
 
@@ -18,6 +22,9 @@ use crate::subroutines::PolynomialCommitmentScheme;
 use crate::transcript::TranscriptError;
 
 
+/// The verifier's side of a `ProverBranch`, run by `VerifierTrackerRef::verify_branches`
+pub type VerifierBranch<'a, E, PCS, R> = Box<dyn FnOnce(&mut VerifierTrackerRef<E, PCS>) -> Result<R, PolyIOPErrors> + 'a>;
+
 #[derive(Derivative)]
 #[derivative(
     Clone(bound = "PCS: PolynomialCommitmentScheme<E>"),
@@ -64,6 +71,16 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTrackerRef<E, PCS>
         TrackedComm::new(res_id, self.tracker_rc.clone())
     }
 
+    pub fn track_public_poly(
+        &self,
+        polynomial: PublicPoly<E::ScalarField>,
+    ) -> TrackedComm<E, PCS> {
+        let tracker_ref: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
+        let mut tracker: RefMut<VerifierTracker<E, PCS>> = tracker_ref.borrow_mut();
+        let res_id = tracker.track_public_poly(polynomial);
+        TrackedComm::new(res_id, self.tracker_rc.clone())
+    }
+
     pub fn get_next_id(&mut self) -> TrackerID {
         let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
         tracker_ref_cell.borrow_mut().get_next_id()
@@ -74,9 +91,11 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTrackerRef<E, PCS>
         tracker_ref_cell.borrow_mut().set_compiled_proof(proof);
     }
 
-    pub fn get_mat_comm(&self, id: TrackerID) -> PCS::Commitment {
+    pub fn get_mat_comm(&self, id: TrackerID) -> Result<PCS::Commitment, PolyIOPErrors> {
         let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
-        tracker_ref_cell.borrow().get_mat_comm(id).unwrap().clone()
+        let tracker = tracker_ref_cell.borrow();
+        let comm = tracker.get_mat_comm(id).ok_or_else(|| PolyIOPErrors::InvalidVerifier(format!("unknown commitment {}", id)))?;
+        Ok(comm.clone())
     }
 
     pub fn get_and_append_challenge(&mut self, label: &'static [u8]) -> Result<E::ScalarField, TranscriptError> {
@@ -102,11 +121,11 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTrackerRef<E, PCS>
         tracker_ref_cell.borrow_mut().add_zerocheck_claim(poly_id);
     }
 
-    pub fn get_prover_claimed_sum(&self, id: TrackerID) -> E::ScalarField {
+    pub fn get_prover_claimed_sum(&self, id: TrackerID) -> Result<E::ScalarField, PolyIOPErrors> {
         let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
         let tracker = tracker_ref_cell.borrow();
-        let sum = tracker.get_prover_claimed_sum(id).unwrap().clone();
-        return sum;
+        let sum = tracker.get_prover_claimed_sum(id).ok_or_else(|| PolyIOPErrors::InvalidVerifier(format!("proof has no claimed sum for poly {}", id)))?;
+        Ok(*sum)
     }
 
     pub fn transfer_proof_poly_evals(&mut self) {
@@ -115,32 +134,58 @@ impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> VerifierTrackerRef<E, PCS>
         tracker.transfer_proof_poly_evals();
     }
 
-    pub fn transfer_prover_comm(&mut self,  id: TrackerID) -> TrackedComm<E, PCS> {
-        let new_id: TrackerID;
+    pub fn transfer_prover_comm(&mut self,  id: TrackerID) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
         let comm: PCS::Commitment;
         let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
         {
             // Scope the immutable borrow
             let tracker = tracker_ref_cell.borrow();
-            let comm_opt: Option<&PCS::Commitment> = tracker.proof.comms.get(&id);
-            match comm_opt {
-                Some(value) => {
-                    comm = value.clone();
-                },
-                None => {
-                    panic!("VerifierTracker Error: attempted to transfer prover comm, but id not found: {}", id);
-                }
-            }
-        } 
+            comm = tracker.proof.comms.get(&id).cloned().ok_or_else(|| {
+                PolyIOPErrors::InvalidVerifier(format!("proof has no commitment for id {}", id))
+            })?;
+        }
         let mut tracker = tracker_ref_cell.borrow_mut();
-        new_id = tracker.track_mat_comm(comm).unwrap();
+        let new_id = tracker.track_mat_comm(comm)?;
+        if id != new_id {
+            return Err(PolyIOPErrors::InvalidVerifier(format!("proof commitment {} was transferred out of order, expected {}", id, new_id)));
+        }
+
+        Ok(TrackedComm::new(new_id, self.tracker_rc.clone()))
+    }
+
+    pub fn get_tracked_comm(&self, id: TrackerID) -> TrackedComm<E, PCS> {
+        TrackedComm::new(id, self.tracker_rc.clone())
+    }
 
-        #[cfg(debug_assertions)] {
-            assert_eq!(id, new_id, "VerifierTracker Error: attempted to transfer prover comm, but ids don't match: {}, {}", id, new_id);
+    /// Runs the verifier side of `ProverTrackerRef::prove_branches`.
+    /// The branches run one after another, but see the same ids and transcripts as the prover's threads did
+    pub fn verify_branches<R>(
+        &mut self,
+        branches: Vec<VerifierBranch<'_, E, PCS, R>>,
+    ) -> Result<Vec<R>, PolyIOPErrors> {
+        let (ranges, fork_transcript, id_limit) = {
+            let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
+            let tracker = tracker_ref_cell.borrow();
+            let ranges = branch_id_ranges(tracker.id_counter, tracker.id_limit, branches.len())?;
+            (ranges, tracker.transcript.clone(), tracker.id_limit)
+        };
+
+        let mut results = Vec::with_capacity(branches.len());
+        for (i, (branch, range)) in branches.into_iter().zip(ranges.iter().copied()).enumerate() {
+            {
+                let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
+                tracker_ref_cell.borrow_mut().enter_branch(&fork_transcript, i, range)?;
+            }
+            results.push(branch(self)?);
+            let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
+            if tracker_ref_cell.borrow().id_counter > range.1 {
+                return Err(PolyIOPErrors::InvalidVerifier(format!("branch used ids past its limit {}", range.1)));
+            }
         }
 
-        let new_comm: TrackedComm<E, PCS> = TrackedComm::new(new_id, self.tracker_rc.clone());
-        new_comm
+        let tracker_ref_cell: &RefCell<VerifierTracker<E, PCS>> = self.tracker_rc.borrow();
+        tracker_ref_cell.borrow_mut().join_branches(fork_transcript, &ranges, id_limit)?;
+        Ok(results)
     }
 
     pub fn verify_claims(&self) -> Result<(), PolyIOPErrors> {
@@ -244,4 +289,84 @@ impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> TrackedComm<E, PCS> {
         let eval = tracker_ref.borrow().eval_virtual_comm(self.id.clone(), point)?;
         Ok(eval)
     }
-}
\ No newline at end of file
+}
+
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> IOPTracker<E> for VerifierTrackerRef<E, PCS> {
+    type Poly = TrackedComm<E, PCS>;
+    type Bag = BagComm<E, PCS>;
+
+    fn get_and_append_challenge(&mut self, label: &'static [u8]) -> Result<E::ScalarField, PolyIOPErrors> {
+        Ok(VerifierTrackerRef::get_and_append_challenge(self, label)?)
+    }
+
+    fn track_public_poly(&mut self, polynomial: PublicPoly<E::ScalarField>) -> Self::Poly {
+        VerifierTrackerRef::track_public_poly(self, polynomial)
+    }
+
+    fn commit_witness<W>(&mut self, _witness: W) -> Result<Self::Poly, PolyIOPErrors>
+    where
+        W: FnOnce(&WitnessToken) -> Result<DenseMultilinearExtension<E::ScalarField>, PolyIOPErrors>,
+    {
+        let id = self.get_next_id();
+        self.transfer_prover_comm(id)
+    }
+
+    fn add_sumcheck(&mut self, poly: &Self::Poly, claimed_sum: E::ScalarField) {
+        self.add_sumcheck_claim(poly.id, claimed_sum);
+    }
+
+    fn add_zerocheck(&mut self, poly: &Self::Poly) {
+        self.add_zerocheck_claim(poly.id);
+    }
+
+    fn add_prover_sumcheck(&mut self, poly: &Self::Poly) -> Result<E::ScalarField, PolyIOPErrors> {
+        let sum = self.get_prover_claimed_sum(poly.id)?;
+        self.add_sumcheck_claim(poly.id, sum);
+        Ok(sum)
+    }
+
+    fn check(&self, ok: bool, err_msg: impl FnOnce() -> String) -> Result<(), PolyIOPErrors> {
+        if !ok {
+            return Err(PolyIOPErrors::InvalidVerifier(err_msg()));
+        }
+        Ok(())
+    }
+}
+
+impl<E: Pairing, PCS: PolynomialCommitmentScheme<E>> IOPPoly<E::ScalarField> for TrackedComm<E, PCS> {
+    fn id(&self) -> TrackerID {
+        self.id
+    }
+
+    fn add_poly(&self, other: &Self) -> Self {
+        self.add_comms(other)
+    }
+
+    fn sub_poly(&self, other: &Self) -> Self {
+        self.sub_comms(other)
+    }
+
+    fn mul_poly(&self, other: &Self) -> Self {
+        self.mul_comms(other)
+    }
+
+    fn add_scalar(&self, c: E::ScalarField) -> Self {
+        TrackedComm::add_scalar(self, c)
+    }
+
+    fn mul_scalar(&self, c: E::ScalarField) -> Self {
+        TrackedComm::mul_scalar(self, c)
+    }
+
+    fn increase_nv_front(&self, added_nv: usize) -> Self {
+        TrackedComm::increase_nv_front(self, added_nv)
+    }
+
+    fn increase_nv_back(&self, added_nv: usize) -> Self {
+        TrackedComm::increase_nv_back(self, added_nv)
+    }
+
+    fn witness_evaluations(&self, _token: &WitnessToken) -> Vec<E::ScalarField> {
+        unreachable!("witness tokens are only created by the prover")
+    }
+}
@@ -92,14 +92,14 @@ where PCS: PolynomialCommitmentScheme<E> {
         let bag_sum_nv = max(bag_a.num_vars(), bag_b.num_vars()) + 1;
         // get ids to transfer
         let sum_poly_id = verifier_tracker.get_next_id();
-        let bag_c_poly = verifier_tracker.transfer_prover_comm(sum_poly_id);
+        let bag_c_poly = verifier_tracker.transfer_prover_comm(sum_poly_id)?;
         let sum_sel_poly_id = verifier_tracker.get_next_id();
-        let bag_c_sel = verifier_tracker.transfer_prover_comm(sum_sel_poly_id);
+        let bag_c_sel = verifier_tracker.transfer_prover_comm(sum_sel_poly_id)?;
         let bag_c = BagComm::new(bag_c_poly, bag_c_sel, bag_sum_nv);
         let ma_id = verifier_tracker.get_next_id();
-        let sum_a_mult_poly = verifier_tracker.transfer_prover_comm(ma_id);
+        let sum_a_mult_poly = verifier_tracker.transfer_prover_comm(ma_id)?;
         let mb_id = verifier_tracker.get_next_id();
-        let sum_b_mult_poly = verifier_tracker.transfer_prover_comm(mb_id);
+        let sum_b_mult_poly = verifier_tracker.transfer_prover_comm(mb_id)?;
 
         Self::verify_with_advice(
             verifier_tracker,
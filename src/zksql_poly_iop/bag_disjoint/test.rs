@@ -191,12 +191,12 @@ mod test {
 
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id), verifier_tracker.transfer_prover_comm(bag_a.selector.id), bag_a.num_vars());
-        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id), verifier_tracker.transfer_prover_comm(bag_b.selector.id), bag_b.num_vars());
-        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id), verifier_tracker.transfer_prover_comm(range_bag.selector.id), range_bag.num_vars());
-        let bag_c_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_c.poly.id), verifier_tracker.transfer_prover_comm(bag_c.selector.id), bag_c.num_vars());
-        let m_a_comm = verifier_tracker.transfer_prover_comm(m_a.id);
-        let m_b_comm = verifier_tracker.transfer_prover_comm(m_b.id);
+        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id)?, verifier_tracker.transfer_prover_comm(bag_a.selector.id)?, bag_a.num_vars());
+        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id)?, verifier_tracker.transfer_prover_comm(bag_b.selector.id)?, bag_b.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_bag.num_vars());
+        let bag_c_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_c.poly.id)?, verifier_tracker.transfer_prover_comm(bag_c.selector.id)?, bag_c.num_vars());
+        let m_a_comm = verifier_tracker.transfer_prover_comm(m_a.id)?;
+        let m_b_comm = verifier_tracker.transfer_prover_comm(m_b.id)?;
         BagDisjointIOP::<E, PCS>::verify_with_advice(
             verifier_tracker,
             &bag_a_comm,
@@ -382,9 +382,9 @@ mod test {
 
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id), verifier_tracker.transfer_prover_comm(bag_a.selector.id), bag_a.num_vars());
-        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id), verifier_tracker.transfer_prover_comm(bag_b.selector.id), bag_b.num_vars());
-        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id), verifier_tracker.transfer_prover_comm(range_bag.selector.id), range_bag.num_vars());
+        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id)?, verifier_tracker.transfer_prover_comm(bag_a.selector.id)?, bag_a.num_vars());
+        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id)?, verifier_tracker.transfer_prover_comm(bag_b.selector.id)?, bag_b.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_bag.num_vars());
         BagDisjointIOP::<E, PCS>::verify(
             verifier_tracker,
             &bag_a_comm,
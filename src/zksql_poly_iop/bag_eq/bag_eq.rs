@@ -1,5 +1,4 @@
 use ark_ec::pairing::Pairing;
-use ark_poly::DenseMultilinearExtension;
 use ark_std::{end_timer, One, start_timer};
 use std::marker::PhantomData;
 
@@ -25,15 +24,7 @@ where PCS: PolynomialCommitmentScheme<E> {
                 "BagEqIOP Error: fx and gx have different number of variables".to_string(),
             ));
         }
-        let nv = fx.num_vars();
-
-        // initialize multiplicity vector
-        let one_const_mle = DenseMultilinearExtension::from_evaluations_vec(nv, vec![E::ScalarField::one(); 2_usize.pow(nv as u32)]);
-        let mx = tracker.track_mat_poly(one_const_mle);
-
-        // call the bag_multitool prover
-        // the null_offset is set to zero here because we assume it is an exact permutation without extra nulls
-        BagMultitoolIOP::<E, PCS>::prove(tracker, &[fx.clone()], &[gx.clone()], &[mx.clone()], &[mx.clone()])?;
+        Self::circuit(tracker, fx, gx)?;
 
         end_timer!(start);
         Ok(())
@@ -44,9 +35,21 @@ where PCS: PolynomialCommitmentScheme<E> {
         fx: &BagComm<E, PCS>,
         gx: &BagComm<E, PCS>,
     ) -> Result<(), PolyIOPErrors> {
-        let one_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::one())};
-        let one_comm = tracker.track_virtual_comm(Box::new(one_closure));
-        BagMultitoolIOP::verify(tracker, &[fx.clone()], &[gx.clone()], &[one_comm.clone()], &[one_comm])?;
-        Ok(())
+        Self::circuit(tracker, fx, gx)
+    }
+
+    pub fn circuit<T: IOPTracker<E>>(
+        tracker: &mut T,
+        fx: &T::Bag,
+        gx: &T::Bag,
+    ) -> Result<(), PolyIOPErrors> {
+        let nv = fx.num_vars();
+
+        // initialize multiplicity vector
+        let mx = tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: E::ScalarField::one() });
+
+        // call the bag_multitool circuit
+        // the null_offset is set to zero here because we assume it is an exact permutation without extra nulls
+        BagMultitoolIOP::<E, PCS>::circuit(tracker, &[fx.clone()], &[gx.clone()], &[mx.clone()], &[mx])
     }
 }
\ No newline at end of file
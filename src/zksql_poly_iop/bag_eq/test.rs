@@ -86,8 +86,8 @@ mod test {
         
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id), verifier_tracker.transfer_prover_comm(f_bag.selector.id), f_nv);
-        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id), verifier_tracker.transfer_prover_comm(g_bag.selector.id), g_nv);
+        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id)?, verifier_tracker.transfer_prover_comm(f_bag.selector.id)?, f_nv);
+        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id)?, verifier_tracker.transfer_prover_comm(g_bag.selector.id)?, g_nv);
         BagEqIOP::<E, PCS>::verify(verifier_tracker, &f_bag_comm, &g_bag_comm)?;
         verifier_tracker.verify_claims()?;
 
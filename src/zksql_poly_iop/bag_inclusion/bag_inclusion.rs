@@ -1,5 +1,4 @@
 use ark_ec::pairing::Pairing;
-use ark_poly::DenseMultilinearExtension;
 use ark_std::{end_timer, One, start_timer};
 use std::marker::PhantomData;
 use crate::subroutines::pcs::PolynomialCommitmentScheme;
@@ -34,8 +33,7 @@ where PCS: PolynomialCommitmentScheme<E> {
         let nv = included_bag.num_vars();
 
         // initialize multiplicity vector
-        let one_const_mle = DenseMultilinearExtension::from_evaluations_vec(nv, vec![E::ScalarField::one(); 2_usize.pow(nv as u32)]);
-        let included_bag_m = tracker.track_mat_poly(one_const_mle);
+        let included_bag_m = tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: E::ScalarField::one() });
 
         // call the bag_multitool prover
         BagMultitoolIOP::<E, PCS>::prove(tracker, &[included_bag.clone()], &[super_bag.clone()], &[included_bag_m.clone()], &[super_bag_m.clone()])?;    
@@ -50,7 +48,7 @@ where PCS: PolynomialCommitmentScheme<E> {
         super_bag: &BagComm<E, PCS>,
     ) -> Result<(), PolyIOPErrors> {
         let super_bag_m_id = tracker.get_next_id();
-        let super_bag_m = tracker.transfer_prover_comm(super_bag_m_id);
+        let super_bag_m = tracker.transfer_prover_comm(super_bag_m_id)?;
         Self::verify_with_advice(tracker, included_bag, super_bag, &super_bag_m)
     }
 
@@ -62,8 +60,7 @@ where PCS: PolynomialCommitmentScheme<E> {
     ) -> Result<(), PolyIOPErrors> {
         let start = start_timer!(|| "BagInclusionIOP verify");
 
-        let one_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::one())};
-        let one_comm = tracker.track_virtual_comm(Box::new(one_closure));
+        let one_comm = tracker.track_public_poly(PublicPoly::Constant { num_vars: included_bag.num_vars(), value: E::ScalarField::one() });
         BagMultitoolIOP::verify(tracker, &[included_bag.clone()], &[super_bag.clone()], &[one_comm.clone()], &[super_bag_m.clone()])?;
  
         end_timer!(start);
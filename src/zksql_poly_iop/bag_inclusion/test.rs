@@ -111,9 +111,9 @@ mod test {
         
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id), verifier_tracker.transfer_prover_comm(f_bag.selector.id), f_nv);
-        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id), verifier_tracker.transfer_prover_comm(g_bag.selector.id), g_nv);
-        let mg_comm = verifier_tracker.transfer_prover_comm(mg.id);
+        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id)?, verifier_tracker.transfer_prover_comm(f_bag.selector.id)?, f_nv);
+        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id)?, verifier_tracker.transfer_prover_comm(g_bag.selector.id)?, g_nv);
+        let mg_comm = verifier_tracker.transfer_prover_comm(mg.id)?;
         BagInclusionIOP::<E, PCS>::verify_with_advice(
             verifier_tracker, 
             &f_bag_comm, 
@@ -205,8 +205,8 @@ mod test {
         
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id), verifier_tracker.transfer_prover_comm(f_bag.selector.id), f_nv);
-        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id), verifier_tracker.transfer_prover_comm(g_bag.selector.id), g_nv);
+        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id)?, verifier_tracker.transfer_prover_comm(f_bag.selector.id)?, f_nv);
+        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id)?, verifier_tracker.transfer_prover_comm(g_bag.selector.id)?, g_nv);
         BagInclusionIOP::<E, PCS>::verify(
             verifier_tracker, 
             &f_bag_comm, 
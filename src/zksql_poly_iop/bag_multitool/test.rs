@@ -168,12 +168,12 @@ mod test {
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
 
-        let f_comms_vec: Vec<TrackedComm<E, PCS>> = f_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Vec<TrackedComm<E, PCS>>>();
-        let f_sel_comms_vec: Vec<TrackedComm<E, PCS>> = f_sel_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Vec<TrackedComm<E, PCS>>>();
-        let mf_comms_vec: Vec<TrackedComm<E, PCS>> = mf_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Vec<TrackedComm<E, PCS>>>();
-        let g_comms_vec: Vec<TrackedComm<E, PCS>> = g_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Vec<TrackedComm<E, PCS>>>();
-        let g_sel_comms_vec: Vec<TrackedComm<E, PCS>> = g_sel_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Vec<TrackedComm<E, PCS>>>();
-        let mg_comms_vec: Vec<TrackedComm<E, PCS>> = mg_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Vec<TrackedComm<E, PCS>>>();
+        let f_comms_vec: Vec<TrackedComm<E, PCS>> = f_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Result<Vec<TrackedComm<E, PCS>>, PolyIOPErrors>>()?;
+        let f_sel_comms_vec: Vec<TrackedComm<E, PCS>> = f_sel_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Result<Vec<TrackedComm<E, PCS>>, PolyIOPErrors>>()?;
+        let mf_comms_vec: Vec<TrackedComm<E, PCS>> = mf_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Result<Vec<TrackedComm<E, PCS>>, PolyIOPErrors>>()?;
+        let g_comms_vec: Vec<TrackedComm<E, PCS>> = g_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Result<Vec<TrackedComm<E, PCS>>, PolyIOPErrors>>()?;
+        let g_sel_comms_vec: Vec<TrackedComm<E, PCS>> = g_sel_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Result<Vec<TrackedComm<E, PCS>>, PolyIOPErrors>>()?;
+        let mg_comms_vec: Vec<TrackedComm<E, PCS>> = mg_polys_vec.iter().map(|p| verifier_tracker.transfer_prover_comm(p.id)).collect::<Result<Vec<TrackedComm<E, PCS>>, PolyIOPErrors>>()?;
 
         let f_bags_vec: Vec<BagComm<E, PCS>> = f_comms_vec.iter()
             .zip(f_sel_comms_vec.iter())
@@ -41,7 +41,7 @@ where PCS: PolynomialCommitmentScheme<E>
         let bag_poly = bag.poly.clone();
         let bag_sel = bag.selector.clone();
         let inverses_poly_id = verifier_tracker.get_next_id();
-        let inverses_poly = verifier_tracker.transfer_prover_comm(inverses_poly_id);
+        let inverses_poly = verifier_tracker.transfer_prover_comm(inverses_poly_id)?;
         let no_dups_check_poly = bag_poly.mul_comms(&bag_sel).mul_comms(&inverses_poly).sub_comms(&bag_sel);
         verifier_tracker.add_zerocheck_claim(no_dups_check_poly.id);
 
@@ -1,6 +1,6 @@
 use ark_ec::pairing::Pairing;
 use ark_poly::DenseMultilinearExtension;
-use ark_std::{end_timer, One, start_timer, Zero};
+use ark_std::{end_timer, One, start_timer};
 use std::marker::PhantomData;
 
 use crate::subroutines::pcs::PolynomialCommitmentScheme;
@@ -36,23 +36,20 @@ where PCS: PolynomialCommitmentScheme<E> {
         // note: fx, gx, perm are already committed to, so ordered_mle, fhat, ghat, etc are fixed
         let gamma = tracker.get_and_append_challenge(b"gamma")?;
 
-         // create "pre-specified" polynomials: one_mle and ordered_mle (0, 1, 2, 3, ..)
-        let one_mle = DenseMultilinearExtension::from_evaluations_vec(nv, vec![E::ScalarField::one(); 2_usize.pow(nv as u32)]);
-        let ordered_evals: Vec<E::ScalarField> = (0..2_usize.pow(nv as u32)).map(|x| E::ScalarField::from(x as u64)).collect();
-        let ordered_mle = DenseMultilinearExtension::from_evaluations_vec(nv, ordered_evals);
+        // create "pre-specified" public polynomials: one and ordered (0, 1, 2, 3, ..)
+        let one_poly = tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: E::ScalarField::one() });
+        let ordered_poly = tracker.track_public_poly(PublicPoly::Identity { num_vars: nv });
        
         // calculate f_hat = s+gamma*p and g_hat = t+gamma*q
         let fx_evals = fx.poly.evaluations();
         let gx_evals = gx.poly.evaluations();
         let perm_evals = perm.evaluations();
-        let fhat_evals = (0..2_usize.pow(fx.num_vars() as u32)).map(|i| ordered_mle[i] + (gamma * fx_evals[i])).collect::<Vec<_>>();
+        let fhat_evals = (0..2_usize.pow(fx.num_vars() as u32)).map(|i| E::ScalarField::from(i as u64) + (gamma * fx_evals[i])).collect::<Vec<_>>();
         let ghat_evals = (0..2_usize.pow(gx.num_vars() as u32)).map(|i| perm_evals[i] + (gamma * gx_evals[i])).collect::<Vec<_>>();
         let fhat_mle = DenseMultilinearExtension::from_evaluations_vec(fx.num_vars(), fhat_evals);
         let ghat_mle = DenseMultilinearExtension::from_evaluations_vec(gx.num_vars(), ghat_evals);
 
         // set up polynomials in the tracker
-        let one_poly = tracker.track_mat_poly(one_mle);
-        let ordered_poly = tracker.track_mat_poly(ordered_mle);
         let fhat = tracker.track_and_commit_poly(fhat_mle)?;
         let ghat = tracker.track_and_commit_poly(ghat_mle)?;
         let fhat_bag = Bag::new(fhat, one_poly.clone());
@@ -80,21 +77,12 @@ where PCS: PolynomialCommitmentScheme<E> {
 
         // set up polynomials in the tracker in same style as prover 
         let gamma = tracker.get_and_append_challenge(b"gamma")?;
-        let one_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::one())};
-        let one_comm = tracker.track_virtual_comm(Box::new(one_closure));
-        let ordered_closure = |pt: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {
-            let mut res = E::ScalarField::zero();
-            for (i, x_i) in pt.iter().enumerate() {
-                let base = 2_usize.pow(i as u32);
-                res += *x_i * E::ScalarField::from(base as u64);
-            }
-            Ok(res)
-        };
-        let ordered_comm = tracker.track_virtual_comm(Box::new(ordered_closure));
+        let one_comm = tracker.track_public_poly(PublicPoly::Constant { num_vars: fx.num_vars(), value: E::ScalarField::one() });
+        let ordered_comm = tracker.track_public_poly(PublicPoly::Identity { num_vars: fx.num_vars() });
         let fhat_id = tracker.get_next_id();
-        let fhat_comm = tracker.transfer_prover_comm(fhat_id);
+        let fhat_comm = tracker.transfer_prover_comm(fhat_id)?;
         let ghat_id = tracker.get_next_id();
-        let ghat_comm = tracker.transfer_prover_comm(ghat_id);
+        let ghat_comm = tracker.transfer_prover_comm(ghat_id)?;
         let fhat_comm_bag = BagComm::new(fhat_comm, one_comm.clone(), fx.num_vars());
         let ghat_comm_bag = BagComm::new(ghat_comm, one_comm, gx.num_vars());
         let fhat_check_poly = ordered_comm.add_comms(&fx.poly.mul_scalar(gamma)).sub_comms(&fhat_comm_bag.poly);
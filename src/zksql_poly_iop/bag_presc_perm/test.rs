@@ -105,9 +105,9 @@ mod test {
 
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id), verifier_tracker.transfer_prover_comm(f_bag.selector.id), nv);
-        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id), verifier_tracker.transfer_prover_comm(g_bag.selector.id), nv);
-        let perm_comm = verifier_tracker.transfer_prover_comm(perm.id);
+        let f_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(f_bag.poly.id)?, verifier_tracker.transfer_prover_comm(f_bag.selector.id)?, nv);
+        let g_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(g_bag.poly.id)?, verifier_tracker.transfer_prover_comm(g_bag.selector.id)?, nv);
+        let perm_comm = verifier_tracker.transfer_prover_comm(perm.id)?;
         BagPrescPermIOP::<E, PCS>::verify(verifier_tracker, &f_bag_comm, &g_bag_comm, &perm_comm)?;
         verifier_tracker.verify_claims()?;
 
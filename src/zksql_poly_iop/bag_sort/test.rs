@@ -15,7 +15,7 @@ mod test {
 
     use crate::{
         tracker::prelude::*,
-        zksql_poly_iop::bag_sort::bag_sort::BagStrictSortIOP,
+        zksql_poly_iop::bag_sort::bag_sort::{BagSortIOP, BagStrictSortIOP, SortOrder},
     };
 
 
@@ -97,6 +97,75 @@ mod test {
 
     }
 
+    fn test_bag_sort() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let nv = 4;
+        let num_range_pow = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, num_range_pow)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create an ascending poly with duplicates, a strictly ascending one, and their reverses
+        let mut asc_nums: Vec<u64> = (0..2_usize.pow(nv as u32)).map(|_| rng.gen_range(1..1000)).collect();
+        asc_nums[1] = asc_nums[0];
+        asc_nums[7] = asc_nums[6];
+        asc_nums.sort();
+        let mut strict_asc_nums = asc_nums.clone();
+        strict_asc_nums.dedup();
+        while strict_asc_nums.len() < 2_usize.pow(nv as u32) {
+            let num = strict_asc_nums.last().unwrap() + 1;
+            strict_asc_nums.push(num);
+        }
+        let desc_nums: Vec<u64> = asc_nums.iter().rev().cloned().collect();
+        let strict_desc_nums: Vec<u64> = strict_asc_nums.iter().rev().cloned().collect();
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let sel = to_mle(&vec![1; 2_usize.pow(nv as u32)]);
+
+        // create the range poly
+        let range_poly_evals = (0..2_usize.pow(num_range_pow as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_poly = DenseMultilinearExtension::from_evaluations_vec(num_range_pow, range_poly_evals);
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // test good paths, a strictly sorted bag is also sorted
+        let good_cases = [
+            (&asc_nums, SortOrder::Asc, false),
+            (&strict_asc_nums, SortOrder::Asc, false),
+            (&strict_asc_nums, SortOrder::Asc, true),
+            (&desc_nums, SortOrder::Desc, false),
+            (&strict_desc_nums, SortOrder::Desc, false),
+            (&strict_desc_nums, SortOrder::Desc, true),
+        ];
+        for (nums, order, strict) in good_cases.iter() {
+            print!("BagSortIOP {:?} strict {} good path test: ", order, strict);
+            test_bag_sort_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(&mut prover_tracker, &mut verifier_tracker, &to_mle(nums), &sel, &range_poly, *order, *strict)?;
+            println!("passed");
+        }
+
+        // test bad paths: duplicates in a strict sort, and bags sorted the other way or not at all
+        let mut unsorted_nums = asc_nums.clone();
+        unsorted_nums.swap(3, 12);
+        let bad_cases = [
+            (&asc_nums, SortOrder::Asc, true),
+            (&desc_nums, SortOrder::Desc, true),
+            (&asc_nums, SortOrder::Desc, false),
+            (&desc_nums, SortOrder::Asc, false),
+            (&unsorted_nums, SortOrder::Asc, false),
+        ];
+        for (nums, order, strict) in bad_cases.iter() {
+            print!("BagSortIOP {:?} strict {} bad path test: ", order, strict);
+            let bad_result = test_bag_sort_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(&mut prover_tracker.deep_copy(), &mut verifier_tracker.deep_copy(), &to_mle(nums), &sel, &range_poly, *order, *strict);
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        Ok(())
+    }
+
     fn test_bag_strict_sort_helper<E: Pairing, PCS>(
         prover_tracker: &mut ProverTrackerRef<E, PCS>,
         verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
@@ -126,8 +195,8 @@ mod test {
        // set up verifier tracker, create subclaims, and verify IOPProofs
        let one_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::one())};
        verifier_tracker.set_compiled_proof(proof);
-       let sorted_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(sorted_bag.poly.id), verifier_tracker.transfer_prover_comm(sorted_bag.selector.id), sorted_bag.num_vars());
-       let range_comm = verifier_tracker.transfer_prover_comm(range_poly.id);
+       let sorted_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(sorted_bag.poly.id)?, verifier_tracker.transfer_prover_comm(sorted_bag.selector.id)?, sorted_bag.num_vars());
+       let range_comm = verifier_tracker.transfer_prover_comm(range_poly.id)?;
        let range_sel_comm = verifier_tracker.track_virtual_comm(Box::new(one_closure));
        let range_bag_comm = BagComm::new(range_comm.clone(), range_sel_comm, range_nv);
        BagStrictSortIOP::<E, PCS>::verify(verifier_tracker, &sorted_bag_comm, &range_bag_comm)?;
@@ -142,9 +211,55 @@ mod test {
        Ok(())
     }
 
+    fn test_bag_sort_helper<E: Pairing, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        sorted_bag_poly: &DenseMultilinearExtension<E::ScalarField>,
+        sorted_bag_sel: &DenseMultilinearExtension<E::ScalarField>,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        order: SortOrder,
+        strict: bool,
+    ) -> Result<(), PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        // Set up prover_tracker and prove
+        let range_nv = range_mle.num_vars;
+        let sorted_bag = Bag::new(prover_tracker.track_and_commit_poly(sorted_bag_poly.clone())?, prover_tracker.track_and_commit_poly(sorted_bag_sel.clone())?);
+        let range_poly = prover_tracker.track_and_commit_poly(range_mle.clone())?;
+        let range_sel = prover_tracker.track_public_poly(PublicPoly::Constant { num_vars: range_nv, value: E::ScalarField::one() });
+        let range_bag = Bag::new(range_poly.clone(), range_sel);
+        BagSortIOP::<E, PCS>::prove(prover_tracker, &sorted_bag, &range_bag, order, strict)?;
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up verifier tracker, create subclaims, and verify IOPProofs
+        verifier_tracker.set_compiled_proof(proof);
+        let sorted_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(sorted_bag.poly.id)?, verifier_tracker.transfer_prover_comm(sorted_bag.selector.id)?, sorted_bag.num_vars());
+        let range_comm = verifier_tracker.transfer_prover_comm(range_poly.id)?;
+        let range_sel_comm = verifier_tracker.track_public_poly(PublicPoly::Constant { num_vars: range_nv, value: E::ScalarField::one() });
+        let range_bag_comm = BagComm::new(range_comm, range_sel_comm, range_nv);
+        BagSortIOP::<E, PCS>::verify(verifier_tracker, &sorted_bag_comm, &range_bag_comm, order, strict)?;
+        verifier_tracker.verify_claims()?;
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+        Ok(())
+    }
+
     #[test]
     fn bag_strict_sort_test() {
         let res = test_bag_strict_sort();
         res.unwrap();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn bag_sort_test() {
+        let res = test_bag_sort();
+        res.unwrap();
+    }
+}
@@ -1,5 +1,4 @@
 use ark_ec::pairing::Pairing;
-use ark_poly::DenseMultilinearExtension;
 use ark_std::{end_timer, One, start_timer};
 use std::marker::PhantomData;
 
@@ -21,11 +20,11 @@ where PCS: PolynomialCommitmentScheme<E> {
         let start = start_timer!(|| "bagsumCheck prove");
 
         // initialize multiplicity vectors
-        let f0_one_const_poly = DenseMultilinearExtension::from_evaluations_vec(fx0.num_vars(), vec![E::ScalarField::one(); 2_usize.pow(fx0.num_vars() as u32)]); 
-        let f1_one_const_poly = DenseMultilinearExtension::from_evaluations_vec(fx1.num_vars(), vec![E::ScalarField::one(); 2_usize.pow(fx1.num_vars() as u32)]);
-        let g_one_const_poly = DenseMultilinearExtension::from_evaluations_vec(gx.num_vars(), vec![E::ScalarField::one(); 2_usize.pow(gx.num_vars() as u32)]);
-        let mfxs = vec![tracker.track_mat_poly(f0_one_const_poly), tracker.track_mat_poly(f1_one_const_poly)];
-        let mgxs = vec![tracker.track_mat_poly(g_one_const_poly)];
+        let mfxs = vec![
+            tracker.track_public_poly(PublicPoly::Constant { num_vars: fx0.num_vars(), value: E::ScalarField::one() }),
+            tracker.track_public_poly(PublicPoly::Constant { num_vars: fx1.num_vars(), value: E::ScalarField::one() }),
+        ];
+        let mgxs = vec![tracker.track_public_poly(PublicPoly::Constant { num_vars: gx.num_vars(), value: E::ScalarField::one() })];
 
         // use bag_multitool
         BagMultitoolIOP::<E, PCS>::prove(tracker, &[fx0.clone(), fx1.clone()], &[gx.clone()], &mfxs.clone(), &mgxs.clone())?;
@@ -41,11 +40,12 @@ where PCS: PolynomialCommitmentScheme<E> {
         gx:  &BagComm<E, PCS>,
     ) -> Result<(), PolyIOPErrors> {
         let start = start_timer!(|| "bagsumCheck verify");
-        let one_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::one())};
-        let one_comm = tracker.track_virtual_comm(Box::new(one_closure));
-        let _ = tracker.track_virtual_comm(Box::new(one_closure)); // extra virtual comm to match the prove structure
-        let _ = tracker.track_virtual_comm(Box::new(one_closure)); // extra virtual comm to match the prove structure
-        BagMultitoolIOP::verify(tracker, &[fx0.clone(), fx1.clone()], &[gx.clone()], &[one_comm.clone(), one_comm.clone()], &[one_comm.clone()])?;
+        let mfxs = vec![
+            tracker.track_public_poly(PublicPoly::Constant { num_vars: fx0.num_vars(), value: E::ScalarField::one() }),
+            tracker.track_public_poly(PublicPoly::Constant { num_vars: fx1.num_vars(), value: E::ScalarField::one() }),
+        ];
+        let mgxs = vec![tracker.track_public_poly(PublicPoly::Constant { num_vars: gx.num_vars(), value: E::ScalarField::one() })];
+        BagMultitoolIOP::verify(tracker, &[fx0.clone(), fx1.clone()], &[gx.clone()], &mfxs, &mgxs)?;
  
         end_timer!(start);
         Ok(())
@@ -81,7 +81,7 @@ where PCS: PolynomialCommitmentScheme<E> {
     ) -> Result<(), PolyIOPErrors> {
 
         let common_mset_supp_m_id = verifier_tracker.get_next_id();
-        let common_mset_supp_m = verifier_tracker.transfer_prover_comm(common_mset_supp_m_id);
+        let common_mset_supp_m = verifier_tracker.transfer_prover_comm(common_mset_supp_m_id)?;
 
         BagSuppIOP::verify_with_advice(
             verifier_tracker, 
@@ -133,9 +133,9 @@ mod test {
         // set up verifier tracker, create subclaims, and verify IOPProofs
         let one_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::one())};
         verifier_tracker.set_compiled_proof(proof);
-        let bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag.poly.id), verifier_tracker.transfer_prover_comm(bag.selector.id).clone(), bag.num_vars());
-        let supp_comm = BagComm::new(verifier_tracker.transfer_prover_comm(supp.poly.id), verifier_tracker.transfer_prover_comm(supp.selector.id).clone(), supp.num_vars());
-        let range_comm = verifier_tracker.transfer_prover_comm(range_poly.id).clone();
+        let bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag.poly.id)?, verifier_tracker.transfer_prover_comm(bag.selector.id)?.clone(), bag.num_vars());
+        let supp_comm = BagComm::new(verifier_tracker.transfer_prover_comm(supp.poly.id)?, verifier_tracker.transfer_prover_comm(supp.selector.id)?.clone(), supp.num_vars());
+        let range_comm = verifier_tracker.transfer_prover_comm(range_poly.id)?.clone();
         let range_sel_comm = verifier_tracker.track_virtual_comm(Box::new(one_closure));
         let range_bag_comm = BagComm::new(range_comm.clone(), range_sel_comm, range_nv);
         BagSuppIOP::<E, PCS>::verify(
@@ -0,0 +1,210 @@
+// Compares two columns row by row, as in `WHERE shipdate > orderdate`.
+// Proves a committed bit column is [a op b] on the rows selected by the selector of a_bag.
+// 1. for a < b, the bit is boolean and the range check of bit * (b - a - 1) + (1 - bit) * (a - b) shows
+//    b - a - 1 >= 0 when the bit is one and a - b >= 0 when it is zero. a <= b shifts both by one.
+//    see prove_range_check for the bound this puts on the difference of the two columns
+// 2. for a = b, the bit is boolean, the difference is zero when the bit is one,
+//    and it has an inverse when the bit is zero, which is given as advice
+
+use ark_ec::pairing::Pairing;
+use ark_std::{One, Zero};
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        column_compare::utils::{calc_compare_advice, calc_compare_bit},
+        selector_valid::selector_valid::SelectorValidIOP,
+        util::prelude::{prove_range_check, verify_range_check},
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CompareOp {
+    Lt, // a < b
+    LtEq, // a <= b
+    Eq, // a = b
+}
+
+impl CompareOp {
+    // the number of advice polys the comparison is proven with, besides the bit
+    pub fn num_advice_polys(&self) -> usize {
+        match self {
+            CompareOp::Eq => 1,
+            _ => 0,
+        }
+    }
+}
+
+pub struct ColumnCompareIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> ColumnCompareIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the comparison bit and its advice, and proves the comparison with them
+    // returns the bit
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        a_bag: &Bag<E, PCS>,
+        b: &TrackedPoly<E, PCS>,
+        op: CompareOp,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<TrackedPoly<E, PCS>, PolyIOPErrors> {
+        let bit_mle = calc_compare_bit(&a_bag.poly, b, op);
+        let advice_mles = calc_compare_advice(&a_bag.poly, b, op);
+        let bit = prover_tracker.track_and_commit_poly(bit_mle)?;
+        let mut advice_polys = Vec::<TrackedPoly<E, PCS>>::with_capacity(advice_mles.len());
+        for mle in advice_mles {
+            advice_polys.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        Self::prove_with_advice(prover_tracker, a_bag, b, op, &bit, &advice_polys, range_bag)?;
+        Ok(bit)
+    }
+
+    // advice_polys are the inverses of a - b for equality, and empty otherwise, see calc_compare_advice
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        a_bag: &Bag<E, PCS>,
+        b: &TrackedPoly<E, PCS>,
+        op: CompareOp,
+        bit: &TrackedPoly<E, PCS>,
+        advice_polys: &[TrackedPoly<E, PCS>],
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        Self::validate_advice(op, advice_polys.len())?;
+        let a = &a_bag.poly;
+        let sel = &a_bag.selector;
+        let one = E::ScalarField::one();
+        SelectorValidIOP::<E, PCS>::prove(prover_tracker, bit)?;
+
+        match op {
+            CompareOp::Lt | CompareOp::LtEq => {
+                let (true_offset, false_offset) = Self::range_check_offsets(op);
+                let a_minus_b = a.sub_poly(b);
+                let one_minus_bit = bit.mul_scalar(-one).add_scalar(one);
+                let range_checked_poly = bit.mul_poly(&a_minus_b.mul_scalar(-one).add_scalar(true_offset))
+                    .add_poly(&one_minus_bit.mul_poly(&a_minus_b.add_scalar(false_offset)));
+                prove_range_check(prover_tracker, &Bag::new(range_checked_poly, sel.clone()), range_bag)?;
+            },
+            CompareOp::Eq => {
+                let a_minus_b = a.sub_poly(b);
+                let inverses = &advice_polys[0];
+                let one_minus_bit = bit.mul_scalar(-one).add_scalar(one);
+                prover_tracker.add_zerocheck_claim(sel.mul_poly(bit).mul_poly(&a_minus_b).id);
+                prover_tracker.add_zerocheck_claim(sel.mul_poly(&one_minus_bit).mul_poly(&a_minus_b.mul_poly(inverses).add_scalar(-one)).id);
+            },
+        }
+
+        Ok(())
+    }
+
+    // compares two columns of a table, on the rows selected by its selector
+    // the columns can not be NULL, since the bit is not defined on NULLs
+    pub fn prove_cols(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table: &Table<E, PCS>,
+        a_col_index: usize,
+        b_col_index: usize,
+        op: CompareOp,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<TrackedPoly<E, PCS>, PolyIOPErrors> {
+        Self::validate_cols(&table.col_validity, a_col_index, b_col_index)?;
+        let a_bag = Bag::new(table.col_vals[a_col_index].clone(), table.selector.clone());
+        Self::prove(prover_tracker, &a_bag, &table.col_vals[b_col_index], op, range_bag)
+    }
+
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        a_bag: &BagComm<E, PCS>,
+        b: &TrackedComm<E, PCS>,
+        op: CompareOp,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
+        let bit_id = verifier_tracker.get_next_id();
+        let bit = verifier_tracker.transfer_prover_comm(bit_id)?;
+        let mut advice_comms = Vec::<TrackedComm<E, PCS>>::with_capacity(op.num_advice_polys());
+        for _ in 0..op.num_advice_polys() {
+            let id = verifier_tracker.get_next_id();
+            advice_comms.push(verifier_tracker.transfer_prover_comm(id)?);
+        }
+        Self::verify_with_advice(verifier_tracker, a_bag, b, op, &bit, &advice_comms, range_bag)?;
+        Ok(bit)
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        a_bag: &BagComm<E, PCS>,
+        b: &TrackedComm<E, PCS>,
+        op: CompareOp,
+        bit: &TrackedComm<E, PCS>,
+        advice_comms: &[TrackedComm<E, PCS>],
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        Self::validate_advice(op, advice_comms.len())?;
+        let a = &a_bag.poly;
+        let sel = &a_bag.selector;
+        let one = E::ScalarField::one();
+        SelectorValidIOP::<E, PCS>::verify(verifier_tracker, bit)?;
+
+        match op {
+            CompareOp::Lt | CompareOp::LtEq => {
+                let (true_offset, false_offset) = Self::range_check_offsets(op);
+                let a_minus_b = a.sub_comms(b);
+                let one_minus_bit = bit.mul_scalar(-one).add_scalar(one);
+                let range_checked_comm = bit.mul_comms(&a_minus_b.mul_scalar(-one).add_scalar(true_offset))
+                    .add_comms(&one_minus_bit.mul_comms(&a_minus_b.add_scalar(false_offset)));
+                verify_range_check(verifier_tracker, &BagComm::new(range_checked_comm, sel.clone(), a_bag.num_vars()), range_bag)?;
+            },
+            CompareOp::Eq => {
+                let a_minus_b = a.sub_comms(b);
+                let inverses = &advice_comms[0];
+                let one_minus_bit = bit.mul_scalar(-one).add_scalar(one);
+                verifier_tracker.add_zerocheck_claim(sel.mul_comms(bit).mul_comms(&a_minus_b).id);
+                verifier_tracker.add_zerocheck_claim(sel.mul_comms(&one_minus_bit).mul_comms(&a_minus_b.mul_comms(inverses).add_scalar(-one)).id);
+            },
+        }
+
+        Ok(())
+    }
+
+    pub fn verify_cols(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table: &TableComm<E, PCS>,
+        a_col_index: usize,
+        b_col_index: usize,
+        op: CompareOp,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_cols(&table.col_validity, a_col_index, b_col_index)?;
+        let a_bag = BagComm::new(table.col_vals[a_col_index].clone(), table.selector.clone(), table.num_vars());
+        Self::verify(verifier_tracker, &a_bag, &table.col_vals[b_col_index], op, range_bag)
+    }
+
+    fn validate_cols<T>(col_validity: &[Option<T>], a_col_index: usize, b_col_index: usize) -> Result<(), PolyIOPErrors> {
+        for i in [a_col_index, b_col_index] {
+            match col_validity.get(i) {
+                None => return Err(PolyIOPErrors::InvalidParameters("ColumnCompareIOP Error: column index out of bounds".to_string())),
+                Some(Some(_)) => return Err(PolyIOPErrors::InvalidParameters("ColumnCompareIOP Error: the compared columns can not be NULL".to_string())),
+                Some(None) => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_advice(op: CompareOp, num_advice_polys: usize) -> Result<(), PolyIOPErrors> {
+        if num_advice_polys != op.num_advice_polys() {
+            return Err(PolyIOPErrors::InvalidParameters(format!("ColumnCompareIOP Error: {:?} takes {} advice polys, got {}", op, op.num_advice_polys(), num_advice_polys)));
+        }
+        Ok(())
+    }
+
+    // the constants added to b - a when the bit is one and to a - b when it is zero, before the range check
+    // a < b is b - a - 1 >= 0 and its negation is a - b >= 0, a <= b is b - a >= 0 and its negation is a - b - 1 >= 0
+    fn range_check_offsets(op: CompareOp) -> (E::ScalarField, E::ScalarField) {
+        let one = E::ScalarField::one();
+        match op {
+            CompareOp::LtEq => (E::ScalarField::zero(), -one),
+            _ => (-one, E::ScalarField::zero()),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod column_compare;
+pub mod utils;
+mod test;
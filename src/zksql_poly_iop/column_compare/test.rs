@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::column_compare::{
+            column_compare::{ColumnCompareIOP, CompareOp},
+            utils::calc_compare_advice,
+        },
+    };
+
+    fn test_column_compare() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // orderdate and shipdate columns, the last two rows are not selected and far apart
+        let nv = 3;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let orderdate = to_mle(&[100, 105, 110, 120, 130, 131, 0, 5000]);
+        let shipdate = to_mle(&[102, 105, 109, 150, 130, 140, 5000, 0]);
+        let sel = to_mle(&[1, 1, 1, 1, 1, 1, 0, 0]);
+
+        // Test each comparison, in both directions
+        let good_cases = [
+            (&orderdate, &shipdate, CompareOp::Lt, [1, 0, 0, 1, 0, 1, 1, 0]),
+            (&shipdate, &orderdate, CompareOp::Lt, [0, 0, 1, 0, 0, 0, 0, 1]),
+            (&orderdate, &shipdate, CompareOp::LtEq, [1, 1, 0, 1, 1, 1, 1, 0]),
+            (&shipdate, &orderdate, CompareOp::LtEq, [0, 1, 1, 0, 1, 0, 0, 1]),
+            (&orderdate, &shipdate, CompareOp::Eq, [0, 1, 0, 0, 1, 0, 0, 0]),
+        ];
+        for (a, b, op, expected_bit_nums) in good_cases.iter() {
+            print!("Testing column compare {:?} good path: ", op);
+            let bit_evals = test_column_compare_helper(
+                &mut prover_tracker,
+                &mut verifier_tracker,
+                a,
+                b,
+                &sel,
+                *op,
+                None,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            )?;
+            assert_eq!(bit_evals, to_mle(expected_bit_nums).evaluations);
+            println!("passed");
+        }
+
+        // Test the given advice good path: the bits of rows that are not selected do not matter
+        print!("Testing column compare with advice good path: ");
+        test_column_compare_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &orderdate,
+            &shipdate,
+            &sel,
+            CompareOp::Lt,
+            Some(to_mle(&[1, 0, 0, 1, 0, 1, 0, 1])),
+            None,
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // Test bad paths, each with a wrong bit on a selected row
+        let bad_cases = [
+            (CompareOp::Lt, [1, 1, 0, 1, 0, 1, 1, 0]), // 105 < 105
+            (CompareOp::Lt, [1, 0, 1, 1, 0, 1, 1, 0]), // 110 < 109
+            (CompareOp::Lt, [0, 0, 0, 1, 0, 1, 1, 0]), // not 100 < 102
+            (CompareOp::LtEq, [1, 0, 0, 1, 1, 1, 1, 0]), // not 105 <= 105
+            (CompareOp::Eq, [1, 1, 0, 0, 1, 0, 0, 0]), // 100 = 102
+            (CompareOp::Eq, [0, 0, 0, 0, 1, 0, 0, 0]), // not 105 = 105
+            (CompareOp::Lt, [1, 0, 0, 1, 0, 2, 1, 0]), // a bit that is not boolean
+        ];
+        for (i, (op, bad_bit_nums)) in bad_cases.iter().enumerate() {
+            print!("Testing column compare bad path {}: ", i + 1);
+            let bad_result = test_column_compare_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &orderdate,
+                &shipdate,
+                &sel,
+                *op,
+                Some(to_mle(bad_bit_nums)),
+                None,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        // Test a bad path with the right bit but zero inverses for a = b, which cannot show that 100 != 102
+        print!("Testing column compare bad path with bad inverses: ");
+        let bad_result = test_column_compare_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &orderdate,
+            &shipdate,
+            &sel,
+            CompareOp::Eq,
+            Some(to_mle(&[0, 1, 0, 0, 1, 0, 0, 0])),
+            Some(vec![to_mle(&[0; 8])]),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test comparing a column that can be NULL, which is rejected
+        print!("Testing column compare with a NULL column: ");
+        let mut bad_prover_tracker = prover_tracker.deep_copy();
+        let orderdate_poly = bad_prover_tracker.track_and_commit_poly(orderdate.clone())?;
+        let shipdate_poly = bad_prover_tracker.track_and_commit_poly(shipdate.clone())?;
+        let shipdate_validity = bad_prover_tracker.track_and_commit_poly(to_mle(&[1, 1, 0, 1, 1, 1, 1, 1]))?;
+        let table = Table::new_with_validity(vec![orderdate_poly, shipdate_poly], vec![None, Some(shipdate_validity)], bad_prover_tracker.track_and_commit_poly(sel.clone())?);
+        let range_bag = Bag::new(bad_prover_tracker.track_and_commit_poly(range_mle.clone())?, bad_prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let bad_result = ColumnCompareIOP::prove_cols(&mut bad_prover_tracker, &table, 0, 1, CompareOp::Lt, &range_bag);
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies ColumnCompareIOP, with the computed bit on the table of a and b or the given one
+    // a given bit is proven with the given advice, or with the advice from calc_compare_advice
+    // returns the evaluations of the bit
+    fn test_column_compare_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        a: &DenseMultilinearExtension<E::ScalarField>,
+        b: &DenseMultilinearExtension<E::ScalarField>,
+        sel: &DenseMultilinearExtension<E::ScalarField>,
+        op: CompareOp,
+        bit: Option<DenseMultilinearExtension<E::ScalarField>>,
+        advice: Option<Vec<DenseMultilinearExtension<E::ScalarField>>>,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<E::ScalarField>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        // set up the prover tracker and prove
+        let a_bag = Bag::new(prover_tracker.track_and_commit_poly(a.clone())?, prover_tracker.track_and_commit_poly(sel.clone())?);
+        let b_poly = prover_tracker.track_and_commit_poly(b.clone())?;
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let given_bit = bit.is_some();
+        let mut advice_ids = Vec::new();
+        let bit_poly = match bit {
+            None => {
+                let table = Table::new(vec![a_bag.poly.clone(), b_poly.clone()], a_bag.selector.clone());
+                ColumnCompareIOP::<E, PCS>::prove_cols(prover_tracker, &table, 0, 1, op, &range_bag)?
+            },
+            Some(bit_mle) => {
+                let advice_mles = advice.unwrap_or_else(|| calc_compare_advice(&a_bag.poly, &b_poly, op));
+                let bit_poly = prover_tracker.track_and_commit_poly(bit_mle)?;
+                let mut advice_polys = Vec::new();
+                for mle in advice_mles {
+                    advice_polys.push(prover_tracker.track_and_commit_poly(mle)?);
+                }
+                advice_ids = advice_polys.iter().map(|p| p.id).collect();
+                ColumnCompareIOP::<E, PCS>::prove_with_advice(prover_tracker, &a_bag, &b_poly, op, &bit_poly, &advice_polys, &range_bag)?;
+                bit_poly
+            },
+        };
+        let bit_evals = bit_poly.evaluations();
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let a_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(a_bag.poly.id)?, verifier_tracker.transfer_prover_comm(a_bag.selector.id)?, a.num_vars);
+        let b_comm = verifier_tracker.transfer_prover_comm(b_poly.id)?;
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let bit_comm = if given_bit {
+            let bit_comm = verifier_tracker.transfer_prover_comm(bit_poly.id)?;
+            let mut advice_comms = Vec::new();
+            for id in advice_ids {
+                advice_comms.push(verifier_tracker.transfer_prover_comm(id)?);
+            }
+            ColumnCompareIOP::<E, PCS>::verify_with_advice(verifier_tracker, &a_bag_comm, &b_comm, op, &bit_comm, &advice_comms, &range_bag_comm)?;
+            bit_comm
+        } else {
+            let table_comm = TableComm::new(vec![a_bag_comm.poly.clone(), b_comm.clone()], a_bag_comm.selector.clone(), a.num_vars);
+            ColumnCompareIOP::<E, PCS>::verify_cols(verifier_tracker, &table_comm, 0, 1, op, &range_bag_comm)?
+        };
+        verifier_tracker.verify_claims()?;
+        assert_eq!(bit_comm.id, bit_poly.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(bit_evals)
+    }
+
+    #[test]
+    fn column_compare_test() {
+        let res = test_column_compare();
+        res.unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::batch_inversion;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{One, Zero};
+
+use crate::tracker::prelude::*;
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::zksql_poly_iop::column_compare::column_compare::CompareOp;
+
+// Calculates the comparison bit [a op b] of every row, including the rows that are not selected
+pub fn calc_compare_bit<E, PCS>(
+    a: &TrackedPoly<E, PCS>,
+    b: &TrackedPoly<E, PCS>,
+    op: CompareOp,
+) -> DenseMultilinearExtension<E::ScalarField>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let a_evals = a.evaluations();
+    let b_evals = b.evaluations();
+    let bit_evals = a_evals.iter().zip(b_evals.iter()).map(|(a, b)| {
+        let res = match op {
+            CompareOp::Lt => a < b,
+            CompareOp::LtEq => a <= b,
+            CompareOp::Eq => a == b,
+        };
+        if res { E::ScalarField::one() } else { E::ScalarField::zero() }
+    }).collect();
+    DenseMultilinearExtension::from_evaluations_vec(a.num_vars(), bit_evals)
+}
+
+// Calculates the advice the comparison is proven with besides the bit
+// a = b needs the inverse of a - b on every row, zero where a = b, and a < b and a <= b need none
+pub fn calc_compare_advice<E, PCS>(
+    a: &TrackedPoly<E, PCS>,
+    b: &TrackedPoly<E, PCS>,
+    op: CompareOp,
+) -> Vec<DenseMultilinearExtension<E::ScalarField>>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    match op {
+        CompareOp::Lt | CompareOp::LtEq => Vec::new(),
+        CompareOp::Eq => {
+            let mut inverse_evals = a.evaluations().iter().zip(b.evaluations().iter()).map(|(a, b)| *a - b).collect::<Vec<_>>();
+            batch_inversion(&mut inverse_evals);
+            vec![DenseMultilinearExtension::from_evaluations_vec(a.num_vars(), inverse_evals)]
+        },
+    }
+}
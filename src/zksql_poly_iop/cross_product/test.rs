@@ -110,17 +110,17 @@ mod test {
         let proof = prover_tracker.compile_proof()?;
 
         verifier_tracker.set_compiled_proof(proof);
-        let a_col_1_comm = verifier_tracker.transfer_prover_comm(a_table.col_vals[0].id);
-        let a_col_2_comm = verifier_tracker.transfer_prover_comm(a_table.col_vals[1].id);
-        let a_col_3_comm = verifier_tracker.transfer_prover_comm(a_table.col_vals[2].id);
+        let a_col_1_comm = verifier_tracker.transfer_prover_comm(a_table.col_vals[0].id)?;
+        let a_col_2_comm = verifier_tracker.transfer_prover_comm(a_table.col_vals[1].id)?;
+        let a_col_3_comm = verifier_tracker.transfer_prover_comm(a_table.col_vals[2].id)?;
         let a_cols_comm = vec![a_col_1_comm, a_col_2_comm, a_col_3_comm];
-        let a_sel_comm = verifier_tracker.transfer_prover_comm(a_table.selector.id);
+        let a_sel_comm = verifier_tracker.transfer_prover_comm(a_table.selector.id)?;
         let a_table_comm = TableComm::new(a_cols_comm, a_sel_comm, a_table.num_vars());
 
-        let b_col_1_comm = verifier_tracker.transfer_prover_comm(b_table.col_vals[0].id);
-        let b_col_2_comm = verifier_tracker.transfer_prover_comm(b_table.col_vals[1].id);
+        let b_col_1_comm = verifier_tracker.transfer_prover_comm(b_table.col_vals[0].id)?;
+        let b_col_2_comm = verifier_tracker.transfer_prover_comm(b_table.col_vals[1].id)?;
         let b_cols_comm = vec![b_col_1_comm, b_col_2_comm];
-        let b_sel_comm = verifier_tracker.transfer_prover_comm(b_table.selector.id);
+        let b_sel_comm = verifier_tracker.transfer_prover_comm(b_table.selector.id)?;
         let b_table_comm = TableComm::new(b_cols_comm, b_sel_comm, b_table.num_vars());
 
         CrossProductIOP::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::verifier_cross_product(
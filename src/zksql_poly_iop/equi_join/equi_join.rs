@@ -0,0 +1,151 @@
+// Proves the result table is the equi-join of table_a and table_b on one column from each, where the selected rows of table_a have distinct keys.
+// 1. JoinReductionIOP splits the selected rows of each table into dangling rows, whose key is not in the other table,
+//    and middle rows, whose keys are the same for both tables
+// 2. FinalJoinOneToManyIOP proves the keys of the middle rows of table_a are distinct and joins them with the middle rows of table_b,
+//    so the result has one row per middle row of table_b
+
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::One;
+use std::marker::PhantomData;
+use std::ops::Neg;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        final_join_one_to_many::final_join_one_to_many::FinalJoinOneToManyIOP,
+        join_reduction::{
+            join_reduction::{JoinReductionIOP, JoinReductionProvingAdvice, JoinReductionVerifyingAdvice},
+            utils::calc_join_reduction_lr_sel_advice,
+        },
+    },
+};
+
+pub struct EquiJoinIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> EquiJoinIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the join reduction advice, proves the reduction and then the join of the middle rows
+    // returns the result table
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>, // primary key table, no duplicates among the selected rows
+        table_b: &Table<E, PCS>, // foreign key table, has duplicates
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        let advice = Self::prove_partition(prover_tracker, table_a, table_b, a_join_col_index, b_join_col_index, range_bag)?;
+        let (mid_table_a, mid_table_b) = Self::prover_mid_tables(table_a, table_b, &advice);
+        FinalJoinOneToManyIOP::<E, PCS>::prove(prover_tracker, &mid_table_a, &mid_table_b, a_join_col_index, b_join_col_index, range_bag)
+    }
+
+    // takes the commitments to the join reduction advice from the proof
+    // returns the result table
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        let advice = Self::verify_partition(verifier_tracker, table_a, table_b, a_join_col_index, b_join_col_index, range_bag)?;
+        let (mid_table_a, mid_table_b) = Self::verifier_mid_tables(table_a, table_b, &advice);
+        FinalJoinOneToManyIOP::<E, PCS>::verify(verifier_tracker, &mid_table_a, &mid_table_b, a_join_col_index, b_join_col_index, range_bag)
+    }
+
+    // computes and commits to the selectors of the dangling rows, and proves with JoinReductionIOP that they split
+    // the rows of each table into dangling and middle rows
+    // returns the join reduction advice, whose l_sel and r_sel select the dangling rows of table_a and table_b
+    pub fn prove_partition(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<JoinReductionProvingAdvice<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls(), table_a.col_vals.len(), table_b.col_vals.len(), a_join_col_index, b_join_col_index)?;
+        let bag_a = Bag::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone());
+        let bag_b = Bag::new(table_b.col_vals[b_join_col_index].clone(), table_b.selector.clone());
+
+        // calculate and commit to the selectors of the dangling rows, and prove the reduction
+        let to_mle = |poly: &TrackedPoly<E, PCS>| DenseMultilinearExtension::from_evaluations_vec(poly.num_vars(), poly.evaluations());
+        let (l_sel_mle, r_sel_mle) = calc_join_reduction_lr_sel_advice::<E>(
+            &to_mle(&bag_a.poly),
+            &to_mle(&bag_a.selector),
+            &to_mle(&bag_b.poly),
+            &to_mle(&bag_b.selector),
+        );
+        let l_sel = prover_tracker.track_and_commit_poly(l_sel_mle)?;
+        let r_sel = prover_tracker.track_and_commit_poly(r_sel_mle)?;
+        JoinReductionIOP::<E, PCS>::prove(prover_tracker, &bag_a, &bag_b, &l_sel, &r_sel, range_bag)
+    }
+
+    // takes the commitments to the join reduction advice from the proof
+    // returns the advice
+    pub fn verify_partition(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<JoinReductionVerifyingAdvice<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls(), table_a.col_vals.len(), table_b.col_vals.len(), a_join_col_index, b_join_col_index)?;
+        let bag_a = BagComm::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone(), table_a.num_vars());
+        let bag_b = BagComm::new(table_b.col_vals[b_join_col_index].clone(), table_b.selector.clone(), table_b.num_vars());
+
+        // transfer the selectors of the dangling rows and verify the reduction
+        let l_sel_id = verifier_tracker.get_next_id();
+        let l_sel = verifier_tracker.transfer_prover_comm(l_sel_id)?;
+        let r_sel_id = verifier_tracker.get_next_id();
+        let r_sel = verifier_tracker.transfer_prover_comm(r_sel_id)?;
+        JoinReductionIOP::<E, PCS>::verify(verifier_tracker, &bag_a, &bag_b, &l_sel, &r_sel, range_bag)
+    }
+
+    // the tables with only their middle rows selected
+    pub fn prover_mid_tables(
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        advice: &JoinReductionProvingAdvice<E, PCS>,
+    ) -> (Table<E, PCS>, Table<E, PCS>) {
+        let one = E::ScalarField::one();
+        let mid_a_sel = table_a.selector.mul_poly(&advice.l_sel.mul_scalar(one.neg()).add_scalar(one));
+        let mid_b_sel = table_b.selector.mul_poly(&advice.r_sel.mul_scalar(one.neg()).add_scalar(one));
+        (Table::new(table_a.col_vals.clone(), mid_a_sel), Table::new(table_b.col_vals.clone(), mid_b_sel))
+    }
+
+    pub fn verifier_mid_tables(
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        advice: &JoinReductionVerifyingAdvice<E, PCS>,
+    ) -> (TableComm<E, PCS>, TableComm<E, PCS>) {
+        let one = E::ScalarField::one();
+        let mid_a_sel = table_a.selector.mul_comms(&advice.l_sel.mul_scalar(one.neg()).add_scalar(one));
+        let mid_b_sel = table_b.selector.mul_comms(&advice.r_sel.mul_scalar(one.neg()).add_scalar(one));
+        (TableComm::new(table_a.col_vals.clone(), mid_a_sel, table_a.num_vars()), TableComm::new(table_b.col_vals.clone(), mid_b_sel, table_b.num_vars()))
+    }
+
+    fn validate_params(
+        a_has_nulls: bool,
+        b_has_nulls: bool,
+        num_a_cols: usize,
+        num_b_cols: usize,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+    ) -> Result<(), PolyIOPErrors> {
+        if a_has_nulls || b_has_nulls {
+            return Err(PolyIOPErrors::InvalidParameters("EquiJoinIOP Error: the input tables can not have NULLs".to_string()));
+        }
+        if a_join_col_index >= num_a_cols || b_join_col_index >= num_b_cols {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "EquiJoinIOP Error: join columns ({}, {}) are out of bounds for tables with {} and {} columns",
+                a_join_col_index, b_join_col_index, num_a_cols, num_b_cols
+            )));
+        }
+        Ok(())
+    }
+}
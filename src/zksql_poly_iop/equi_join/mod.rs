@@ -0,0 +1,2 @@
+pub mod equi_join;
+mod test;
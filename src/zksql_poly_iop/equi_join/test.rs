@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::equi_join::equi_join::EquiJoinIOP,
+    };
+
+    fn test_equi_join() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // customers (cust_id, region) and orders (order_id, cust_id), joined on cust_id.
+        // customers 4 and 6 have no orders, customer 7 is not selected, and customer 9 does not exist,
+        // so the orders of customers 7 and 9 are dangling along with customers 4 and 6
+        let nv = 3;
+        let to_mle = |nv: usize, nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let a_cols = vec![to_mle(nv, &[1, 2, 3, 4, 5, 6, 7, 8]), to_mle(nv, &[31, 32, 33, 34, 35, 36, 37, 38])];
+        let a_sel = to_mle(nv, &[1, 1, 1, 1, 1, 1, 0, 0]);
+        let b_cols = vec![to_mle(nv, &[40, 41, 42, 43, 44, 45, 46, 47]), to_mle(nv, &[2, 2, 5, 9, 1, 7, 5, 3])];
+        let b_sel = to_mle(nv, &[1, 1, 1, 1, 1, 1, 1, 0]);
+
+        // Test the good path, where the result has a row for each order with a selected customer
+        print!("EquiJoinIOP good path test: ");
+        let res_evals = test_equi_join_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &a_cols,
+            &a_sel,
+            &b_cols,
+            &b_sel,
+            (0, 1),
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        let expected_evals = [
+            to_mle(nv, &[2, 2, 5, 0, 1, 0, 5, 0]),
+            to_mle(nv, &[32, 32, 35, 0, 31, 0, 35, 0]),
+            b_cols[0].clone(),
+            b_cols[1].clone(),
+            to_mle(nv, &[1, 1, 1, 0, 1, 0, 1, 0]),
+        ].iter().map(|mle| mle.evaluations.clone()).collect::<Vec<_>>();
+        assert_eq!(res_evals, expected_evals);
+        println!("passed");
+
+        // Test the good path with a smaller table_a and no dangling rows
+        print!("EquiJoinIOP good path with no dangling rows test: ");
+        let small_a_cols = vec![to_mle(2, &[1, 2, 3, 4]), to_mle(2, &[31, 32, 33, 34])];
+        let small_b_cols = vec![to_mle(nv, &[40, 41, 42, 43, 44, 45, 0, 0]), to_mle(nv, &[3, 1, 3, 3, 2, 4, 0, 0])];
+        let res_evals = test_equi_join_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &small_a_cols,
+            &to_mle(2, &[1, 1, 1, 1]),
+            &small_b_cols,
+            &to_mle(nv, &[1, 1, 1, 1, 1, 1, 0, 0]),
+            (0, 1),
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        assert_eq!(res_evals[1], to_mle(nv, &[33, 31, 33, 33, 32, 34, 0, 0]).evaluations);
+        println!("passed");
+
+        // Test join columns that are out of bounds
+        for (i, join_col_indices) in [(2, 1), (0, 2)].into_iter().enumerate() {
+            print!("EquiJoinIOP bad join column {} test: ", i + 1);
+            let bad_result = test_equi_join_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &a_cols,
+                &a_sel,
+                &b_cols,
+                &b_sel,
+                join_col_indices,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies EquiJoinIOP
+    // returns the evaluations of the result columns followed by the result selector
+    fn test_equi_join_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        a_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        a_sel: &DenseMultilinearExtension<E::ScalarField>,
+        b_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        b_sel: &DenseMultilinearExtension<E::ScalarField>,
+        join_col_indices: (usize, usize),
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<Vec<E::ScalarField>>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let (a_join_col_index, b_join_col_index) = join_col_indices;
+
+        // set up the prover tracker and prove
+        let mut a_col_polys = Vec::new();
+        for col in a_cols.iter() {
+            a_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_a = Table::new(a_col_polys, prover_tracker.track_and_commit_poly(a_sel.clone())?);
+        let mut b_col_polys = Vec::new();
+        for col in b_cols.iter() {
+            b_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_b = Table::new(b_col_polys, prover_tracker.track_and_commit_poly(b_sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let res_table = EquiJoinIOP::<E, PCS>::prove(prover_tracker, &table_a, &table_b, a_join_col_index, b_join_col_index, &range_bag)?;
+        let mut res_evals = res_table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+        res_evals.push(res_table.selector.evaluations());
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut a_col_comms = Vec::new();
+        for col_poly in table_a.col_vals.iter() {
+            a_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_a_comm = TableComm::new(a_col_comms, verifier_tracker.transfer_prover_comm(table_a.selector.id)?, a_sel.num_vars);
+        let mut b_col_comms = Vec::new();
+        for col_poly in table_b.col_vals.iter() {
+            b_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_b_comm = TableComm::new(b_col_comms, verifier_tracker.transfer_prover_comm(table_b.selector.id)?, b_sel.num_vars);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let res_table_comm = EquiJoinIOP::<E, PCS>::verify(verifier_tracker, &table_a_comm, &table_b_comm, a_join_col_index, b_join_col_index, &range_bag_comm)?;
+        verifier_tracker.verify_claims()?;
+        assert_eq!(res_table_comm.selector.id, res_table.selector.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_evals)
+    }
+
+    #[test]
+    fn equi_join_test() {
+        let res = test_equi_join();
+        res.unwrap();
+    }
+}
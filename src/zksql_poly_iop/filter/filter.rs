@@ -0,0 +1,255 @@
+// Filters a table by a predicate on one of its columns, as in a WHERE clause.
+// The result table has the same columns, and its selector is the input selector times the predicate bit of each row
+// 1. equality uses an is-zero gadget: with inv the inverse of col - c (zero when col = c), [col = c] = 1 - (col - c) * inv
+// 2. a comparison col < c or col <= c uses a committed bit, proven with ColumnCompareIOP against the constant column c.
+//    see prove_range_check for how far apart the values and the constant can be
+
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_std::One;
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        column_compare::column_compare::{ColumnCompareIOP, CompareOp},
+        filter::utils::calc_filter_advice,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterPredicate<F: PrimeField> {
+    Eq(F), // col = c
+    NotEq(F), // col != c
+    Lt(F), // col < c
+    LtEq(F), // col <= c
+    Between(F, F), // lo <= col <= hi
+}
+
+impl<F: PrimeField> FilterPredicate<F> {
+    // the number of advice polys the predicate is proven with
+    pub fn num_advice_polys(&self) -> usize {
+        match self {
+            FilterPredicate::Between(_, _) => 2,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterInstruction<F: PrimeField> {
+    pub col_idx: usize,
+    pub predicate: FilterPredicate<F>,
+}
+
+pub struct FilterIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> FilterIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the predicate advice and the result selector, and proves the filter with them
+    // returns the result table
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        filter_instruction: &FilterInstruction<E::ScalarField>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_instruction(filter_instruction, input_table.col_vals.len(), input_table.has_nulls())?;
+        let (advice_mles, res_sel_mle) = calc_filter_advice(input_table, filter_instruction);
+        let mut advice_polys = Vec::<TrackedPoly<E, PCS>>::with_capacity(advice_mles.len());
+        for mle in advice_mles {
+            advice_polys.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        let res_sel = prover_tracker.track_and_commit_poly(res_sel_mle)?;
+        Self::prove_with_advice(prover_tracker, input_table, filter_instruction, &advice_polys, &res_sel, range_bag)
+    }
+
+    // advice_polys are the inverses of col - c for equality, or the comparison bits, see calc_filter_advice
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        filter_instruction: &FilterInstruction<E::ScalarField>,
+        advice_polys: &[TrackedPoly<E, PCS>],
+        res_sel: &TrackedPoly<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_instruction(filter_instruction, input_table.col_vals.len(), input_table.has_nulls())?;
+        Self::validate_advice(filter_instruction, advice_polys.len())?;
+        let col = &input_table.col_vals[filter_instruction.col_idx];
+        let sel = &input_table.selector;
+        let one = E::ScalarField::one();
+
+        // 1. get the predicate bit of each row
+        let predicate = match filter_instruction.predicate {
+            FilterPredicate::Eq(c) => Self::prove_is_zero(prover_tracker, &col.add_scalar(-c), &advice_polys[0]),
+            FilterPredicate::NotEq(c) => Self::prove_is_zero(prover_tracker, &col.add_scalar(-c), &advice_polys[0]).mul_scalar(-one).add_scalar(one),
+            FilterPredicate::Lt(c) => Self::prove_compare_const(prover_tracker, input_table, filter_instruction.col_idx, CompareOp::Lt, c, &advice_polys[0], range_bag)?,
+            FilterPredicate::LtEq(c) => Self::prove_compare_const(prover_tracker, input_table, filter_instruction.col_idx, CompareOp::LtEq, c, &advice_polys[0], range_bag)?,
+            FilterPredicate::Between(lo, hi) => {
+                let below_lo = Self::prove_compare_const(prover_tracker, input_table, filter_instruction.col_idx, CompareOp::Lt, lo, &advice_polys[0], range_bag)?;
+                let at_most_hi = Self::prove_compare_const(prover_tracker, input_table, filter_instruction.col_idx, CompareOp::LtEq, hi, &advice_polys[1], range_bag)?;
+                at_most_hi.sub_poly(&below_lo.mul_poly(&at_most_hi))
+            },
+        };
+
+        // 2. the result selector is the input selector times the predicate bit
+        prover_tracker.add_zerocheck_claim(res_sel.sub_poly(&sel.mul_poly(&predicate)).id);
+
+        Ok(Table::new(input_table.col_vals.clone(), res_sel.clone()))
+    }
+
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        filter_instruction: &FilterInstruction<E::ScalarField>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_instruction(filter_instruction, input_table.col_vals.len(), input_table.has_nulls())?;
+        let mut advice_comms = Vec::<TrackedComm<E, PCS>>::with_capacity(filter_instruction.predicate.num_advice_polys());
+        for _ in 0..filter_instruction.predicate.num_advice_polys() {
+            let id = verifier_tracker.get_next_id();
+            advice_comms.push(verifier_tracker.transfer_prover_comm(id)?);
+        }
+        let res_sel_id = verifier_tracker.get_next_id();
+        let res_sel = verifier_tracker.transfer_prover_comm(res_sel_id)?;
+        Self::verify_with_advice(verifier_tracker, input_table, filter_instruction, &advice_comms, &res_sel, range_bag)
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        filter_instruction: &FilterInstruction<E::ScalarField>,
+        advice_comms: &[TrackedComm<E, PCS>],
+        res_sel: &TrackedComm<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_instruction(filter_instruction, input_table.col_vals.len(), input_table.has_nulls())?;
+        Self::validate_advice(filter_instruction, advice_comms.len())?;
+        let nv = input_table.num_vars();
+        let col = &input_table.col_vals[filter_instruction.col_idx];
+        let sel = &input_table.selector;
+        let one = E::ScalarField::one();
+
+        // 1. get the predicate bit of each row
+        let predicate = match filter_instruction.predicate {
+            FilterPredicate::Eq(c) => Self::verify_is_zero(verifier_tracker, &col.add_scalar(-c), &advice_comms[0]),
+            FilterPredicate::NotEq(c) => Self::verify_is_zero(verifier_tracker, &col.add_scalar(-c), &advice_comms[0]).mul_scalar(-one).add_scalar(one),
+            FilterPredicate::Lt(c) => Self::verify_compare_const(verifier_tracker, input_table, filter_instruction.col_idx, CompareOp::Lt, c, &advice_comms[0], range_bag)?,
+            FilterPredicate::LtEq(c) => Self::verify_compare_const(verifier_tracker, input_table, filter_instruction.col_idx, CompareOp::LtEq, c, &advice_comms[0], range_bag)?,
+            FilterPredicate::Between(lo, hi) => {
+                let below_lo = Self::verify_compare_const(verifier_tracker, input_table, filter_instruction.col_idx, CompareOp::Lt, lo, &advice_comms[0], range_bag)?;
+                let at_most_hi = Self::verify_compare_const(verifier_tracker, input_table, filter_instruction.col_idx, CompareOp::LtEq, hi, &advice_comms[1], range_bag)?;
+                at_most_hi.sub_comms(&below_lo.mul_comms(&at_most_hi))
+            },
+        };
+
+        // 2. the result selector is the input selector times the predicate bit
+        verifier_tracker.add_zerocheck_claim(res_sel.sub_comms(&sel.mul_comms(&predicate)).id);
+
+        Ok(TableComm::new(input_table.col_vals.clone(), res_sel.clone(), nv))
+    }
+
+    // returns the bit [diff = 0], given the inverses of diff
+    // diff * (1 - diff * inv) = 0 forces the bit to zero when diff is not zero, and it is one when diff is zero
+    fn prove_is_zero(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        diff: &TrackedPoly<E, PCS>,
+        inverses: &TrackedPoly<E, PCS>,
+    ) -> TrackedPoly<E, PCS> {
+        let one = E::ScalarField::one();
+        let is_zero = diff.mul_poly(inverses).mul_scalar(-one).add_scalar(one);
+        prover_tracker.add_zerocheck_claim(diff.mul_poly(&is_zero).id);
+        is_zero
+    }
+
+    fn verify_is_zero(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        diff: &TrackedComm<E, PCS>,
+        inverses: &TrackedComm<E, PCS>,
+    ) -> TrackedComm<E, PCS> {
+        let one = E::ScalarField::one();
+        let is_zero = diff.mul_comms(inverses).mul_scalar(-one).add_scalar(one);
+        verifier_tracker.add_zerocheck_claim(diff.mul_comms(&is_zero).id);
+        is_zero
+    }
+
+    // returns the bit [col op c], given the committed bit
+    fn prove_compare_const(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        col_idx: usize,
+        op: CompareOp,
+        c: E::ScalarField,
+        bit: &TrackedPoly<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<TrackedPoly<E, PCS>, PolyIOPErrors> {
+        let const_poly = prover_tracker.track_public_poly(PublicPoly::Constant { num_vars: input_table.num_vars(), value: c });
+        ColumnCompareIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &Bag::new(input_table.col_vals[col_idx].clone(), input_table.selector.clone()),
+            &const_poly,
+            op,
+            bit,
+            &[],
+            range_bag,
+        )?;
+        Ok(bit.clone())
+    }
+
+    fn verify_compare_const(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        col_idx: usize,
+        op: CompareOp,
+        c: E::ScalarField,
+        bit: &TrackedComm<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
+        let const_comm = verifier_tracker.track_public_poly(PublicPoly::Constant { num_vars: input_table.num_vars(), value: c });
+        ColumnCompareIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &BagComm::new(input_table.col_vals[col_idx].clone(), input_table.selector.clone(), input_table.num_vars()),
+            &const_comm,
+            op,
+            bit,
+            &[],
+            range_bag,
+        )?;
+        Ok(bit.clone())
+    }
+
+    // the result table keeps the columns without their validity, so the input table can not have NULLs
+    fn validate_instruction(
+        filter_instruction: &FilterInstruction<E::ScalarField>,
+        num_input_cols: usize,
+        input_has_nulls: bool,
+    ) -> Result<(), PolyIOPErrors> {
+        if input_has_nulls {
+            return Err(PolyIOPErrors::InvalidParameters("FilterIOP Error: the input table can not have NULLs".to_string()));
+        }
+        if filter_instruction.col_idx >= num_input_cols {
+            return Err(PolyIOPErrors::InvalidParameters(format!("FilterIOP Error: column index {} is out of bounds", filter_instruction.col_idx)));
+        }
+        if let FilterPredicate::Between(lo, hi) = filter_instruction.predicate {
+            if lo > hi {
+                return Err(PolyIOPErrors::InvalidParameters("FilterIOP Error: the lower bound of BETWEEN is above the upper bound".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_advice(
+        filter_instruction: &FilterInstruction<E::ScalarField>,
+        num_advice_polys: usize,
+    ) -> Result<(), PolyIOPErrors> {
+        if num_advice_polys != filter_instruction.predicate.num_advice_polys() {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "FilterIOP Error: {} advice polys for a predicate that needs {}",
+                num_advice_polys,
+                filter_instruction.predicate.num_advice_polys(),
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod filter;
+pub mod utils;
+mod test;
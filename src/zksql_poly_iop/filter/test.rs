@@ -0,0 +1,298 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::{
+            filter::filter::{FilterIOP, FilterInstruction, FilterPredicate},
+            outer_join::outer_join::{OuterJoinIOP, OuterJoinType},
+        },
+    };
+
+    fn test_filter() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // the filtered column is the second one, and the last two rows are not selected
+        let nv = 3;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let table_vals = vec![to_mle(&[1, 2, 3, 4, 5, 6, 7, 8]), to_mle(&[3, 7, 5, 7, 1, 9, 7, 0])];
+        let table_sel = to_mle(&[1, 1, 1, 1, 1, 1, 0, 0]);
+
+        // Test each predicate
+        let good_cases = [
+            (FilterPredicate::Eq(Fr::from(7_u64)), [0, 1, 0, 1, 0, 0, 0, 0]),
+            (FilterPredicate::NotEq(Fr::from(7_u64)), [1, 0, 1, 0, 1, 1, 0, 0]),
+            (FilterPredicate::Lt(Fr::from(5_u64)), [1, 0, 0, 0, 1, 0, 0, 0]),
+            (FilterPredicate::LtEq(Fr::from(5_u64)), [1, 0, 1, 0, 1, 0, 0, 0]),
+            (FilterPredicate::Between(Fr::from(3_u64), Fr::from(7_u64)), [1, 1, 1, 1, 0, 0, 0, 0]),
+            (FilterPredicate::Between(Fr::from(10_u64), Fr::from(20_u64)), [0, 0, 0, 0, 0, 0, 0, 0]),
+        ];
+        for (predicate, expected_sel_nums) in good_cases.iter() {
+            print!("Testing filter {:?} good path: ", predicate);
+            let filter_instruction = FilterInstruction { col_idx: 1, predicate: *predicate };
+            let res_sel_evals = test_filter_helper(
+                &mut prover_tracker,
+                &mut verifier_tracker,
+                &table_vals,
+                &table_sel,
+                &filter_instruction,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            )?;
+            assert_eq!(res_sel_evals, to_mle(expected_sel_nums).evaluations);
+            println!("passed");
+        }
+
+        // the inverses of col - 7, for the equality bad paths
+        let mut inverse_evals = table_vals[1].evaluations.iter().map(|x| *x - Fr::from(7_u64)).collect::<Vec<_>>();
+        ark_ff::batch_inversion(&mut inverse_evals);
+        let filter_instruction = FilterInstruction { col_idx: 1, predicate: FilterPredicate::Eq(Fr::from(7_u64)) };
+
+        // Test the given advice good path, which the bad paths change
+        print!("Testing filter with advice good path: ");
+        let advice = vec![DenseMultilinearExtension::from_evaluations_vec(nv, inverse_evals.clone())];
+        test_filter_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &table_vals,
+            &table_sel,
+            &filter_instruction,
+            Some((advice.clone(), to_mle(&[0, 1, 0, 1, 0, 0, 0, 0]))),
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // Test bad path 1: the result selector keeps a row that does not match
+        print!("Testing filter bad path 1: ");
+        let bad_result = test_filter_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &table_vals,
+            &table_sel,
+            &filter_instruction,
+            Some((advice, to_mle(&[1, 1, 0, 1, 0, 0, 0, 0]))),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 2: a zero inverse to claim a row is equal to the constant
+        print!("Testing filter bad path 2: ");
+        let mut bad_inverse_evals = inverse_evals.clone();
+        bad_inverse_evals[0] = Fr::from(0_u64);
+        let bad_advice = vec![DenseMultilinearExtension::from_evaluations_vec(nv, bad_inverse_evals)];
+        let bad_result = test_filter_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &table_vals,
+            &table_sel,
+            &filter_instruction,
+            Some((bad_advice, to_mle(&[1, 1, 0, 1, 0, 0, 0, 0]))),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 3: a comparison bit that claims 9 < 5
+        print!("Testing filter bad path 3: ");
+        let filter_instruction = FilterInstruction { col_idx: 1, predicate: FilterPredicate::Lt(Fr::from(5_u64)) };
+        let bad_result = test_filter_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &table_vals,
+            &table_sel,
+            &filter_instruction,
+            Some((vec![to_mle(&[1, 0, 0, 0, 1, 1, 0, 0])], to_mle(&[1, 0, 0, 0, 1, 1, 0, 0]))),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 4: a comparison bit that is not boolean
+        print!("Testing filter bad path 4: ");
+        let bad_result = test_filter_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &table_vals,
+            &table_sel,
+            &filter_instruction,
+            Some((vec![to_mle(&[1, 0, 0, 0, 2, 0, 0, 0])], to_mle(&[1, 0, 0, 0, 2, 0, 0, 0]))),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 5: a row in the range of BETWEEN that is dropped
+        print!("Testing filter bad path 5: ");
+        let filter_instruction = FilterInstruction { col_idx: 1, predicate: FilterPredicate::Between(Fr::from(3_u64), Fr::from(7_u64)) };
+        let bad_advice = vec![to_mle(&[0, 0, 0, 0, 1, 0, 1, 1]), to_mle(&[1, 0, 1, 1, 1, 0, 0, 1])];
+        let bad_result = test_filter_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &table_vals,
+            &table_sel,
+            &filter_instruction,
+            Some((bad_advice, to_mle(&[1, 0, 1, 1, 0, 0, 0, 0]))),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad instructions
+        print!("Testing filter bad instructions: ");
+        let bad_instructions = [
+            FilterInstruction { col_idx: 2, predicate: FilterPredicate::Eq(Fr::from(7_u64)) },
+            FilterInstruction { col_idx: 1, predicate: FilterPredicate::Between(Fr::from(7_u64), Fr::from(3_u64)) },
+        ];
+        for bad_instruction in bad_instructions.iter() {
+            let bad_result = test_filter_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &table_vals,
+                &table_sel,
+                bad_instruction,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+        }
+        println!("passed");
+
+        // Test an input table with NULLs: the result of a left outer join, where the unmatched row has NULL columns from table_b
+        print!("Testing filter on an outer join result: ");
+        let mut bad_prover_tracker = prover_tracker.deep_copy();
+        let mut a_col_polys = Vec::new();
+        for col in table_vals.iter() {
+            a_col_polys.push(bad_prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_a = Table::new(a_col_polys, bad_prover_tracker.track_and_commit_poly(table_sel.clone())?);
+        let b_cols = [to_mle(&[10, 11, 12, 13, 14, 15, 16, 17]), to_mle(&[1, 2, 2, 3, 4, 5, 0, 0])];
+        let mut b_col_polys = Vec::new();
+        for col in b_cols.iter() {
+            b_col_polys.push(bad_prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_b = Table::new(b_col_polys, bad_prover_tracker.track_and_commit_poly(to_mle(&[1, 1, 1, 1, 1, 1, 0, 0]))?);
+        let range_bag = Bag::new(bad_prover_tracker.track_and_commit_poly(range_mle.clone())?, bad_prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let outer_join_table = OuterJoinIOP::prove(&mut bad_prover_tracker, &table_a, &table_b, 0, 1, OuterJoinType::Left, &range_bag)?;
+        assert!(outer_join_table.has_nulls());
+        let filter_instruction = FilterInstruction { col_idx: 2, predicate: FilterPredicate::Lt(Fr::from(12_u64)) };
+        let bad_result = FilterIOP::prove(&mut bad_prover_tracker, &outer_join_table, &filter_instruction, &range_bag);
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies FilterIOP, with the computed advice or the given (advice, result selector)
+    // returns the evaluations of the result selector
+    fn test_filter_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_vals: &[DenseMultilinearExtension<E::ScalarField>],
+        table_sel: &DenseMultilinearExtension<E::ScalarField>,
+        filter_instruction: &FilterInstruction<E::ScalarField>,
+        advice: Option<(Vec<DenseMultilinearExtension<E::ScalarField>>, DenseMultilinearExtension<E::ScalarField>)>,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<E::ScalarField>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        // set up the prover tracker and prove
+        let mut table_val_polys = Vec::new();
+        for vals_mle in table_vals {
+            table_val_polys.push(prover_tracker.track_and_commit_poly(vals_mle.clone())?);
+        }
+        let table_sel_poly = prover_tracker.track_and_commit_poly(table_sel.clone())?;
+        let table = Table::new(table_val_polys.clone(), table_sel_poly.clone());
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let mut advice_ids = None;
+        let res_table = match advice {
+            None => FilterIOP::<E, PCS>::prove(prover_tracker, &table, filter_instruction, &range_bag)?,
+            Some((advice_mles, res_sel_mle)) => {
+                let mut advice_polys = Vec::new();
+                for mle in advice_mles {
+                    advice_polys.push(prover_tracker.track_and_commit_poly(mle)?);
+                }
+                let res_sel = prover_tracker.track_and_commit_poly(res_sel_mle)?;
+                advice_ids = Some((advice_polys.iter().map(|poly| poly.id).collect::<Vec<_>>(), res_sel.id));
+                FilterIOP::<E, PCS>::prove_with_advice(prover_tracker, &table, filter_instruction, &advice_polys, &res_sel, &range_bag)?
+            },
+        };
+        let res_sel_evals = res_table.selector.evaluations();
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut table_val_comms = Vec::new();
+        for vals_poly in table_val_polys.iter() {
+            table_val_comms.push(verifier_tracker.transfer_prover_comm(vals_poly.id)?);
+        }
+        let table_sel_comm = verifier_tracker.transfer_prover_comm(table_sel_poly.id)?;
+        let table_comm = TableComm::new(table_val_comms, table_sel_comm, table.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let res_table_comm = match advice_ids {
+            None => FilterIOP::<E, PCS>::verify(verifier_tracker, &table_comm, filter_instruction, &range_bag_comm)?,
+            Some((advice_ids, res_sel_id)) => {
+                let mut advice_comms = Vec::new();
+                for id in advice_ids {
+                    advice_comms.push(verifier_tracker.transfer_prover_comm(id)?);
+                }
+                let res_sel_comm = verifier_tracker.transfer_prover_comm(res_sel_id)?;
+                FilterIOP::<E, PCS>::verify_with_advice(verifier_tracker, &table_comm, filter_instruction, &advice_comms, &res_sel_comm, &range_bag_comm)?
+            },
+        };
+        verifier_tracker.verify_claims()?;
+        assert_eq!(res_table_comm.selector.id, res_table.selector.id);
+        assert_eq!(res_table_comm.col_vals.len(), table_vals.len());
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_sel_evals)
+    }
+
+    #[test]
+    fn filter_test() {
+        let res = test_filter();
+        res.unwrap();
+    }
+}
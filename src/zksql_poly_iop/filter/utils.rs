@@ -0,0 +1,57 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::batch_inversion;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{One, Zero};
+
+use crate::tracker::prelude::*;
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::zksql_poly_iop::filter::filter::{FilterInstruction, FilterPredicate};
+
+// Calculates the advice for filtering a table: the polys the predicate is proven with, in the order
+// FilterIOP expects them, and the selector of the result table
+// equality uses the inverses of col - c, and each comparison col < c uses the bit [col < c]
+pub fn calc_filter_advice<E, PCS>(
+    table: &Table<E, PCS>,
+    filter_instruction: &FilterInstruction<E::ScalarField>,
+) -> (Vec<DenseMultilinearExtension<E::ScalarField>>, DenseMultilinearExtension<E::ScalarField>) // (predicate advice, result selector)
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let nv = table.num_vars();
+    let one = E::ScalarField::one();
+    let col_evals = table.col_vals[filter_instruction.col_idx].evaluations();
+    let sel_evals = table.selector.evaluations();
+    let to_bit = |b: bool| if b { one } else { E::ScalarField::zero() };
+    let less_than_bits = |c: E::ScalarField| col_evals.iter().map(|x| to_bit(*x < c)).collect::<Vec<_>>();
+
+    let (advice_evals, predicate_evals) = match filter_instruction.predicate {
+        FilterPredicate::Eq(c) | FilterPredicate::NotEq(c) => {
+            let mut inverses = col_evals.iter().map(|x| *x - c).collect::<Vec<_>>();
+            batch_inversion(&mut inverses);
+            let is_eq = filter_instruction.predicate == FilterPredicate::Eq(c);
+            let predicate_evals = col_evals.iter().map(|x| to_bit((*x == c) == is_eq)).collect();
+            (vec![inverses], predicate_evals)
+        },
+        FilterPredicate::Lt(c) => {
+            let bits = less_than_bits(c);
+            (vec![bits.clone()], bits)
+        },
+        FilterPredicate::LtEq(c) => {
+            let bits = less_than_bits(c + one);
+            (vec![bits.clone()], bits)
+        },
+        FilterPredicate::Between(lo, hi) => {
+            let below_lo = less_than_bits(lo);
+            let below_hi = less_than_bits(hi + one);
+            let predicate_evals = below_lo.iter().zip(below_hi.iter()).map(|(a, b)| (one - a) * b).collect();
+            (vec![below_lo, below_hi], predicate_evals)
+        },
+    };
+    let res_sel_evals = sel_evals.iter().zip(predicate_evals.iter()).map(|(s, p): (&E::ScalarField, &E::ScalarField)| *s * p).collect();
+
+    (
+        advice_evals.into_iter().map(|evals| DenseMultilinearExtension::from_evaluations_vec(nv, evals)).collect(),
+        DenseMultilinearExtension::from_evaluations_vec(nv, res_sel_evals),
+    )
+}
@@ -0,0 +1,285 @@
+// Proves the result table is the join of table_a and table_b on one column from each, where both may have duplicate keys.
+// Each row of the result is a pair of selected rows, one from each table, with the index of each row in its table
+// 1. the table_a half of every result row is a row of table_a at its index, and likewise for table_b, and the join columns are equal.
+//    so the result only has matching pairs
+// 2. the result is strictly sorted by (a_index, b_index), so no pair is in it twice
+// 3. a strictly sorted support of the keys of both tables holds the number of rows of each table with each key,
+//    shown with bag inclusions of the join columns into the support with the counts as multiplicities.
+//    the result has a_count * b_count rows with each key, which is the number of matching pairs, so it has all of them.
+//    the sorts of the keys and the indices are range checked, see prove_range_check
+
+use ark_ec::pairing::Pairing;
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        bag_inclusion::bag_inclusion::BagInclusionIOP,
+        bag_sort::bag_sort::{BagStrictSortIOP, SortOrder},
+        final_join_many_to_many::utils::{calc_final_join_many_to_many_advice, calc_join_key_supp_advice},
+        index_transform::index_transform::IndexTransformIOP,
+        selector_valid::selector_valid::SelectorValidIOP,
+        table_sort::table_sort::TableSortIOP,
+    },
+};
+
+#[derive(Clone)]
+pub struct FinalJoinManyToManyProvingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub res_table: Table<E, PCS>, // the columns of table_a, then the columns of table_b
+    pub a_index: TrackedPoly<E, PCS>,
+    pub b_index: TrackedPoly<E, PCS>,
+    pub key_supp: Bag<E, PCS>,
+    pub a_key_counts: TrackedPoly<E, PCS>,
+    pub b_key_counts: TrackedPoly<E, PCS>,
+}
+
+#[derive(Clone)]
+pub struct FinalJoinManyToManyVerifyingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub res_table: TableComm<E, PCS>,
+    pub a_index: TrackedComm<E, PCS>,
+    pub b_index: TrackedComm<E, PCS>,
+    pub key_supp: BagComm<E, PCS>,
+    pub a_key_counts: TrackedComm<E, PCS>,
+    pub b_key_counts: TrackedComm<E, PCS>,
+}
+
+pub struct FinalJoinManyToManyIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> FinalJoinManyToManyIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the result table, its indices and the key support, and proves the join with them as advice
+    // returns the result table
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls(), table_a.col_vals.len(), table_b.col_vals.len(), a_join_col_index, b_join_col_index, table_a.col_vals.len() + table_b.col_vals.len())?;
+
+        // calculate the result table and the key support
+        let (res_col_mles, res_sel_mle, index_mles) = calc_final_join_many_to_many_advice(table_a, table_b, a_join_col_index, b_join_col_index);
+        let (supp_mle, supp_sel_mle, count_mles) = calc_join_key_supp_advice(table_a, table_b, a_join_col_index, b_join_col_index);
+
+        // put everything into the prover tracker
+        let mut res_cols = Vec::<TrackedPoly<E, PCS>>::with_capacity(res_col_mles.len());
+        for mle in res_col_mles {
+            res_cols.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        let res_sel = prover_tracker.track_and_commit_poly(res_sel_mle)?;
+        let mut index_polys = Vec::<TrackedPoly<E, PCS>>::with_capacity(2);
+        for mle in index_mles {
+            index_polys.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        let supp = prover_tracker.track_and_commit_poly(supp_mle)?;
+        let supp_sel = prover_tracker.track_and_commit_poly(supp_sel_mle)?;
+        let mut count_polys = Vec::<TrackedPoly<E, PCS>>::with_capacity(2);
+        for mle in count_mles {
+            count_polys.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        let advice = FinalJoinManyToManyProvingAdvice {
+            res_table: Table::new(res_cols, res_sel),
+            a_index: index_polys[0].clone(),
+            b_index: index_polys[1].clone(),
+            key_supp: Bag::new(supp, supp_sel),
+            a_key_counts: count_polys[0].clone(),
+            b_key_counts: count_polys[1].clone(),
+        };
+
+        Self::prove_with_advice(prover_tracker, table_a, table_b, a_join_col_index, b_join_col_index, &advice, range_bag)
+    }
+
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        advice: &FinalJoinManyToManyProvingAdvice<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        let res_table = &advice.res_table;
+        let num_a_cols = table_a.col_vals.len();
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls(), num_a_cols, table_b.col_vals.len(), a_join_col_index, b_join_col_index, res_table.col_vals.len())?;
+
+        // 1. the halves of the result rows are rows of the tables at their indices, and the join columns are equal
+        let a_identity = prover_tracker.track_public_poly(PublicPoly::Identity { num_vars: table_a.num_vars() });
+        IndexTransformIOP::<E, PCS>::prove(
+            prover_tracker,
+            &Table::new([vec![a_identity], table_a.col_vals.clone()].concat(), table_a.selector.clone()),
+            &Table::new([vec![advice.a_index.clone()], res_table.col_vals[..num_a_cols].to_vec()].concat(), res_table.selector.clone()),
+        )?;
+        let b_identity = prover_tracker.track_public_poly(PublicPoly::Identity { num_vars: table_b.num_vars() });
+        IndexTransformIOP::<E, PCS>::prove(
+            prover_tracker,
+            &Table::new([vec![b_identity], table_b.col_vals.clone()].concat(), table_b.selector.clone()),
+            &Table::new([vec![advice.b_index.clone()], res_table.col_vals[num_a_cols..].to_vec()].concat(), res_table.selector.clone()),
+        )?;
+        let res_join_col = &res_table.col_vals[a_join_col_index];
+        let equality_check_poly = res_join_col.sub_poly(&res_table.col_vals[num_a_cols + b_join_col_index]).mul_poly(&res_table.selector);
+        prover_tracker.add_zerocheck_claim(equality_check_poly.id);
+
+        // 2. no pair of rows is in the result twice
+        TableSortIOP::<E, PCS>::prove_sorted(
+            prover_tracker,
+            &Table::new(vec![advice.a_index.clone(), advice.b_index.clone()], res_table.selector.clone()),
+            &[(0, SortOrder::Asc), (1, SortOrder::Asc)],
+            range_bag,
+            true,
+        )?;
+
+        // 3. the support has no duplicates, and the result has a_count * b_count rows with each key
+        SelectorValidIOP::<E, PCS>::prove(prover_tracker, &advice.key_supp.selector)?;
+        BagStrictSortIOP::<E, PCS>::prove(prover_tracker, &advice.key_supp, range_bag)?;
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &Bag::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone()),
+            &advice.key_supp,
+            &advice.a_key_counts,
+        )?;
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &Bag::new(table_b.col_vals[b_join_col_index].clone(), table_b.selector.clone()),
+            &advice.key_supp,
+            &advice.b_key_counts,
+        )?;
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &Bag::new(res_join_col.clone(), res_table.selector.clone()),
+            &advice.key_supp,
+            &advice.a_key_counts.mul_poly(&advice.b_key_counts),
+        )?;
+
+        Ok(res_table.clone())
+    }
+
+    // takes the commitments to the result table, its indices and the key support from the proof.
+    // the number of variables of the result is public, like the number of rows it can hold
+    // returns the result table
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        res_nv: usize,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        let num_res_cols = table_a.col_vals.len() + table_b.col_vals.len();
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls(), table_a.col_vals.len(), table_b.col_vals.len(), a_join_col_index, b_join_col_index, num_res_cols)?;
+
+        // tranfer the trackerIDs of the advice to the verifier tracker, in the order the prover committed to them:
+        // the result columns and selector, the two indices, the support and its selector, and the two counts
+        let mut comms = Vec::<TrackedComm<E, PCS>>::with_capacity(num_res_cols + 7);
+        for _ in 0..num_res_cols + 7 {
+            let id = verifier_tracker.get_next_id();
+            comms.push(verifier_tracker.transfer_prover_comm(id)?);
+        }
+        let supp_nv = table_a.num_vars().max(table_b.num_vars()) + 1;
+        let advice = FinalJoinManyToManyVerifyingAdvice {
+            res_table: TableComm::new(comms[..num_res_cols].to_vec(), comms[num_res_cols].clone(), res_nv),
+            a_index: comms[num_res_cols + 1].clone(),
+            b_index: comms[num_res_cols + 2].clone(),
+            key_supp: BagComm::new(comms[num_res_cols + 3].clone(), comms[num_res_cols + 4].clone(), supp_nv),
+            a_key_counts: comms[num_res_cols + 5].clone(),
+            b_key_counts: comms[num_res_cols + 6].clone(),
+        };
+
+        Self::verify_with_advice(verifier_tracker, table_a, table_b, a_join_col_index, b_join_col_index, &advice, range_bag)
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        advice: &FinalJoinManyToManyVerifyingAdvice<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        let res_table = &advice.res_table;
+        let res_nv = res_table.num_vars();
+        let num_a_cols = table_a.col_vals.len();
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls(), num_a_cols, table_b.col_vals.len(), a_join_col_index, b_join_col_index, res_table.col_vals.len())?;
+
+        // 1. the halves of the result rows are rows of the tables at their indices, and the join columns are equal
+        let a_identity = verifier_tracker.track_public_poly(PublicPoly::Identity { num_vars: table_a.num_vars() });
+        IndexTransformIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &TableComm::new([vec![a_identity], table_a.col_vals.clone()].concat(), table_a.selector.clone(), table_a.num_vars()),
+            &TableComm::new([vec![advice.a_index.clone()], res_table.col_vals[..num_a_cols].to_vec()].concat(), res_table.selector.clone(), res_nv),
+        )?;
+        let b_identity = verifier_tracker.track_public_poly(PublicPoly::Identity { num_vars: table_b.num_vars() });
+        IndexTransformIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &TableComm::new([vec![b_identity], table_b.col_vals.clone()].concat(), table_b.selector.clone(), table_b.num_vars()),
+            &TableComm::new([vec![advice.b_index.clone()], res_table.col_vals[num_a_cols..].to_vec()].concat(), res_table.selector.clone(), res_nv),
+        )?;
+        let res_join_col = &res_table.col_vals[a_join_col_index];
+        let equality_check_comm = res_join_col.sub_comms(&res_table.col_vals[num_a_cols + b_join_col_index]).mul_comms(&res_table.selector);
+        verifier_tracker.add_zerocheck_claim(equality_check_comm.id);
+
+        // 2. no pair of rows is in the result twice
+        TableSortIOP::<E, PCS>::verify_sorted(
+            verifier_tracker,
+            &TableComm::new(vec![advice.a_index.clone(), advice.b_index.clone()], res_table.selector.clone(), res_nv),
+            &[(0, SortOrder::Asc), (1, SortOrder::Asc)],
+            range_bag,
+            true,
+        )?;
+
+        // 3. the support has no duplicates, and the result has a_count * b_count rows with each key
+        SelectorValidIOP::<E, PCS>::verify(verifier_tracker, &advice.key_supp.selector)?;
+        BagStrictSortIOP::<E, PCS>::verify(verifier_tracker, &advice.key_supp, range_bag)?;
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &BagComm::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone(), table_a.num_vars()),
+            &advice.key_supp,
+            &advice.a_key_counts,
+        )?;
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &BagComm::new(table_b.col_vals[b_join_col_index].clone(), table_b.selector.clone(), table_b.num_vars()),
+            &advice.key_supp,
+            &advice.b_key_counts,
+        )?;
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &BagComm::new(res_join_col.clone(), res_table.selector.clone(), res_nv),
+            &advice.key_supp,
+            &advice.a_key_counts.mul_comms(&advice.b_key_counts),
+        )?;
+
+        Ok(res_table.clone())
+    }
+
+    fn validate_params(
+        a_has_nulls: bool,
+        b_has_nulls: bool,
+        num_a_cols: usize,
+        num_b_cols: usize,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        num_res_cols: usize,
+    ) -> Result<(), PolyIOPErrors> {
+        if a_has_nulls || b_has_nulls {
+            return Err(PolyIOPErrors::InvalidParameters("FinalJoinManyToManyIOP Error: the input tables can not have NULLs".to_string()));
+        }
+        if a_join_col_index >= num_a_cols || b_join_col_index >= num_b_cols {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "FinalJoinManyToManyIOP Error: join columns {} and {} are out of bounds for tables with {} and {} columns",
+                a_join_col_index, b_join_col_index, num_a_cols, num_b_cols
+            )));
+        }
+        if num_res_cols != num_a_cols + num_b_cols {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "FinalJoinManyToManyIOP Error: the result table has {} columns, but the tables have {} and {} columns",
+                num_res_cols, num_a_cols, num_b_cols
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod final_join_many_to_many;
+pub mod utils;
+mod test;
\ No newline at end of file
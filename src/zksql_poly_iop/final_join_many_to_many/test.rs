@@ -0,0 +1,313 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::{One, Zero};
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::final_join_many_to_many::final_join_many_to_many::{
+            FinalJoinManyToManyIOP,
+            FinalJoinManyToManyProvingAdvice,
+            FinalJoinManyToManyVerifyingAdvice,
+        },
+    };
+
+    fn test_final_join_many_to_many() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // employees (dept, emp_id) and projects (dept, project_id), joined on dept.
+        // dept 1 has 3 employees and 2 selected projects, dept 2 has 2 and 3, dept 3 has 1 and 1,
+        // dept 4 has no projects and dept 5 has no employees
+        let nv = 3;
+        let to_mle = |nv: usize, nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let a_dept = [1, 2, 1, 3, 2, 1, 4, 9];
+        let a_sel = [1, 1, 1, 1, 1, 1, 1, 0];
+        let b_dept = [2, 1, 5, 1, 2, 2, 3, 1];
+        let b_sel = [1, 1, 1, 0, 1, 1, 1, 1];
+        let a_cols = vec![to_mle(nv, &a_dept), to_mle(nv, &[10, 11, 12, 13, 14, 15, 16, 17])];
+        let b_cols = vec![to_mle(nv, &b_dept), to_mle(nv, &[20, 21, 22, 23, 24, 25, 26, 27])];
+
+        // the matching pairs of rows, ordered by the row of table_a and then the row of table_b
+        let mut pairs = Vec::new();
+        for i in 0..8 {
+            for j in 0..8 {
+                if a_sel[i] == 1 && b_sel[j] == 1 && a_dept[i] == b_dept[j] {
+                    pairs.push((i as u64, j as u64));
+                }
+            }
+        }
+        assert_eq!(pairs.len(), 13);
+        let res_nv = 4;
+        let pad = |mut nums: Vec<u64>| { nums.resize(16, 0); to_mle(res_nv, &nums) };
+        let res_cols = vec![
+            pad(pairs.iter().map(|(i, _)| a_dept[*i as usize]).collect()),
+            pad(pairs.iter().map(|(i, _)| 10 + i).collect()),
+            pad(pairs.iter().map(|(_, j)| b_dept[*j as usize]).collect()),
+            pad(pairs.iter().map(|(_, j)| 20 + j).collect()),
+        ];
+        let res_sel = pad(vec![1; 13]);
+        let a_index = pad(pairs.iter().map(|(i, _)| *i).collect());
+        let b_index = pad(pairs.iter().map(|(_, j)| *j).collect());
+        let supp_pad = |nums: &[u64]| to_mle(nv + 1, &[vec![0; 16 - nums.len()], nums.to_vec()].concat());
+        let supp = supp_pad(&[1, 2, 3, 4, 5]);
+        let supp_sel = supp_pad(&[1, 1, 1, 1, 1]);
+        let a_counts = supp_pad(&[3, 2, 1, 1, 0]);
+        let b_counts = supp_pad(&[2, 3, 1, 0, 1]);
+        let advice = [res_cols.clone(), vec![res_sel.clone(), a_index.clone(), b_index.clone(), supp.clone(), supp_sel.clone(), a_counts.clone(), b_counts.clone()]].concat();
+
+        // Test the good path, checking the computed result against the matching pairs
+        print!("FinalJoinManyToManyIOP good path test: ");
+        let res_evals = test_final_join_many_to_many_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &a_cols,
+            &to_mle(nv, &a_sel),
+            &b_cols,
+            &to_mle(nv, &b_sel),
+            (0, 0),
+            None,
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        let expected_evals = [res_cols.clone(), vec![res_sel.clone()]].concat().iter().map(|mle| mle.evaluations.clone()).collect::<Vec<_>>();
+        assert_eq!(res_evals, expected_evals);
+        println!("passed");
+
+        // Test the good path with no matching pairs, joining the ids
+        print!("FinalJoinManyToManyIOP good path with an empty result test: ");
+        let res_evals = test_final_join_many_to_many_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &a_cols,
+            &to_mle(nv, &a_sel),
+            &b_cols,
+            &to_mle(nv, &b_sel),
+            (1, 1),
+            None,
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        assert_eq!(res_evals[4], vec![Fr::zero(); 2]);
+        println!("passed");
+
+        // Test the good path with the given advice
+        print!("FinalJoinManyToManyIOP with advice good path test: ");
+        test_final_join_many_to_many_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &a_cols,
+            &to_mle(nv, &a_sel),
+            &b_cols,
+            &to_mle(nv, &b_sel),
+            (0, 0),
+            Some(advice.clone()),
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // Test bad paths, each a wrong piece of advice given by its position in the advice and its new evaluations.
+        // pairs 0 and 1 are (0, 1) and (0, 7), and pairs 11 and 12 are (5, 1) and (5, 7), all with dept 1
+        let replace = |mle: &DenseMultilinearExtension<Fr>, changes: &[(usize, u64)]| {
+            let mut evals = mle.evaluations.clone();
+            for (i, val) in changes.iter() {
+                evals[*i] = Fr::from(*val);
+            }
+            DenseMultilinearExtension::from_evaluations_vec(mle.num_vars, evals)
+        };
+        let bad_cases = [
+            // a dropped pair
+            vec![(4, replace(&res_sel, &[(12, 0)]))],
+            // pair (0, 1) twice instead of (0, 1) and (0, 7), which keeps the number of rows with dept 1
+            vec![(3, replace(&res_cols[3], &[(1, 21)])), (6, replace(&b_index, &[(1, 1)]))],
+            // pair (0, 0), whose depts are not equal
+            vec![(2, replace(&res_cols[2], &[(0, 2)])), (3, replace(&res_cols[3], &[(0, 20)])), (6, replace(&b_index, &[(0, 0)]))],
+            // a table_a half that is not a row of table_a
+            vec![(1, replace(&res_cols[1], &[(0, 99)]))],
+            // a table_a half at the wrong index
+            vec![(5, replace(&a_index, &[(12, 2)]))],
+            // a wrong count, with dept 3 having no projects
+            vec![(10, replace(&b_counts, &[(13, 0)]))],
+            // a support with dept 1 twice that splits its rows as 2 * 2 + 1 * 0 pairs, so it allows two pairs fewer
+            vec![
+                (4, replace(&res_sel, &[(11, 0), (12, 0)])),
+                (7, supp_pad(&[1, 1, 2, 3, 4, 5])),
+                (8, supp_pad(&[1, 1, 1, 1, 1, 1])),
+                (9, supp_pad(&[2, 1, 2, 1, 1, 0])),
+                (10, supp_pad(&[2, 0, 3, 1, 0, 1])),
+            ],
+        ];
+        for (i, changes) in bad_cases.into_iter().enumerate() {
+            print!("FinalJoinManyToManyIOP bad path {} test: ", i + 1);
+            let mut bad_advice = advice.clone();
+            for (pos, mle) in changes {
+                bad_advice[pos] = mle;
+            }
+            let bad_result = test_final_join_many_to_many_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &a_cols,
+                &to_mle(nv, &a_sel),
+                &b_cols,
+                &to_mle(nv, &b_sel),
+                (0, 0),
+                Some(bad_advice),
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        // Test a join column that is out of bounds
+        print!("FinalJoinManyToManyIOP bad join column test: ");
+        let bad_result = test_final_join_many_to_many_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &a_cols,
+            &to_mle(nv, &a_sel),
+            &b_cols,
+            &to_mle(nv, &b_sel),
+            (0, 2),
+            None,
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies FinalJoinManyToManyIOP, with the computed advice or the given one,
+    // which is given in the order it is committed: the result columns and selector, the indices, the support and its selector, and the counts
+    // returns the evaluations of the result columns followed by the result selector
+    fn test_final_join_many_to_many_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        a_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        a_sel: &DenseMultilinearExtension<E::ScalarField>,
+        b_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        b_sel: &DenseMultilinearExtension<E::ScalarField>,
+        join_col_indices: (usize, usize),
+        advice: Option<Vec<DenseMultilinearExtension<E::ScalarField>>>,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<Vec<E::ScalarField>>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let (a_join_col_index, b_join_col_index) = join_col_indices;
+        let num_res_cols = a_cols.len() + b_cols.len();
+
+        // set up the prover tracker and prove
+        let mut a_col_polys = Vec::new();
+        for col in a_cols.iter() {
+            a_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_a = Table::new(a_col_polys, prover_tracker.track_and_commit_poly(a_sel.clone())?);
+        let mut b_col_polys = Vec::new();
+        for col in b_cols.iter() {
+            b_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_b = Table::new(b_col_polys, prover_tracker.track_and_commit_poly(b_sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let given_advice = advice.is_some();
+        let res_table = match advice {
+            None => FinalJoinManyToManyIOP::<E, PCS>::prove(prover_tracker, &table_a, &table_b, a_join_col_index, b_join_col_index, &range_bag)?,
+            Some(advice_mles) => {
+                let mut advice_polys = Vec::new();
+                for mle in advice_mles {
+                    advice_polys.push(prover_tracker.track_and_commit_poly(mle)?);
+                }
+                let advice = FinalJoinManyToManyProvingAdvice {
+                    res_table: Table::new(advice_polys[..num_res_cols].to_vec(), advice_polys[num_res_cols].clone()),
+                    a_index: advice_polys[num_res_cols + 1].clone(),
+                    b_index: advice_polys[num_res_cols + 2].clone(),
+                    key_supp: Bag::new(advice_polys[num_res_cols + 3].clone(), advice_polys[num_res_cols + 4].clone()),
+                    a_key_counts: advice_polys[num_res_cols + 5].clone(),
+                    b_key_counts: advice_polys[num_res_cols + 6].clone(),
+                };
+                FinalJoinManyToManyIOP::<E, PCS>::prove_with_advice(prover_tracker, &table_a, &table_b, a_join_col_index, b_join_col_index, &advice, &range_bag)?
+            },
+        };
+        let mut res_evals = res_table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+        res_evals.push(res_table.selector.evaluations());
+        let res_nv = res_table.num_vars();
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut a_col_comms = Vec::new();
+        for col_poly in table_a.col_vals.iter() {
+            a_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_a_comm = TableComm::new(a_col_comms, verifier_tracker.transfer_prover_comm(table_a.selector.id)?, a_sel.num_vars);
+        let mut b_col_comms = Vec::new();
+        for col_poly in table_b.col_vals.iter() {
+            b_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_b_comm = TableComm::new(b_col_comms, verifier_tracker.transfer_prover_comm(table_b.selector.id)?, b_sel.num_vars);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let res_table_comm = if given_advice {
+            let mut advice_comms = Vec::new();
+            for _ in 0..num_res_cols + 7 {
+                let id = verifier_tracker.get_next_id();
+                advice_comms.push(verifier_tracker.transfer_prover_comm(id)?);
+            }
+            let supp_nv = a_sel.num_vars.max(b_sel.num_vars) + 1;
+            let advice = FinalJoinManyToManyVerifyingAdvice {
+                res_table: TableComm::new(advice_comms[..num_res_cols].to_vec(), advice_comms[num_res_cols].clone(), res_nv),
+                a_index: advice_comms[num_res_cols + 1].clone(),
+                b_index: advice_comms[num_res_cols + 2].clone(),
+                key_supp: BagComm::new(advice_comms[num_res_cols + 3].clone(), advice_comms[num_res_cols + 4].clone(), supp_nv),
+                a_key_counts: advice_comms[num_res_cols + 5].clone(),
+                b_key_counts: advice_comms[num_res_cols + 6].clone(),
+            };
+            FinalJoinManyToManyIOP::<E, PCS>::verify_with_advice(verifier_tracker, &table_a_comm, &table_b_comm, a_join_col_index, b_join_col_index, &advice, &range_bag_comm)?
+        } else {
+            FinalJoinManyToManyIOP::<E, PCS>::verify(verifier_tracker, &table_a_comm, &table_b_comm, a_join_col_index, b_join_col_index, res_nv, &range_bag_comm)?
+        };
+        verifier_tracker.verify_claims()?;
+        assert_eq!(res_table_comm.selector.id, res_table.selector.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_evals)
+    }
+
+    #[test]
+    fn final_join_many_to_many_test() {
+        let res = test_final_join_many_to_many();
+        res.unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{One, Zero};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::tracker::prelude::*;
+
+// Calculates the result table of the join: every pair of selected rows of table_a and table_b with equal join columns,
+// ordered by the index of the table_a row and then by the index of the table_b row, followed by unselected rows of zeros.
+// The result has the fewest variables that fit the pairs, and at least one.
+// returns (res_cols, res_sel, [a_index, b_index]), where the indices are the rows of table_a and table_b each pair comes from
+pub fn calc_final_join_many_to_many_advice<E, PCS>(
+    table_a: &Table<E, PCS>,
+    table_b: &Table<E, PCS>,
+    a_join_col_index: usize,
+    b_join_col_index: usize,
+) -> (Vec<DenseMultilinearExtension<E::ScalarField>>, DenseMultilinearExtension<E::ScalarField>, Vec<DenseMultilinearExtension<E::ScalarField>>)
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    // map the values of the table_b join column to the indices of the selected rows that have them
+    let b_join_col_evals = table_b.col_vals[b_join_col_index].evaluations();
+    let b_sel_evals = table_b.selector.evaluations();
+    let mut b_rows_by_key = HashMap::<E::ScalarField, Vec<usize>>::new();
+    for (j, key) in b_join_col_evals.iter().enumerate() {
+        if b_sel_evals[j] == E::ScalarField::one() {
+            b_rows_by_key.entry(*key).or_default().push(j);
+        }
+    }
+
+    // pair every selected row of table_a with the matching rows of table_b
+    let a_join_col_evals = table_a.col_vals[a_join_col_index].evaluations();
+    let a_sel_evals = table_a.selector.evaluations();
+    let mut pairs = Vec::<(usize, usize)>::new();
+    for (i, key) in a_join_col_evals.iter().enumerate() {
+        if a_sel_evals[i] == E::ScalarField::one() {
+            if let Some(b_rows) = b_rows_by_key.get(key) {
+                pairs.extend(b_rows.iter().map(|j| (i, *j)));
+            }
+        }
+    }
+
+    let res_nv = (pairs.len().next_power_of_two().trailing_zeros() as usize).max(1);
+    let res_len = 2_usize.pow(res_nv as u32);
+    let to_mle = |evals: Vec<E::ScalarField>| {
+        let mut evals = evals;
+        evals.resize(res_len, E::ScalarField::zero());
+        DenseMultilinearExtension::from_evaluations_vec(res_nv, evals)
+    };
+    let a_col_evals = table_a.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+    let b_col_evals = table_b.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+    let mut res_cols = Vec::with_capacity(a_col_evals.len() + b_col_evals.len());
+    for evals in a_col_evals.iter() {
+        res_cols.push(to_mle(pairs.iter().map(|(i, _)| evals[*i]).collect()));
+    }
+    for evals in b_col_evals.iter() {
+        res_cols.push(to_mle(pairs.iter().map(|(_, j)| evals[*j]).collect()));
+    }
+    let res_sel = to_mle(vec![E::ScalarField::one(); pairs.len()]);
+    let a_index = to_mle(pairs.iter().map(|(i, _)| E::ScalarField::from(*i as u64)).collect());
+    let b_index = to_mle(pairs.iter().map(|(_, j)| E::ScalarField::from(*j as u64)).collect());
+
+    (res_cols, res_sel, vec![a_index, b_index])
+}
+
+// Calculates the support of the join keys of both tables, laid out like calc_bag_supp_advice:
+// the distinct keys of the selected rows sorted at the end, zeros at the front, and the number of rows of each table with each key.
+// The support has one more variable than the larger table, so it fits the keys of both
+// returns (supp, supp_sel, [a_counts, b_counts])
+pub fn calc_join_key_supp_advice<E, PCS>(
+    table_a: &Table<E, PCS>,
+    table_b: &Table<E, PCS>,
+    a_join_col_index: usize,
+    b_join_col_index: usize,
+) -> (DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>, Vec<DenseMultilinearExtension<E::ScalarField>>)
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    // count the selected rows of each table by key, in ascending order of the keys
+    let mut counts = BTreeMap::<E::ScalarField, (u64, u64)>::new();
+    let a_join_col_evals = table_a.col_vals[a_join_col_index].evaluations();
+    let a_sel_evals = table_a.selector.evaluations();
+    for (i, key) in a_join_col_evals.iter().enumerate() {
+        if a_sel_evals[i] == E::ScalarField::one() {
+            counts.entry(*key).or_insert((0, 0)).0 += 1;
+        }
+    }
+    let b_join_col_evals = table_b.col_vals[b_join_col_index].evaluations();
+    let b_sel_evals = table_b.selector.evaluations();
+    for (j, key) in b_join_col_evals.iter().enumerate() {
+        if b_sel_evals[j] == E::ScalarField::one() {
+            counts.entry(*key).or_insert((0, 0)).1 += 1;
+        }
+    }
+
+    let supp_nv = table_a.num_vars().max(table_b.num_vars()) + 1;
+    let num_fillers = 2_usize.pow(supp_nv as u32) - counts.len();
+    let with_fillers = |vals: Vec<E::ScalarField>| {
+        let mut evals = vec![E::ScalarField::zero(); num_fillers];
+        evals.extend(vals);
+        DenseMultilinearExtension::from_evaluations_vec(supp_nv, evals)
+    };
+    let supp = with_fillers(counts.keys().copied().collect());
+    let supp_sel = with_fillers(vec![E::ScalarField::one(); counts.len()]);
+    let a_counts = with_fillers(counts.values().map(|(a_count, _)| E::ScalarField::from(*a_count)).collect());
+    let b_counts = with_fillers(counts.values().map(|(_, b_count)| E::ScalarField::from(*b_count)).collect());
+
+    (supp, supp_sel, vec![a_counts, b_counts])
+}
@@ -1,7 +1,6 @@
 
 use ark_ec::pairing::Pairing;
 use ark_poly::DenseMultilinearExtension;
-use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use crate::subroutines::pcs::PolynomialCommitmentScheme;
@@ -10,9 +9,22 @@ use crate::{
     zksql_poly_iop::{
         index_transform::index_transform::IndexTransformIOP,
         final_join_one_to_many::utils::{calc_final_join_one_to_many_index_transform, calc_index_transformed_bag},
+        unique_column::{unique_column::UniqueColumnIOP, utils::calc_unique_column_advice},
     },
 };
 
+#[derive(Clone)]
+pub struct FinalJoinOneToManyProvingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub transformed_a_cols: Vec<TrackedPoly<E, PCS>>, // the rows of table_a matching each row of table_b
+    pub a_sorted: Bag<E, PCS>, // the sorted join column of table_a, see calc_unique_column_advice
+}
+
+#[derive(Clone)]
+pub struct FinalJoinOneToManyVerifyingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub transformed_a_cols: Vec<TrackedComm<E, PCS>>,
+    pub a_sorted: BagComm<E, PCS>,
+}
+
 pub struct FinalJoinOneToManyIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
 
 impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> FinalJoinOneToManyIOP<E, PCS> 
@@ -23,7 +35,9 @@ where PCS: PolynomialCommitmentScheme<E> {
         table_b: &Table<E, PCS>, // foreign key table, has duplicates
         a_join_col_index: usize,
         b_join_col_index: usize,
+        range_bag: &Bag<E, PCS>, // needed for UniqueColumnIOP
     ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls())?;
         // calculate the mles needed for the result table
         // calculate the index transform that should be applied to table_a to get rows of the result table 
         let a_index_transform = calc_final_join_one_to_many_index_transform(table_a, table_b, a_join_col_index, b_join_col_index)?;
@@ -33,11 +47,20 @@ where PCS: PolynomialCommitmentScheme<E> {
             res_table_a_mles.push(calc_index_transformed_bag(&a_index_transform, a_col, &table_b.selector)?);
         }
 
-        // put res_table_a_mles into the prover tracker
+        // calculate the sorted join column of table_a, the advice for proving it has no duplicates
+        let a_join_col_bag = Bag::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone());
+        let (a_sorted_mle, a_sorted_sel_mle) = calc_unique_column_advice(&a_join_col_bag)?;
+
+        // put res_table_a_mles and the sorted join column into the prover tracker
         let mut transformed_a_cols = Vec::<TrackedPoly<E, PCS>>::with_capacity(table_a.col_vals.len());
         for col_mle in res_table_a_mles.iter() {
             transformed_a_cols.push(prover_tracker.track_and_commit_poly(col_mle.clone())?);
         }
+        let a_sorted = Bag::new(
+            prover_tracker.track_and_commit_poly(a_sorted_mle)?,
+            prover_tracker.track_and_commit_poly(a_sorted_sel_mle)?,
+        );
+        let advice = FinalJoinOneToManyProvingAdvice { transformed_a_cols, a_sorted };
 
         // invoke the gadget IOPs to prove the result table is correct
         let res_table = FinalJoinOneToManyIOP::prove_with_advice(
@@ -46,7 +69,8 @@ where PCS: PolynomialCommitmentScheme<E> {
             table_b,
             a_join_col_index,
             b_join_col_index,
-            &transformed_a_cols,
+            &advice,
+            range_bag,
         )?;
 
         Ok(res_table)
@@ -58,27 +82,21 @@ where PCS: PolynomialCommitmentScheme<E> {
         table_b: &Table<E, PCS>, // foreign key table, has duplicates
         a_join_col_index: usize,
         b_join_col_index: usize,
-        transformed_a_cols: &Vec::<TrackedPoly<E, PCS>>,
+        advice: &FinalJoinOneToManyProvingAdvice<E, PCS>,
+        range_bag: &Bag<E, PCS>, // needed for UniqueColumnIOP
     ) -> Result<Table<E, PCS>, PolyIOPErrors> {
-        // sanity check that the table_a join_col does not have duplicates, since the IOP is not sound otherwise
-        #[cfg(debug_assertions)] 
-        {
-            let table_a_join_col_evals = table_a.col_vals[a_join_col_index].evaluations();
-            let mut counts = HashMap::<E::ScalarField, usize>::new();
-            for i in 0..table_a_join_col_evals.len() {
-                let val = table_a_join_col_evals[i];
-                let count = counts.entry(val).or_insert(0);
-                *count += 1;
-            }
-            for (_, count) in counts.iter() {
-                if *count > 1 {
-                    return Err(PolyIOPErrors::InvalidParameters(format!("FinalJoinOneToManyIOP Error: table_a join_col has duplicates")));
-                }
-            }
-        }
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls())?;
+        // prove the table_a join_col does not have duplicates, since the IOP is not sound otherwise
+        let a_join_col_bag = Bag::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone());
+        UniqueColumnIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &a_join_col_bag,
+            &advice.a_sorted,
+            range_bag,
+        )?;
 
         // set up the result table
-        let mut res_table_col_polys = transformed_a_cols.clone();
+        let mut res_table_col_polys = advice.transformed_a_cols.clone();
         res_table_col_polys.append(&mut table_b.col_vals.clone());
         let res_table = Table::new(res_table_col_polys, table_b.selector.clone());
 
@@ -106,13 +124,21 @@ where PCS: PolynomialCommitmentScheme<E> {
         table_b: &TableComm<E, PCS>, // foreign key table, has duplicates
         a_join_col_index: usize,
         b_join_col_index: usize,
+        range_bag: &BagComm<E, PCS>,
     ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls())?;
         // tranfer trackerIDs for the transformed_a_cols to the verifier tracker
         let mut transformed_a_cols = Vec::<TrackedComm<E, PCS>>::with_capacity(table_a.col_vals.len());
         for _ in table_a.col_vals.iter() {
             let next_id = verifier_tracker.get_next_id();
-            transformed_a_cols.push(verifier_tracker.transfer_prover_comm(next_id));
+            transformed_a_cols.push(verifier_tracker.transfer_prover_comm(next_id)?);
         }
+        let a_sorted_id = verifier_tracker.get_next_id();
+        let a_sorted_comm = verifier_tracker.transfer_prover_comm(a_sorted_id)?;
+        let a_sorted_sel_id = verifier_tracker.get_next_id();
+        let a_sorted_sel_comm = verifier_tracker.transfer_prover_comm(a_sorted_sel_id)?;
+        let a_sorted = BagComm::new(a_sorted_comm, a_sorted_sel_comm, table_a.num_vars());
+        let advice = FinalJoinOneToManyVerifyingAdvice { transformed_a_cols, a_sorted };
 
         // invoke the gadget IOPs to prove the result table is correct
         let res_table = FinalJoinOneToManyIOP::verify_with_advice(
@@ -121,7 +147,8 @@ where PCS: PolynomialCommitmentScheme<E> {
             table_b,
             a_join_col_index,
             b_join_col_index,
-            &transformed_a_cols,
+            &advice,
+            range_bag,
         )?;
 
         Ok(res_table)
@@ -133,10 +160,21 @@ where PCS: PolynomialCommitmentScheme<E> {
         table_b: &TableComm<E, PCS>, // foreign key table, has duplicates
         a_join_col_index: usize,
         b_join_col_index: usize,
-        transformed_a_cols: &Vec::<TrackedComm<E, PCS>>,
+        advice: &FinalJoinOneToManyVerifyingAdvice<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
     ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls())?;
+        // verify the table_a join_col does not have duplicates
+        let a_join_col_bag = BagComm::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone(), table_a.num_vars());
+        UniqueColumnIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &a_join_col_bag,
+            &advice.a_sorted,
+            range_bag,
+        )?;
+
         // set up the result table
-        let mut res_table_col_polys = transformed_a_cols.clone();
+        let mut res_table_col_polys = advice.transformed_a_cols.clone();
         res_table_col_polys.append(&mut table_b.col_vals.clone());
         let res_table = TableComm::new(res_table_col_polys, table_b.selector.clone(), table_b.num_vars());
 
@@ -157,4 +195,11 @@ where PCS: PolynomialCommitmentScheme<E> {
 
         Ok(res_table)
     }
+
+    fn validate_params(a_has_nulls: bool, b_has_nulls: bool) -> Result<(), PolyIOPErrors> {
+        if a_has_nulls || b_has_nulls {
+            return Err(PolyIOPErrors::InvalidParameters("FinalJoinOneToManyIOP Error: the input tables can not have NULLs".to_string()));
+        }
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -9,11 +9,14 @@ mod test {
     };
 
     use ark_bls12_381::{Bls12_381, Fr};
-    use ark_std::test_rng;
+    use ark_std::{One, test_rng};
 
     use crate::{
         tracker::prelude::*,
-        zksql_poly_iop::final_join_one_to_many::final_join_one_to_many::FinalJoinOneToManyIOP,
+        zksql_poly_iop::{
+            final_join_one_to_many::final_join_one_to_many::{FinalJoinOneToManyIOP, FinalJoinOneToManyProvingAdvice, FinalJoinOneToManyVerifyingAdvice},
+            unique_column::utils::calc_unique_column_advice,
+        },
     };
 
     fn test_final_join_one_to_many() -> Result<(), PolyIOPErrors> {
@@ -28,6 +31,10 @@ mod test {
          // create trackers
          let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
          let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+         // create the range poly and its selector
+         let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect());
+         let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
  
         print!("FinalJoinOneToManyIOP good path test: ");
         let table_a_nv = 2;
@@ -63,9 +70,49 @@ mod test {
             &b_sel_mle, 
             a_join_col_index,
             b_join_col_index,
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // Test good path with a duplicate key on an unselected row of table_a, which is not part of the primary key
+        print!("FinalJoinOneToManyIOP good path with an unselected duplicate test: ");
+        let a_col_0_mle = DenseMultilinearExtension::from_evaluations_vec(table_a_nv, [1, 2, 3, 1].iter().map(|x| Fr::from(*x as u64)).collect());
+        let a_sel_mle = DenseMultilinearExtension::from_evaluations_vec(table_a_nv, [1, 1, 1, 0].iter().map(|x| Fr::from(*x as u64)).collect());
+        let a_cols = vec![a_col_0_mle.clone(), a_col_1_mle.clone()];
+
+        test_final_join_one_to_many_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &a_cols,
+            &b_cols,
+            &a_sel_mle,
+            &b_sel_mle,
+            a_join_col_index,
+            b_join_col_index,
+            &range_mle,
+            &range_sel_mle,
         )?;
         println!("passed");
 
+        // Test bad path with a duplicate key on selected rows of table_a
+        print!("FinalJoinOneToManyIOP bad path with a selected duplicate test: ");
+        let a_sel_mle = DenseMultilinearExtension::from_evaluations_vec(table_a_nv, [1, 1, 1, 1].iter().map(|x| Fr::from(*x as u64)).collect());
+
+        let bad_result = test_final_join_one_to_many_helper(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &a_cols,
+            &b_cols,
+            &a_sel_mle,
+            &b_sel_mle,
+            a_join_col_index,
+            b_join_col_index,
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
 
          Ok(())
     }
@@ -79,6 +126,8 @@ mod test {
         table_b_sel: &DenseMultilinearExtension<E::ScalarField>,
         a_join_col_index: usize,
         b_join_col_index: usize,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
     ) -> Result<(), PolyIOPErrors>
     where
     E: Pairing,
@@ -98,6 +147,7 @@ mod test {
         }
         let table_b_sel_poly = prover_tracker.track_and_commit_poly(table_b_sel.clone())?;
         let table_b = Table::new(table_b_col_polys.clone(), table_b_sel_poly.clone());
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
 
         let res_table = FinalJoinOneToManyIOP::<E, PCS>::prove(
             prover_tracker,
@@ -105,6 +155,7 @@ mod test {
             &table_b,
             a_join_col_index,
             b_join_col_index,
+            &range_bag,
         )?;
         let proof = prover_tracker.compile_proof()?;
         assert_eq!(res_table.col_vals.len(), table_a.col_vals.len() + table_b.col_vals.len());
@@ -118,19 +169,20 @@ mod test {
         let mut table_a_col_comms = Vec::<TrackedComm<E, PCS>>::new();
         for poly in table_a_col_polys {
             let id = poly.id;
-            let comm = verifier_tracker.transfer_prover_comm(id);
+            let comm = verifier_tracker.transfer_prover_comm(id)?;
             table_a_col_comms.push(comm);
         }
-        let table_a_sel_comm = verifier_tracker.transfer_prover_comm(table_a_sel_poly.id);
+        let table_a_sel_comm = verifier_tracker.transfer_prover_comm(table_a_sel_poly.id)?;
         let table_a_comm = TableComm::new(table_a_col_comms, table_a_sel_comm, table_a.num_vars());
         let mut table_b_col_comms = Vec::<TrackedComm<E, PCS>>::new();
         for poly in table_b_col_polys {
             let id = poly.id;
-            let comm = verifier_tracker.transfer_prover_comm(id);
+            let comm = verifier_tracker.transfer_prover_comm(id)?;
             table_b_col_comms.push(comm);
         }
-        let table_b_sel_comm = verifier_tracker.transfer_prover_comm(table_b_sel_poly.id);
+        let table_b_sel_comm = verifier_tracker.transfer_prover_comm(table_b_sel_poly.id)?;
         let table_b_comm = TableComm::new(table_b_col_comms, table_b_sel_comm, table_b.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
 
         FinalJoinOneToManyIOP::<E, PCS>::verify(
             verifier_tracker,
@@ -138,6 +190,7 @@ mod test {
             &table_b_comm,
             a_join_col_index,
             b_join_col_index,
+            &range_bag_comm,
         )?;
         verifier_tracker.verify_claims()?;
 
@@ -164,6 +217,10 @@ mod test {
         let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
         let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
 
+        // create the range poly and its selector
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect());
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
         // // Test good path 1: one-to-one join simple case
         print!("FinalJoinOneToManyIOP good path 1 test: ");
         let table_a_nv = 2;
@@ -199,6 +256,9 @@ mod test {
             a_join_col_index,
             b_join_col_index,
             &transformed_a_cols,
+            None,
+            &range_mle,
+            &range_sel_mle,
         )?;
         println!("passed");
 
@@ -243,6 +303,9 @@ mod test {
             a_join_col_index,
             b_join_col_index,
             &transformed_a_cols,
+            None,
+            &range_mle,
+            &range_sel_mle,
         )?;
         println!("passed");
 
@@ -287,6 +350,9 @@ mod test {
             a_join_col_index,
             b_join_col_index,
             &transformed_a_cols,
+            None,
+            &range_mle,
+            &range_sel_mle,
         );
         assert!(bad_result1.is_err());
         println!("passed");
@@ -332,6 +398,9 @@ mod test {
             a_join_col_index,
             b_join_col_index,
             &transformed_a_cols,
+            None,
+            &range_mle,
+            &range_sel_mle,
         );
         assert!(bad_result2.is_err());
         println!("passed");
@@ -377,11 +446,93 @@ mod test {
             a_join_col_index,
             b_join_col_index,
             &transformed_a_cols,
+            None,
+            &range_mle,
+            &range_sel_mle,
         );
         assert!(bad_result3.is_err());
         println!("passed");
 
 
+        Ok(())
+    }
+
+    fn test_final_join_one_to_many_duplicate_keys() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create trackers
+        let prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its selector
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect());
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // table_a has the key 1 on two selected rows, so the prover can pick either of them for a row of table_b.
+        // the second row of the result takes the row (1, 8) while the first takes (1, 5)
+        let table_a_nv = 2;
+        let table_b_nv = 3;
+        let to_mle = |nv: usize, nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let a_cols = vec![to_mle(table_a_nv, &[1, 2, 3, 1]), to_mle(table_a_nv, &[5, 6, 7, 8])];
+        let b_cols = vec![to_mle(table_b_nv, &[11, 12, 13, 14, 15, 16, 0, 0]), to_mle(table_b_nv, &[1, 1, 2, 2, 3, 3, 0, 0])];
+        let a_sel_mle = to_mle(table_a_nv, &[1, 1, 1, 1]);
+        let b_sel_mle = to_mle(table_b_nv, &[1, 1, 1, 1, 1, 1, 0, 0]);
+        let transformed_a_cols = vec![to_mle(table_b_nv, &[1, 1, 2, 2, 3, 3, 0, 0]), to_mle(table_b_nv, &[5, 8, 6, 6, 7, 7, 0, 0])];
+
+        // the sorted join column can not be computed for the duplicate key
+        print!("FinalJoinOneToManyIOP duplicate key without advice test: ");
+        let bad_result = test_final_join_one_to_many_with_advice_helper(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &a_cols,
+            &b_cols,
+            &a_sel_mle,
+            &b_sel_mle,
+            0,
+            1,
+            &transformed_a_cols,
+            None,
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // hand-built sorted join columns that try to hide the duplicate key, which the prover can prove with but the verifier rejects
+        let bad_sorted_cols = [
+            // the duplicate is dropped
+            [to_mle(table_a_nv, &[0, 1, 2, 3]), to_mle(table_a_nv, &[0, 1, 1, 1])],
+            // the duplicate is kept, so the column is not strictly sorted
+            [to_mle(table_a_nv, &[1, 1, 2, 3]), to_mle(table_a_nv, &[1, 1, 1, 1])],
+            // the duplicate is kept once with a selector of 2
+            [to_mle(table_a_nv, &[0, 1, 2, 3]), to_mle(table_a_nv, &[0, 2, 1, 1])],
+        ];
+        for (i, bad_sorted) in bad_sorted_cols.into_iter().enumerate() {
+            print!("FinalJoinOneToManyIOP duplicate key with advice {} test: ", i + 1);
+            let bad_result = test_final_join_one_to_many_with_advice_helper(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &a_cols,
+                &b_cols,
+                &a_sel_mle,
+                &b_sel_mle,
+                0,
+                1,
+                &transformed_a_cols,
+                Some(bad_sorted.to_vec()),
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(matches!(bad_result, Err(PolyIOPErrors::InvalidVerifier(_))));
+            println!("passed");
+        }
+
         Ok(())
     }
     fn test_final_join_one_to_many_with_advice_helper<E: Pairing, PCS>(
@@ -394,6 +545,9 @@ mod test {
         a_join_col_index: usize,
         b_join_col_index: usize,
         transformed_a_cols: &Vec::<DenseMultilinearExtension<E::ScalarField>>,
+        a_sorted: Option<Vec<DenseMultilinearExtension<E::ScalarField>>>, // the sorted join column of table_a and its selector, computed when not given
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
     ) -> Result<(), PolyIOPErrors>
     where
     E: Pairing,
@@ -413,11 +567,21 @@ mod test {
         }
         let table_b_sel_poly = prover_tracker.track_and_commit_poly(table_b_sel.clone())?;
         let table_b = Table::new(table_b_col_polys.clone(), table_b_sel_poly.clone());
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
         let mut transformed_a_col_polys = Vec::<TrackedPoly<E, PCS>>::new();
         for col in transformed_a_cols {
             let col_poly = prover_tracker.track_and_commit_poly(col.clone())?;
             transformed_a_col_polys.push(col_poly);
         }
+        let a_sorted_mles = match a_sorted {
+            Some(a_sorted_mles) => a_sorted_mles,
+            None => {
+                let (a_sorted_mle, a_sorted_sel_mle) = calc_unique_column_advice(&Bag::new(table_a.col_vals[a_join_col_index].clone(), table_a.selector.clone()))?;
+                vec![a_sorted_mle, a_sorted_sel_mle]
+            },
+        };
+        let a_sorted_bag = Bag::new(prover_tracker.track_and_commit_poly(a_sorted_mles[0].clone())?, prover_tracker.track_and_commit_poly(a_sorted_mles[1].clone())?);
+        let advice = FinalJoinOneToManyProvingAdvice { transformed_a_cols: transformed_a_col_polys, a_sorted: a_sorted_bag.clone() };
 
         let res_table = FinalJoinOneToManyIOP::<E, PCS>::prove_with_advice(
             prover_tracker,
@@ -425,7 +589,8 @@ mod test {
             &table_b,
             a_join_col_index,
             b_join_col_index,
-            &transformed_a_col_polys,
+            &advice,
+            &range_bag,
         )?;
         let proof = prover_tracker.compile_proof()?;
         assert_eq!(res_table.col_vals.len(), table_a.col_vals.len() + table_b.col_vals.len());
@@ -439,25 +604,28 @@ mod test {
         let mut table_a_col_comms = Vec::<TrackedComm<E, PCS>>::new();
         for poly in table_a_col_polys {
             let id = poly.id;
-            let comm = verifier_tracker.transfer_prover_comm(id);
+            let comm = verifier_tracker.transfer_prover_comm(id)?;
             table_a_col_comms.push(comm);
         }
-        let table_a_sel_comm = verifier_tracker.transfer_prover_comm(table_a_sel_poly.id);
+        let table_a_sel_comm = verifier_tracker.transfer_prover_comm(table_a_sel_poly.id)?;
         let table_a_comm = TableComm::new(table_a_col_comms, table_a_sel_comm, table_a.num_vars());
         let mut table_b_col_comms = Vec::<TrackedComm<E, PCS>>::new();
         for poly in table_b_col_polys {
             let id = poly.id;
-            let comm = verifier_tracker.transfer_prover_comm(id);
+            let comm = verifier_tracker.transfer_prover_comm(id)?;
             table_b_col_comms.push(comm);
         }
-        let table_b_sel_comm = verifier_tracker.transfer_prover_comm(table_b_sel_poly.id);
+        let table_b_sel_comm = verifier_tracker.transfer_prover_comm(table_b_sel_poly.id)?;
         let table_b_comm = TableComm::new(table_b_col_comms, table_b_sel_comm, table_b.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
         let mut transformed_a_col_comms = Vec::<TrackedComm<E, PCS>>::new();
-        for poly in transformed_a_col_polys {
+        for poly in advice.transformed_a_cols {
             let id = poly.id;
-            let comm = verifier_tracker.transfer_prover_comm(id);
+            let comm = verifier_tracker.transfer_prover_comm(id)?;
             transformed_a_col_comms.push(comm);
         }
+        let a_sorted_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(a_sorted_bag.poly.id)?, verifier_tracker.transfer_prover_comm(a_sorted_bag.selector.id)?, table_a.num_vars());
+        let advice_comms = FinalJoinOneToManyVerifyingAdvice { transformed_a_cols: transformed_a_col_comms, a_sorted: a_sorted_bag_comm };
 
         FinalJoinOneToManyIOP::<E, PCS>::verify_with_advice(
             verifier_tracker,
@@ -465,7 +633,8 @@ mod test {
             &table_b_comm,
             a_join_col_index,
             b_join_col_index,
-            &transformed_a_col_comms,
+            &advice_comms,
+            &range_bag_comm,
         )?;
         verifier_tracker.verify_claims()?;
 
@@ -490,4 +659,10 @@ mod test {
         let res = test_final_join_one_to_many_with_advice();
         res.unwrap();
     }
+
+    #[test]
+    fn final_join_one_to_many_duplicate_keys_test() {
+        let res = test_final_join_one_to_many_duplicate_keys();
+        res.unwrap();
+    }
 }
\ No newline at end of file
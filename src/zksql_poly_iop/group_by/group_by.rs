@@ -3,7 +3,7 @@
 /// 2. go through the list of aggregation instructions and prove each one on the relevant column
 
 use ark_ec::pairing::Pairing;
-use ark_poly::DenseMultilinearExtension;
+use ark_std::One;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
@@ -11,7 +11,16 @@ use crate::subroutines::pcs::PolynomialCommitmentScheme;
 use crate::{
     tracker::prelude::*,
     zksql_poly_iop::{
-        bag_multitool::bag_multitool::BagMultitoolIOP, bag_supp::{bag_supp::BagSuppIOP, utils::calc_bag_supp_advice}
+        bag_eq::bag_eq::BagEqIOP,
+        bag_inclusion::bag_inclusion::BagInclusionIOP,
+        bag_multitool::bag_multitool::BagMultitoolIOP, bag_supp::{bag_supp::BagSuppIOP, utils::calc_bag_supp_advice},
+        bag_no_zeros::BagNoZerosIOP,
+        bag_presc_perm::bag_presc_perm::BagPrescPermIOP,
+        bag_sort::bag_sort::SortOrder,
+        group_by::utils::{calc_agg_advice, calc_avg_advice, calc_extremum_advice, calc_multi_col_supp_advice},
+        index_transform::utils::{prover_sample_rands, table_row_prover_agg, table_row_verifier_agg, verifier_sample_rands},
+        table_sort::table_sort::TableSortIOP,
+        util::prelude::{prove_range_check, shift_mle_left, verify_range_check},
     },
 };
 
@@ -22,10 +31,10 @@ pub enum AggregationType {
     Avg,
     Min,
     Max,
+    CountDistinct, // COUNT(DISTINCT col), the number of distinct values of the column in each group
     // MEDIAN()
     // MODE()
     // STDDEV()
-    // COUNT(DISTINCT)
     // PERCENTILE_CONT()
     // ...
 }
@@ -58,45 +67,70 @@ pub struct GroupByIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomDat
 
 impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> GroupByIOP<E, PCS> 
 where PCS: PolynomialCommitmentScheme<E> {
-    // pub fn prove(
-    //     prover_tracker: &mut ProverTrackerRef<E, PCS>,
-    //     input_table: &Table<E, PCS>,
-    //     group_by_instructions: &GroupByInstruction,
-    //     range_bag: &Bag<E, PCS>,
-    // ) -> Result<(), PolyIOPErrors> {
-    //     // input validation for group_by_instructions
-    //     if group_by_instructions.grouping_cols.len() > 1 {
-    //         return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: only 1 grouping column is supported for now")));
-    //     }
-    //     let grouping_col_idx = group_by_instructions.grouping_cols[0];
-    //     if grouping_col_idx >= input_table.col_vals.len() {
-    //         return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: grouping column index {} is out of bounds", grouping_col_idx)));
-    //     }
-    //     for (col_idx, _) in group_by_instructions.agg_instr.iter() {
-    //         if *col_idx >= input_table.col_vals.len() {
-    //             return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: aggregation column index {} is out of bounds", *col_idx)));
-    //         }
-    //     }
-
-    //     // calculate the support of the grouping column and put into the prover tracker
-    //     let pre_grouping_col_bag = Bag::new(input_table.col_vals[grouping_col_idx].clone(), input_table.selector.clone());
-    //     let (support_mle, support_sel_mle, support_multiplicity_mle) = calc_bag_supp_advice(&pre_grouping_col_bag)?;
-
-    //     let supp_poly = prover_tracker.track_and_commit_poly(support_mle)?;
-    //     let supp_sel_poly = prover_tracker.track_and_commit_poly(support_sel_mle)?;
-    //     let support_multiplicity_poly = prover_tracker.track_and_commit_poly(support_multiplicity_mle)?;
-
-    //     // TODO:
-    //     // iterate through the list of aggregation instructions and calculate results/advice
-    //     // put everything into a GroupByInstructionWithAdvice struct
-    //     // call prove_with_advice
-        
-
-    //     todo!();
-    // }
+    // computes the support and every aggregation column, commits to them and proves the group by with them as advice
+    // returns the result table
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        group_by_instructions: &GroupByInstruction,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        let grouping_cols = &group_by_instructions.grouping_cols;
+        Self::validate_instructions(
+            grouping_cols,
+            grouping_cols.len(),
+            group_by_instructions.agg_instr.iter().map(|(col_idx, _)| *col_idx),
+            input_table.col_vals.len(),
+            input_table.has_nulls(),
+        )?;
+
+        // calculate the support of the grouping columns and the aggregation columns
+        let (support_mles, support_sel_mle, support_multiplicity_mle) = if grouping_cols.len() == 1 {
+            let pre_grouping_col_bag = Bag::new(input_table.col_vals[grouping_cols[0]].clone(), input_table.selector.clone());
+            let (support_mle, support_sel_mle, support_multiplicity_mle) = calc_bag_supp_advice(&pre_grouping_col_bag)?;
+            (vec![support_mle], support_sel_mle, support_multiplicity_mle)
+        } else {
+            calc_multi_col_supp_advice(input_table, grouping_cols)
+        };
+        let mut agg_mles = Vec::with_capacity(group_by_instructions.agg_instr.len());
+        for (col_idx, agg_type) in group_by_instructions.agg_instr.iter() {
+            let agg_mle = match agg_type {
+                // the count is the support multiplicity, which is already committed
+                AggregationType::Count => None,
+                _ => Some(calc_agg_advice(input_table, grouping_cols, *col_idx, *agg_type, &support_mles, &support_sel_mle)?),
+            };
+            agg_mles.push(agg_mle);
+        }
+
+        // put everything into the prover tracker
+        let mut support_cols = Vec::<TrackedPoly<E, PCS>>::with_capacity(support_mles.len());
+        for support_mle in support_mles {
+            support_cols.push(prover_tracker.track_and_commit_poly(support_mle)?);
+        }
+        let support_sel = prover_tracker.track_and_commit_poly(support_sel_mle)?;
+        let support_multiplicity = prover_tracker.track_and_commit_poly(support_multiplicity_mle)?;
+        let mut agg_instr = Vec::<(usize, AggregationType, TrackedPoly<E, PCS>)>::with_capacity(agg_mles.len());
+        for ((col_idx, agg_type), agg_mle) in group_by_instructions.agg_instr.iter().zip(agg_mles) {
+            let agg_poly = match agg_mle {
+                Some(agg_mle) => prover_tracker.track_and_commit_poly(agg_mle)?,
+                None => support_multiplicity.clone(),
+            };
+            agg_instr.push((*col_idx, *agg_type, agg_poly));
+        }
+        let group_by_instructions = GroupByInstructionWithProvingAdvice {
+            grouping_cols: grouping_cols.clone(),
+            support_cols,
+            support_sel,
+            support_multiplicity,
+            agg_instr,
+        };
+
+        Self::prove_with_advice(prover_tracker, input_table, &group_by_instructions, range_bag)
+    }
 
     // prove with advice
     // returns the result table
+    // AVG results are range checked, so only averages smaller than the size of the range bag can be proven
     pub fn prove_with_advice(
         prover_tracker: &mut ProverTrackerRef<E, PCS>,
         input_table: &Table<E, PCS>,
@@ -104,38 +138,65 @@ where PCS: PolynomialCommitmentScheme<E> {
         range_bag: &Bag<E, PCS>,
     ) -> Result<Table<E, PCS>, PolyIOPErrors> { 
         // 0. input validation for group_by_instructions
-        if group_by_instructions.grouping_cols.len() > 1 {
-            return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: only 1 grouping column is supported for now")));
-        }
-        let grouping_col_idx = group_by_instructions.grouping_cols[0];
-        if grouping_col_idx >= input_table.col_vals.len() {
-            return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: grouping column index {} is out of bounds", grouping_col_idx)));
-        }
-        for (col_idx, _, _) in group_by_instructions.agg_instr.iter() {
-            if *col_idx >= input_table.col_vals.len() {
-                return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: aggregation column index {} is out of bounds", *col_idx)));
-            }
-        }
-        let supp_poly = group_by_instructions.support_cols[0].clone();
+        Self::validate_instructions(
+            &group_by_instructions.grouping_cols,
+            group_by_instructions.support_cols.len(),
+            group_by_instructions.agg_instr.iter().map(|(col_idx, _, _)| *col_idx),
+            input_table.col_vals.len(),
+            input_table.has_nulls(),
+        )?;
+        let grouping_cols = &group_by_instructions.grouping_cols;
         let supp_sel_poly = group_by_instructions.support_sel.clone();
         let support_multiplicity_poly = group_by_instructions.support_multiplicity.clone();
-        let pre_grouping_col_bag = Bag::new(input_table.col_vals[grouping_col_idx].clone(), input_table.selector.clone());
 
         // 1. prove the grouping bag is a the support of the pre-grouping bag
         //    as part of this proof, it shows that support_multiplicity_poly is the relevent multiplicity vector for proving 
         //    the grouping bag is a subset of the support of the pre-grouping bag
-        let grouped_col_bag = Bag::new(supp_poly.clone(), supp_sel_poly.clone());
-        BagSuppIOP::<E, PCS>::prove_with_advice(
-            prover_tracker,
-            &pre_grouping_col_bag.clone(),
-            &grouped_col_bag.clone(),
-            &support_multiplicity_poly.clone(),
-            range_bag,
-        )?;
-        let mut res_table_col_polys = Vec::<TrackedPoly<E, PCS>>::with_capacity(1 + group_by_instructions.agg_instr.len());
-        res_table_col_polys.push(supp_poly.clone());
+        //    With several grouping columns, each row's key is compressed into one value with random coefficients.
+        //    The compressed keys go through the same steps as BagSuppIOP, except for the strict sort, which is done
+        //    lexicographically on the support columns with TableSortIOP since the compressed keys have no useful order
+        let (pre_grouping_col_bag, grouped_col_bag, rand_coeffs) = if grouping_cols.len() == 1 {
+            let pre_grouping_col_bag = Bag::new(input_table.col_vals[grouping_cols[0]].clone(), input_table.selector.clone());
+            let grouped_col_bag = Bag::new(group_by_instructions.support_cols[0].clone(), supp_sel_poly.clone());
+            BagSuppIOP::<E, PCS>::prove_with_advice(
+                prover_tracker,
+                &pre_grouping_col_bag.clone(),
+                &grouped_col_bag.clone(),
+                &support_multiplicity_poly.clone(),
+                range_bag,
+            )?;
+            (pre_grouping_col_bag, grouped_col_bag, Vec::new())
+        } else {
+            let pre_grouping_table = Table::new(grouping_cols.iter().map(|i| input_table.col_vals[*i].clone()).collect(), input_table.selector.clone());
+            let support_table = Table::new(group_by_instructions.support_cols.clone(), supp_sel_poly.clone());
+            let rand_coeffs = prover_sample_rands(prover_tracker, grouping_cols.len())?;
+            let pre_grouping_col_bag = table_row_prover_agg(&pre_grouping_table, &rand_coeffs)?;
+            let grouped_col_bag = table_row_prover_agg(&support_table, &rand_coeffs)?;
+            BagInclusionIOP::<E, PCS>::prove_with_advice(
+                prover_tracker,
+                &pre_grouping_col_bag,
+                &grouped_col_bag,
+                &support_multiplicity_poly,
+            )?;
+            BagNoZerosIOP::<E, PCS>::prove(
+                prover_tracker,
+                &Bag::new(support_multiplicity_poly.clone(), supp_sel_poly.clone()),
+            )?;
+            TableSortIOP::<E, PCS>::prove_sorted(prover_tracker, &support_table, &Self::lex_sort_keys(grouping_cols.len()), range_bag, true)?;
+            (pre_grouping_col_bag, grouped_col_bag, rand_coeffs)
+        };
+
+        // the result table starts with the grouping columns, followed by one column per aggregation
+        let mut res_table_col_polys = Vec::<TrackedPoly<E, PCS>>::with_capacity(grouping_cols.len() + group_by_instructions.agg_instr.len());
+        res_table_col_polys.extend(group_by_instructions.support_cols.iter().cloned());
         let mut res_table = Table::new(res_table_col_polys, supp_sel_poly.clone());
 
+        // AVG is tied to the SUM of the same column when there is one, so the sum is only proven once
+        let mut sum_polys: HashMap<usize, TrackedPoly<E, PCS>> = group_by_instructions.agg_instr.iter()
+            .filter(|(_, agg_type, _)| *agg_type == AggregationType::Sum)
+            .map(|(col_idx, _, agg_poly)| (*col_idx, agg_poly.clone()))
+            .collect();
+
         // 2. go through the list of aggregation instructions and prove each one on the relevant column
         for (col_idx, agg_instr, agg_poly) in group_by_instructions.agg_instr.iter() {
             match agg_instr {
@@ -149,23 +210,77 @@ where PCS: PolynomialCommitmentScheme<E> {
                     // use bag_multitool with the grouping columns as values and the agg_poly as multiplicities
                     BagMultitoolIOP::<E, PCS>::prove(
                         prover_tracker,
-                        &vec![pre_grouping_col_bag.clone()],
-                        &vec![grouped_col_bag.clone()],
-                        &vec![pre_agg_poly.clone()],
-                        &vec![agg_poly.clone()],
+                        std::slice::from_ref(&pre_grouping_col_bag),
+                        std::slice::from_ref(&grouped_col_bag),
+                        std::slice::from_ref(&pre_agg_poly),
+                        std::slice::from_ref(agg_poly),
                     )?;
+                    res_table.col_vals.push(agg_poly.clone());
                 },
                 AggregationType::Avg => {
                     // prove the avg aggregation is correct
-                    todo!();
+                    // avg is the floor of sum / count, shown by sum = avg * count + rem with 0 <= rem < count
+                    // the sum is the SUM column of the same input column. Without one, the sum is committed here
+                    // and proven the same way as in the Sum case. rem is advice committed here
+                    let pre_agg_poly = input_table.col_vals[*col_idx].clone();
+                    let (sum_mle, rem_mle) = calc_avg_advice(&pre_grouping_col_bag, &pre_agg_poly, &grouped_col_bag, &support_multiplicity_poly, agg_poly);
+                    let sum_poly = match sum_polys.get(col_idx) {
+                        Some(sum_poly) => sum_poly.clone(),
+                        None => {
+                            let sum_poly = prover_tracker.track_and_commit_poly(sum_mle)?;
+                            BagMultitoolIOP::<E, PCS>::prove(
+                                prover_tracker,
+                                std::slice::from_ref(&pre_grouping_col_bag),
+                                std::slice::from_ref(&grouped_col_bag),
+                                std::slice::from_ref(&pre_agg_poly),
+                                std::slice::from_ref(&sum_poly),
+                            )?;
+                            sum_polys.insert(*col_idx, sum_poly.clone());
+                            sum_poly
+                        },
+                    };
+                    let rem_poly = prover_tracker.track_and_commit_poly(rem_mle)?;
+
+                    // sum = avg * count + rem on every group
+                    let avg_check_poly = supp_sel_poly.mul_poly(&agg_poly.mul_poly(&support_multiplicity_poly).add_poly(&rem_poly).sub_poly(&sum_poly));
+                    prover_tracker.add_zerocheck_claim(avg_check_poly.id);
+
+                    // range check rem and count - rem - 1 to show 0 <= rem < count
+                    // avg is also range checked, so avg * count + rem cannot wrap around the field.
+                    // this is what limits avg to less than the size of the range bag
+                    let rem_upper_poly = support_multiplicity_poly.sub_poly(&rem_poly).add_scalar(-E::ScalarField::one());
+                    for range_checked_poly in [rem_poly, rem_upper_poly, agg_poly.clone()] {
+                        prove_range_check(prover_tracker, &Bag::new(range_checked_poly, supp_sel_poly.clone()), range_bag)?;
+                    }
+                    res_table.col_vals.push(agg_poly.clone());
                 },
-                AggregationType::Min => {
-                    // prove the min aggregation is correct
-                    todo!();
+                AggregationType::Min | AggregationType::Max => {
+                    // prove the min or max aggregation is correct
+                    let pre_agg_poly = input_table.col_vals[*col_idx].clone();
+                    Self::prove_extremum(
+                        prover_tracker,
+                        &pre_grouping_col_bag,
+                        &pre_agg_poly,
+                        &grouped_col_bag,
+                        agg_poly,
+                        *agg_instr == AggregationType::Max,
+                        range_bag,
+                    )?;
+                    res_table.col_vals.push(agg_poly.clone());
                 },
-                AggregationType::Max => {
-                    // prove the max aggregation is correct
-                    todo!();
+                AggregationType::CountDistinct => {
+                    // prove the count distinct aggregation is correct
+                    let mut pre_pair_cols = grouping_cols.iter().map(|i| input_table.col_vals[*i].clone()).collect::<Vec<_>>();
+                    pre_pair_cols.push(input_table.col_vals[*col_idx].clone());
+                    Self::prove_count_distinct(
+                        prover_tracker,
+                        &Table::new(pre_pair_cols, input_table.selector.clone()),
+                        &grouped_col_bag,
+                        &rand_coeffs,
+                        agg_poly,
+                        range_bag,
+                    )?;
+                    res_table.col_vals.push(agg_poly.clone());
                 },
             }
         }
@@ -174,9 +289,51 @@ where PCS: PolynomialCommitmentScheme<E> {
         Ok(res_table)
     }
 
+    // takes the commitments to the support and the aggregation columns from the proof, in the order the prover committed them
+    // returns the result table, and the verifying advice built from the commitments
     pub fn verify(
-    ) -> Result<(), PolyIOPErrors> {
-        todo!()
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        group_by_instructions: &GroupByInstruction,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(TableComm<E, PCS>, GroupByInstructionWithVerifyingAdvice<E, PCS>), PolyIOPErrors> {
+        let grouping_cols = &group_by_instructions.grouping_cols;
+        Self::validate_instructions(
+            grouping_cols,
+            grouping_cols.len(),
+            group_by_instructions.agg_instr.iter().map(|(col_idx, _)| *col_idx),
+            input_table.col_vals.len(),
+            input_table.has_nulls(),
+        )?;
+
+        let next_comm = |tracker: &mut VerifierTrackerRef<E, PCS>| {
+            let id = tracker.get_next_id();
+            tracker.transfer_prover_comm(id)
+        };
+        let mut support_cols = Vec::<TrackedComm<E, PCS>>::with_capacity(grouping_cols.len());
+        for _ in 0..grouping_cols.len() {
+            support_cols.push(next_comm(verifier_tracker)?);
+        }
+        let support_sel = next_comm(verifier_tracker)?;
+        let support_multiplicity = next_comm(verifier_tracker)?;
+        let mut agg_instr = Vec::<(usize, AggregationType, TrackedComm<E, PCS>)>::with_capacity(group_by_instructions.agg_instr.len());
+        for (col_idx, agg_type) in group_by_instructions.agg_instr.iter() {
+            let agg_comm = match agg_type {
+                AggregationType::Count => support_multiplicity.clone(),
+                _ => next_comm(verifier_tracker)?,
+            };
+            agg_instr.push((*col_idx, *agg_type, agg_comm));
+        }
+        let group_by_instructions = GroupByInstructionWithVerifyingAdvice {
+            grouping_cols: grouping_cols.clone(),
+            support_cols,
+            support_sel,
+            support_multiplicity,
+            agg_instr,
+        };
+
+        let res_table = Self::verify_with_advice(verifier_tracker, input_table, &group_by_instructions, range_bag)?;
+        Ok((res_table, group_by_instructions))
     }
 
     pub fn verify_with_advice(
@@ -191,39 +348,63 @@ where PCS: PolynomialCommitmentScheme<E> {
         let res_table_nv = input_table.num_vars();
 
         // 0. input validation for group_by_instructions
-        if group_by_instructions.grouping_cols.len() > 1 {
-            return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: only 1 grouping column is supported for now")));
-        }
-        let grouping_col_idx = group_by_instructions.grouping_cols[0];
-        if grouping_col_idx >= input_table.col_vals.len() {
-            return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: grouping column index {} is out of bounds", grouping_col_idx)));
-        }
-        for (col_idx, _, _) in group_by_instructions.agg_instr.iter() {
-            if *col_idx >= input_table.col_vals.len() {
-                return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: aggregation column index {} is out of bounds", *col_idx)));
-            }
-        }
-        let supp_comm = group_by_instructions.support_cols[0].clone();
+        Self::validate_instructions(
+            &group_by_instructions.grouping_cols,
+            group_by_instructions.support_cols.len(),
+            group_by_instructions.agg_instr.iter().map(|(col_idx, _, _)| *col_idx),
+            input_table.col_vals.len(),
+            input_table.has_nulls(),
+        )?;
+        let grouping_cols = &group_by_instructions.grouping_cols;
         let supp_sel_comm = group_by_instructions.support_sel.clone();
         let support_multiplicity_comm = group_by_instructions.support_multiplicity.clone();
-        let pre_grouping_col_bag = BagComm::new(input_table.col_vals[grouping_col_idx].clone(), input_table.selector.clone(), input_table.num_vars());
-        
 
         // 1. verify the grouping bag is a the support of the pre-grouping bag
         //    as part of this proof, it shows that support_multiplicity_poly is the relevent multiplicity vector for proving 
         //    the grouping bag is a subset of the support of the pre-grouping bag
-        let grouped_col_bag = BagComm::new(supp_comm.clone(), supp_sel_comm.clone(), res_table_nv); 
-        BagSuppIOP::<E, PCS>::verify_with_advice(
-            verifier_tracker,
-            &pre_grouping_col_bag,
-            &grouped_col_bag,
-            &support_multiplicity_comm,
-            &range_bag,
-        )?;
-        let mut res_table_col_comms = Vec::<TrackedComm<E, PCS>>::with_capacity(1 + group_by_instructions.agg_instr.len());
-        res_table_col_comms.push(supp_comm.clone());
+        //    several grouping columns are compressed into one key, see prove_with_advice
+        let (pre_grouping_col_bag, grouped_col_bag, rand_coeffs) = if grouping_cols.len() == 1 {
+            let pre_grouping_col_bag = BagComm::new(input_table.col_vals[grouping_cols[0]].clone(), input_table.selector.clone(), input_table.num_vars());
+            let grouped_col_bag = BagComm::new(group_by_instructions.support_cols[0].clone(), supp_sel_comm.clone(), res_table_nv);
+            BagSuppIOP::<E, PCS>::verify_with_advice(
+                verifier_tracker,
+                &pre_grouping_col_bag,
+                &grouped_col_bag,
+                &support_multiplicity_comm,
+                range_bag,
+            )?;
+            (pre_grouping_col_bag, grouped_col_bag, Vec::new())
+        } else {
+            let pre_grouping_table = TableComm::new(grouping_cols.iter().map(|i| input_table.col_vals[*i].clone()).collect(), input_table.selector.clone(), input_table.num_vars());
+            let support_table = TableComm::new(group_by_instructions.support_cols.clone(), supp_sel_comm.clone(), res_table_nv);
+            let rand_coeffs = verifier_sample_rands(verifier_tracker, grouping_cols.len())?;
+            let pre_grouping_col_bag = table_row_verifier_agg(&pre_grouping_table, &rand_coeffs)?;
+            let grouped_col_bag = table_row_verifier_agg(&support_table, &rand_coeffs)?;
+            BagInclusionIOP::<E, PCS>::verify_with_advice(
+                verifier_tracker,
+                &pre_grouping_col_bag,
+                &grouped_col_bag,
+                &support_multiplicity_comm,
+            )?;
+            BagNoZerosIOP::<E, PCS>::verify(
+                verifier_tracker,
+                &BagComm::new(support_multiplicity_comm.clone(), supp_sel_comm.clone(), res_table_nv),
+            )?;
+            TableSortIOP::<E, PCS>::verify_sorted(verifier_tracker, &support_table, &Self::lex_sort_keys(grouping_cols.len()), range_bag, true)?;
+            (pre_grouping_col_bag, grouped_col_bag, rand_coeffs)
+        };
+
+        // the result table starts with the grouping columns, followed by one column per aggregation
+        let mut res_table_col_comms = Vec::<TrackedComm<E, PCS>>::with_capacity(grouping_cols.len() + group_by_instructions.agg_instr.len());
+        res_table_col_comms.extend(group_by_instructions.support_cols.iter().cloned());
         let mut res_table = TableComm::new(res_table_col_comms, supp_sel_comm.clone(), res_table_nv);
 
+        // AVG is tied to the SUM of the same column when there is one, see prove_with_advice
+        let mut sum_comms: HashMap<usize, TrackedComm<E, PCS>> = group_by_instructions.agg_instr.iter()
+            .filter(|(_, agg_type, _)| *agg_type == AggregationType::Sum)
+            .map(|(col_idx, _, agg_comm)| (*col_idx, agg_comm.clone()))
+            .collect();
+
         // 2. go through the list of aggregation instructions and prove each one on the relevant column
         for (col_idx, agg_instr, agg_poly) in group_by_instructions.agg_instr.iter() {
             match agg_instr {
@@ -237,23 +418,73 @@ where PCS: PolynomialCommitmentScheme<E> {
                     // use bag_multitool with the grouping columns as values and the agg_poly as multiplicities
                     BagMultitoolIOP::<E, PCS>::verify(
                         verifier_tracker,
-                        &vec![pre_grouping_col_bag.clone()],
-                        &vec![grouped_col_bag.clone()],
-                        &vec![pre_agg_poly],
-                        &vec![agg_poly.clone()],
+                        std::slice::from_ref(&pre_grouping_col_bag),
+                        std::slice::from_ref(&grouped_col_bag),
+                        &[pre_agg_poly],
+                        std::slice::from_ref(agg_poly),
                     )?;
+                    res_table.col_vals.push(agg_poly.clone());
                 },
                 AggregationType::Avg => {
-                    // prove the avg aggregation is correct
-                    todo!();
+                    // verify the avg aggregation is correct, see prove_with_advice
+                    let pre_agg_comm = input_table.col_vals[*col_idx].clone();
+                    let sum_comm = match sum_comms.get(col_idx) {
+                        Some(sum_comm) => sum_comm.clone(),
+                        None => {
+                            let sum_id = verifier_tracker.get_next_id();
+                            let sum_comm = verifier_tracker.transfer_prover_comm(sum_id)?;
+                            BagMultitoolIOP::<E, PCS>::verify(
+                                verifier_tracker,
+                                std::slice::from_ref(&pre_grouping_col_bag),
+                                std::slice::from_ref(&grouped_col_bag),
+                                &[pre_agg_comm],
+                                std::slice::from_ref(&sum_comm),
+                            )?;
+                            sum_comms.insert(*col_idx, sum_comm.clone());
+                            sum_comm
+                        },
+                    };
+                    let rem_id = verifier_tracker.get_next_id();
+                    let rem_comm = verifier_tracker.transfer_prover_comm(rem_id)?;
+
+                    // sum = avg * count + rem on every group
+                    let avg_check_comm = supp_sel_comm.mul_comms(&agg_poly.mul_comms(&support_multiplicity_comm).add_comms(&rem_comm).sub_comms(&sum_comm));
+                    verifier_tracker.add_zerocheck_claim(avg_check_comm.id);
+
+                    // range check rem, count - rem - 1 and avg
+                    let rem_upper_comm = support_multiplicity_comm.sub_comms(&rem_comm).add_scalar(-E::ScalarField::one());
+                    for range_checked_comm in [rem_comm, rem_upper_comm, agg_poly.clone()] {
+                        verify_range_check(verifier_tracker, &BagComm::new(range_checked_comm, supp_sel_comm.clone(), res_table_nv), range_bag)?;
+                    }
+                    res_table.col_vals.push(agg_poly.clone());
                 },
-                AggregationType::Min => {
-                    // prove the min aggregation is correct
-                    todo!();
+                AggregationType::Min | AggregationType::Max => {
+                    // verify the min or max aggregation is correct
+                    let pre_agg_comm = input_table.col_vals[*col_idx].clone();
+                    Self::verify_extremum(
+                        verifier_tracker,
+                        &pre_grouping_col_bag,
+                        &pre_agg_comm,
+                        &grouped_col_bag,
+                        agg_poly,
+                        *agg_instr == AggregationType::Max,
+                        range_bag,
+                    )?;
+                    res_table.col_vals.push(agg_poly.clone());
                 },
-                AggregationType::Max => {
-                    // prove the max aggregation is correct
-                    todo!();
+                AggregationType::CountDistinct => {
+                    // verify the count distinct aggregation is correct
+                    let mut pre_pair_cols = grouping_cols.iter().map(|i| input_table.col_vals[*i].clone()).collect::<Vec<_>>();
+                    pre_pair_cols.push(input_table.col_vals[*col_idx].clone());
+                    Self::verify_count_distinct(
+                        verifier_tracker,
+                        &TableComm::new(pre_pair_cols, input_table.selector.clone(), input_table.num_vars()),
+                        &grouped_col_bag,
+                        &rand_coeffs,
+                        agg_poly,
+                        range_bag,
+                    )?;
+                    res_table.col_vals.push(agg_poly.clone());
                 },
             }
         }
@@ -261,6 +492,309 @@ where PCS: PolynomialCommitmentScheme<E> {
         // TODO: do we want outputs? 
         Ok(res_table)
     }
-        
-}
 
+    // Proves agg_poly holds the max (or min) of each group, using a sort of the (group, value) pairs
+    //  1. the sorted pairs are a permutation of the selected input pairs, with the selected rows first
+    //  2. shifted copies of the sorted columns give access to the next row, as in BagStrictSortIOP
+    //  3. group_end marks the last row of each run. Within a run the group stays the same and the value
+    //     does not decrease (max) or increase (min), shown with a range check on the difference
+    //  4. the (group, value) pairs at the ends of the runs are the same bag as the (group, agg) pairs of the result.
+    //     since each group is in the result once, each group is a single run, and its end holds the extremum
+    // the steps between values in a group are range checked, see prove_range_check
+    fn prove_extremum(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        pre_grouping_col_bag: &Bag<E, PCS>,
+        pre_agg_poly: &TrackedPoly<E, PCS>,
+        grouped_col_bag: &Bag<E, PCS>,
+        agg_poly: &TrackedPoly<E, PCS>,
+        is_max: bool,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        let nv = pre_grouping_col_bag.num_vars();
+        let one = E::ScalarField::one();
+
+        // commit to the sorted columns, the run ends, and the shifted columns
+        let [sorted_group_mle, sorted_agg_mle, sorted_sel_mle, group_end_mle] = calc_extremum_advice(pre_grouping_col_bag, pre_agg_poly, is_max);
+        let next_group_mle = shift_mle_left(&sorted_group_mle);
+        let next_agg_mle = shift_mle_left(&sorted_agg_mle);
+        let next_sel_mle = shift_mle_left(&sorted_sel_mle);
+        let sorted_group = prover_tracker.track_and_commit_poly(sorted_group_mle)?;
+        let sorted_agg = prover_tracker.track_and_commit_poly(sorted_agg_mle)?;
+        let sorted_sel = prover_tracker.track_and_commit_poly(sorted_sel_mle)?;
+        let group_end = prover_tracker.track_and_commit_poly(group_end_mle)?;
+        let next_group = prover_tracker.track_and_commit_poly(next_group_mle)?;
+        let next_agg = prover_tracker.track_and_commit_poly(next_agg_mle)?;
+        let next_sel = prover_tracker.track_and_commit_poly(next_sel_mle)?;
+
+        // 1. the sorted pairs are a permutation of the input pairs, combining each pair as group + gamma * value
+        let gamma = prover_tracker.get_and_append_challenge(b"gamma")?;
+        let sorted_pairs = sorted_group.add_poly(&sorted_agg.mul_scalar(gamma));
+        let pre_pairs = pre_grouping_col_bag.poly.add_poly(&pre_agg_poly.mul_scalar(gamma));
+        BagEqIOP::<E, PCS>::prove(
+            prover_tracker,
+            &Bag::new(pre_pairs, pre_grouping_col_bag.selector.clone()),
+            &Bag::new(sorted_pairs.clone(), sorted_sel.clone()),
+        )?;
+
+        // 2. the next columns are the sorted columns shifted by one, all shown at once with a random combination
+        let delta = prover_tracker.get_and_append_challenge(b"delta")?;
+        let one_poly = prover_tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: one });
+        let shift_perm_poly = prover_tracker.track_public_poly(PublicPoly::ShiftedIdentity { num_vars: nv, shift: 1 });
+        let sorted_combined = sorted_group.add_poly(&sorted_agg.mul_scalar(delta)).add_poly(&sorted_sel.mul_scalar(delta * delta));
+        let next_combined = next_group.add_poly(&next_agg.mul_scalar(delta)).add_poly(&next_sel.mul_scalar(delta * delta));
+        BagPrescPermIOP::<E, PCS>::prove(
+            prover_tracker,
+            &Bag::new(sorted_combined, one_poly.clone()),
+            &Bag::new(next_combined, one_poly.clone()),
+            &shift_perm_poly,
+        )?;
+
+        // 3. the run checks. sorted_sel and group_end are boolean, a row that is not a run end is followed by
+        //    a selected row of the same group, and the last row always ends a run since its next row wraps around
+        let last_row = prover_tracker.track_public_poly(PublicPoly::Eq { r: vec![one; nv] });
+        let in_run = sorted_sel.mul_poly(&group_end.mul_scalar(-one).add_scalar(one));
+        let next_in_table = next_sel.sub_poly(&next_sel.mul_poly(&last_row));
+        prover_tracker.add_zerocheck_claim(sorted_sel.mul_poly(&sorted_sel).sub_poly(&sorted_sel).id);
+        prover_tracker.add_zerocheck_claim(group_end.mul_poly(&group_end).sub_poly(&group_end).id);
+        prover_tracker.add_zerocheck_claim(in_run.sub_poly(&in_run.mul_poly(&next_in_table)).id);
+        prover_tracker.add_zerocheck_claim(in_run.mul_poly(&next_group.sub_poly(&sorted_group)).id);
+        let step_poly = if is_max { next_agg.sub_poly(&sorted_agg) } else { sorted_agg.sub_poly(&next_agg) };
+        prove_range_check(prover_tracker, &Bag::new(step_poly, in_run), range_bag)?;
+
+        // 4. the run ends are the result pairs
+        let result_pairs = grouped_col_bag.poly.add_poly(&agg_poly.mul_scalar(gamma));
+        BagEqIOP::<E, PCS>::prove(
+            prover_tracker,
+            &Bag::new(sorted_pairs, sorted_sel.mul_poly(&group_end)),
+            &Bag::new(result_pairs, grouped_col_bag.selector.clone()),
+        )?;
+
+        Ok(())
+    }
+
+    fn verify_extremum(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        pre_grouping_col_bag: &BagComm<E, PCS>,
+        pre_agg_comm: &TrackedComm<E, PCS>,
+        grouped_col_bag: &BagComm<E, PCS>,
+        agg_comm: &TrackedComm<E, PCS>,
+        is_max: bool,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        let nv = pre_grouping_col_bag.num_vars();
+        let one = E::ScalarField::one();
+
+        // get the sorted columns, the run ends, and the shifted columns
+        let next_comm = |tracker: &mut VerifierTrackerRef<E, PCS>| {
+            let id = tracker.get_next_id();
+            tracker.transfer_prover_comm(id)
+        };
+        let sorted_group = next_comm(verifier_tracker)?;
+        let sorted_agg = next_comm(verifier_tracker)?;
+        let sorted_sel = next_comm(verifier_tracker)?;
+        let group_end = next_comm(verifier_tracker)?;
+        let next_group = next_comm(verifier_tracker)?;
+        let next_agg = next_comm(verifier_tracker)?;
+        let next_sel = next_comm(verifier_tracker)?;
+
+        // 1. the sorted pairs are a permutation of the input pairs
+        let gamma = verifier_tracker.get_and_append_challenge(b"gamma")?;
+        let sorted_pairs = sorted_group.add_comms(&sorted_agg.mul_scalar(gamma));
+        let pre_pairs = pre_grouping_col_bag.poly.add_comms(&pre_agg_comm.mul_scalar(gamma));
+        BagEqIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &BagComm::new(pre_pairs, pre_grouping_col_bag.selector.clone(), nv),
+            &BagComm::new(sorted_pairs.clone(), sorted_sel.clone(), nv),
+        )?;
+
+        // 2. the next columns are the sorted columns shifted by one
+        let delta = verifier_tracker.get_and_append_challenge(b"delta")?;
+        let one_comm = verifier_tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: one });
+        let shift_perm_comm = verifier_tracker.track_public_poly(PublicPoly::ShiftedIdentity { num_vars: nv, shift: 1 });
+        let sorted_combined = sorted_group.add_comms(&sorted_agg.mul_scalar(delta)).add_comms(&sorted_sel.mul_scalar(delta * delta));
+        let next_combined = next_group.add_comms(&next_agg.mul_scalar(delta)).add_comms(&next_sel.mul_scalar(delta * delta));
+        BagPrescPermIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &BagComm::new(sorted_combined, one_comm.clone(), nv),
+            &BagComm::new(next_combined, one_comm.clone(), nv),
+            &shift_perm_comm,
+        )?;
+
+        // 3. the run checks
+        let last_row = verifier_tracker.track_public_poly(PublicPoly::Eq { r: vec![one; nv] });
+        let in_run = sorted_sel.mul_comms(&group_end.mul_scalar(-one).add_scalar(one));
+        let next_in_table = next_sel.sub_comms(&next_sel.mul_comms(&last_row));
+        verifier_tracker.add_zerocheck_claim(sorted_sel.mul_comms(&sorted_sel).sub_comms(&sorted_sel).id);
+        verifier_tracker.add_zerocheck_claim(group_end.mul_comms(&group_end).sub_comms(&group_end).id);
+        verifier_tracker.add_zerocheck_claim(in_run.sub_comms(&in_run.mul_comms(&next_in_table)).id);
+        verifier_tracker.add_zerocheck_claim(in_run.mul_comms(&next_group.sub_comms(&sorted_group)).id);
+        let step_comm = if is_max { next_agg.sub_comms(&sorted_agg) } else { sorted_agg.sub_comms(&next_agg) };
+        verify_range_check(verifier_tracker, &BagComm::new(step_comm, in_run, nv), range_bag)?;
+
+        // 4. the run ends are the result pairs
+        let result_pairs = grouped_col_bag.poly.add_comms(&agg_comm.mul_scalar(gamma));
+        BagEqIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &BagComm::new(sorted_pairs, sorted_sel.mul_comms(&group_end), nv),
+            &BagComm::new(result_pairs, grouped_col_bag.selector.clone(), grouped_col_bag.num_vars()),
+        )?;
+
+        Ok(())
+    }
+
+    // Proves agg_poly holds the number of distinct values of each group, in two support steps
+    //  1. the distinct (group, value) pairs are the support of the input pairs. Like the grouping with several columns,
+    //     the pairs are compressed with random coefficients for the inclusion, and their uniqueness is shown
+    //     with a lexicographic strict sort of the pair columns
+    //  2. counting the distinct pairs by their group gives agg_poly, shown with bag_multitool where each distinct pair
+    //     has multiplicity one. The group of a pair is compressed the same way as the grouping columns
+    // pre_pair_table holds the grouping columns followed by the aggregated column,
+    // and rand_coeffs are the coefficients that compressed the grouping columns, empty for a single grouping column
+    fn prove_count_distinct(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        pre_pair_table: &Table<E, PCS>,
+        grouped_col_bag: &Bag<E, PCS>,
+        rand_coeffs: &[E::ScalarField],
+        agg_poly: &TrackedPoly<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        let nv = pre_pair_table.num_vars();
+        let num_pair_cols = pre_pair_table.col_vals.len();
+        let one = E::ScalarField::one();
+
+        // commit to the distinct pairs and their multiplicities
+        let pair_col_idxs = (0..num_pair_cols).collect::<Vec<_>>();
+        let (pair_col_mles, pair_sel_mle, pair_mult_mle) = calc_multi_col_supp_advice(pre_pair_table, &pair_col_idxs);
+        let mut pair_cols = Vec::<TrackedPoly<E, PCS>>::with_capacity(num_pair_cols);
+        for mle in pair_col_mles {
+            pair_cols.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        let pair_sel = prover_tracker.track_and_commit_poly(pair_sel_mle)?;
+        let pair_mult = prover_tracker.track_and_commit_poly(pair_mult_mle)?;
+
+        // 1. the distinct pairs are the support of the input pairs
+        //    pair_sel is a multiplicity in step 2, so it also has to be boolean, which the sort shows
+        let pair_table = Table::new(pair_cols.clone(), pair_sel.clone());
+        let pair_coeffs = prover_sample_rands(prover_tracker, num_pair_cols)?;
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &table_row_prover_agg(pre_pair_table, &pair_coeffs)?,
+            &table_row_prover_agg(&pair_table, &pair_coeffs)?,
+            &pair_mult,
+        )?;
+        BagNoZerosIOP::<E, PCS>::prove(
+            prover_tracker,
+            &Bag::new(pair_mult, pair_sel.clone()),
+        )?;
+        TableSortIOP::<E, PCS>::prove_sorted(prover_tracker, &pair_table, &Self::lex_sort_keys(num_pair_cols), range_bag, true)?;
+
+        // 2. the distinct pairs counted by group are agg_poly
+        let pair_group_poly = if rand_coeffs.is_empty() {
+            pair_cols[0].clone()
+        } else {
+            let pair_group_table = Table::new(pair_cols[..num_pair_cols - 1].to_vec(), pair_sel.clone());
+            table_row_prover_agg(&pair_group_table, &rand_coeffs.to_vec())?.poly
+        };
+        let one_poly = prover_tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: one });
+        BagMultitoolIOP::<E, PCS>::prove(
+            prover_tracker,
+            &[Bag::new(pair_group_poly, pair_sel)],
+            std::slice::from_ref(grouped_col_bag),
+            &[one_poly],
+            std::slice::from_ref(agg_poly),
+        )?;
+
+        Ok(())
+    }
+
+    fn verify_count_distinct(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        pre_pair_table: &TableComm<E, PCS>,
+        grouped_col_bag: &BagComm<E, PCS>,
+        rand_coeffs: &[E::ScalarField],
+        agg_comm: &TrackedComm<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        let nv = pre_pair_table.num_vars();
+        let num_pair_cols = pre_pair_table.col_vals.len();
+        let one = E::ScalarField::one();
+
+        // get the distinct pairs and their multiplicities
+        let mut pair_cols = Vec::<TrackedComm<E, PCS>>::with_capacity(num_pair_cols);
+        for _ in 0..num_pair_cols {
+            let id = verifier_tracker.get_next_id();
+            pair_cols.push(verifier_tracker.transfer_prover_comm(id)?);
+        }
+        let pair_sel_id = verifier_tracker.get_next_id();
+        let pair_sel = verifier_tracker.transfer_prover_comm(pair_sel_id)?;
+        let pair_mult_id = verifier_tracker.get_next_id();
+        let pair_mult = verifier_tracker.transfer_prover_comm(pair_mult_id)?;
+
+        // 1. the distinct pairs are the support of the input pairs
+        let pair_table = TableComm::new(pair_cols.clone(), pair_sel.clone(), nv);
+        let pair_coeffs = verifier_sample_rands(verifier_tracker, num_pair_cols)?;
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &table_row_verifier_agg(pre_pair_table, &pair_coeffs)?,
+            &table_row_verifier_agg(&pair_table, &pair_coeffs)?,
+            &pair_mult,
+        )?;
+        BagNoZerosIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &BagComm::new(pair_mult, pair_sel.clone(), nv),
+        )?;
+        TableSortIOP::<E, PCS>::verify_sorted(verifier_tracker, &pair_table, &Self::lex_sort_keys(num_pair_cols), range_bag, true)?;
+
+        // 2. the distinct pairs counted by group are agg_comm
+        let pair_group_comm = if rand_coeffs.is_empty() {
+            pair_cols[0].clone()
+        } else {
+            let pair_group_table = TableComm::new(pair_cols[..num_pair_cols - 1].to_vec(), pair_sel.clone(), nv);
+            table_row_verifier_agg(&pair_group_table, &rand_coeffs.to_vec())?.poly
+        };
+        let one_comm = verifier_tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: one });
+        BagMultitoolIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &[BagComm::new(pair_group_comm, pair_sel, nv)],
+            std::slice::from_ref(grouped_col_bag),
+            &[one_comm],
+            std::slice::from_ref(agg_comm),
+        )?;
+
+        Ok(())
+    }
+
+    fn validate_instructions(
+        grouping_cols: &[usize],
+        num_support_cols: usize,
+        agg_cols: impl Iterator<Item = usize>,
+        num_input_cols: usize,
+        input_has_nulls: bool,
+    ) -> Result<(), PolyIOPErrors> {
+        if input_has_nulls {
+            return Err(PolyIOPErrors::InvalidParameters("GroupByIOP Error: the input table can not have NULLs".to_string()));
+        }
+        if grouping_cols.is_empty() {
+            return Err(PolyIOPErrors::InvalidParameters("GroupByIOP Error: there are no grouping columns".to_string()));
+        }
+        if num_support_cols != grouping_cols.len() {
+            return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: {} support columns for {} grouping columns", num_support_cols, grouping_cols.len())));
+        }
+        for grouping_col_idx in grouping_cols.iter() {
+            if *grouping_col_idx >= num_input_cols {
+                return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: grouping column index {} is out of bounds", grouping_col_idx)));
+            }
+        }
+        for col_idx in agg_cols {
+            if col_idx >= num_input_cols {
+                return Err(PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: aggregation column index {} is out of bounds", col_idx)));
+            }
+        }
+        Ok(())
+    }
+
+    // the keys of a lexicographic sort by every column in ascending order
+    fn lex_sort_keys(num_cols: usize) -> Vec<(usize, SortOrder)> {
+        (0..num_cols).map(|j| (j, SortOrder::Asc)).collect()
+    }
+}
@@ -1,2 +1,3 @@
 pub mod group_by;
+pub mod utils;
 mod test;
\ No newline at end of file
@@ -14,7 +14,7 @@ mod test {
 
     use crate::{
         tracker::prelude::*,
-        zksql_poly_iop::group_by::group_by::{AggregationType, GroupByIOP, GroupByInstructionWithProvingAdvice, GroupByInstructionWithVerifyingAdvice},
+        zksql_poly_iop::group_by::group_by::{AggregationType, GroupByIOP, GroupByInstruction, GroupByInstructionWithProvingAdvice, GroupByInstructionWithVerifyingAdvice},
         
     };
 
@@ -268,12 +268,454 @@ mod test {
         )?;
         println!("passed");
 
+        // the sums are output as a column of the result table, after the grouping column
+        print!("Testing group by sum outputs the sum column: ");
+        let instructions = GroupByInstruction {
+            grouping_cols: grouping_cols.clone(),
+            agg_instr: vec![(1, AggregationType::Sum)],
+        };
+        let res_cols = test_group_by_without_advice_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &table_vals,
+            &table_sel,
+            &instructions,
+            &range_mle,
+            &range_sel_mle,
+            range_nv,
+        )?;
+        assert_eq!(res_cols.len(), 3); // grouping column, sum column, selector
+        assert_eq!(res_cols[0], support_col_vals);
+        assert_eq!(res_cols[1], agg_vals);
+        assert_eq!(res_cols[2], support_sel_vals);
+        println!("passed");
+
         // Test bad path with bad agg nums
         print!("Testing group by sum bad path: ");
         let bad_agg_nums = vec![0, 0, 0, 0, 0, 16, 36, 29]; // switched nums to the wrong order
         let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
         let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
-        let bad_agg_mle_instructions = vec![(1, AggregationType::Sum, bad_agg_mle.clone())];
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Sum, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    fn test_group_by_avg() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param.clone());
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // Test the avg aggregation
+        let pre_nv = 3; 
+        let pre_group_nums = [1, 1, 1, 2, 2, 3, 0, 0];
+        let pre_sel_nums = [1, 1, 1, 1, 1, 1, 0, 0];
+        let pre_agg_nums = [11, 12, 13, 14, 15, 16, 0, 0];
+        let support_col_nums = [0, 0, 0, 0, 0, 1, 2, 3]; // recall these need to be ordered for the support IOP gadget to pass
+        let support_sel_nums = [0, 0, 0, 0, 0, 1, 1, 1];
+        let support_mult_nums = [0, 0, 0, 0, 0, 3, 2, 1];
+        let agg_nums = [0, 0, 0, 0, 0, 12, 14, 16]; // sums are 36, 29, 16, so the avg of group 2 is rounded down
+
+        let pre_group_vals = pre_group_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let pre_group_sel_vals = pre_sel_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let pre_agg_vals = pre_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_col_vals = support_col_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_sel_vals = support_sel_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_mult_vals = support_mult_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let agg_vals = agg_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+
+        let pre_group_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_group_vals.clone());
+        let pre_group_sel_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_group_sel_vals.clone());
+        let pre_agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_agg_vals.clone());
+        let support_col_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_col_vals.clone());
+        let support_sel_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_sel_vals.clone());
+        let support_mult_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_mult_vals.clone());
+        let agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, agg_vals.clone());
+
+        let table_vals = vec![pre_group_mle.clone(), pre_agg_mle.clone()];
+        let table_sel = pre_group_sel_mle.clone();
+        let grouping_cols = vec![0];
+        let agg_mle_instructions = vec![(1, AggregationType::Avg, agg_mle.clone())];
+
+        print!("Testing group by avg good path: ");
+        test_group_by_helper(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        )?;
+        println!("passed");
+
+        // Test bad path 1: the avg is not the floor of sum / count
+        print!("Testing group by avg bad path 1: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 12, 13, 16]; // 13 * 2 leaves a remainder of 3, which is not less than the count
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Avg, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 2: the avg is not the floor of sum / count
+        print!("Testing group by avg bad path 2: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 12, 15, 16]; // 15 * 2 is more than the sum, so the remainder is negative
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Avg, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test the largest average the range bag allows, and the first one it does not, on fresh trackers
+        for (big_num, in_range) in [(1023, true), (1024, false)] {
+            print!("Testing group by avg of {}: ", big_num);
+            let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param.clone());
+            let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param.clone());
+            let big_pre_agg_nums = [11, 12, 13, 14, 15, big_num, 0, 0];
+            let big_agg_nums = [0, 0, 0, 0, 0, 12, 14, big_num];
+            let big_pre_agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, big_pre_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect());
+            let big_agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, big_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect());
+            let big_table_vals = vec![pre_group_mle.clone(), big_pre_agg_mle];
+            let big_agg_mle_instructions = vec![(1, AggregationType::Avg, big_agg_mle)];
+            let big_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+                &mut prover_tracker, 
+                &mut verifier_tracker, 
+                &big_table_vals, 
+                &table_sel, 
+                &grouping_cols, 
+                &vec![support_col_mle.clone()], 
+                &support_sel_mle, 
+                &support_mult_mle, 
+                &big_agg_mle_instructions, 
+                &range_mle, 
+                &range_sel_mle, 
+                range_nv,
+            );
+            assert_eq!(big_result.is_ok(), in_range);
+            println!("passed");
+        }
+
+        Ok(())
+    }
+
+    fn test_group_by_max() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // Test the max aggregation
+        let pre_nv = 3; 
+        let pre_group_nums = [2, 1, 3, 1, 2, 1, 0, 0]; // groups are out of order, which the sort in the gadget takes care of
+        let pre_sel_nums = [1, 1, 1, 1, 1, 1, 0, 0];
+        let pre_agg_nums = [15, 12, 16, 13, 14, 11, 0, 0];
+        let support_col_nums = [0, 0, 0, 0, 0, 1, 2, 3]; // recall these need to be ordered for the support IOP gadget to pass
+        let support_sel_nums = [0, 0, 0, 0, 0, 1, 1, 1];
+        let support_mult_nums = [0, 0, 0, 0, 0, 3, 2, 1];
+        let agg_nums = [0, 0, 0, 0, 0, 13, 15, 16]; // group 1 has 12, 13, 11, group 2 has 15, 14 and group 3 has 16
+
+        let pre_group_vals = pre_group_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let pre_group_sel_vals = pre_sel_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let pre_agg_vals = pre_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_col_vals = support_col_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_sel_vals = support_sel_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_mult_vals = support_mult_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let agg_vals = agg_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+
+        let pre_group_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_group_vals.clone());
+        let pre_group_sel_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_group_sel_vals.clone());
+        let pre_agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_agg_vals.clone());
+        let support_col_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_col_vals.clone());
+        let support_sel_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_sel_vals.clone());
+        let support_mult_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_mult_vals.clone());
+        let agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, agg_vals.clone());
+
+        let table_vals = vec![pre_group_mle.clone(), pre_agg_mle.clone()];
+        let table_sel = pre_group_sel_mle.clone();
+        let grouping_cols = vec![0];
+        let agg_mle_instructions = vec![(1, AggregationType::Max, agg_mle.clone())];
+
+        print!("Testing group by max good path: ");
+        test_group_by_helper(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        )?;
+        println!("passed");
+
+        // Test bad path 1: a value of the group that is not the max
+        print!("Testing group by max bad path 1: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 12, 15, 16];
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Max, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 2: a value larger than the max that is not in the group
+        print!("Testing group by max bad path 2: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 14, 15, 16];
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Max, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 3: the min instead of the max
+        print!("Testing group by max bad path 3: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 11, 14, 16];
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Max, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    fn test_group_by_min() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // Test the min aggregation
+        let pre_nv = 3; 
+        let pre_group_nums = [2, 1, 3, 1, 2, 1, 0, 0]; // groups are out of order, which the sort in the gadget takes care of
+        let pre_sel_nums = [1, 1, 1, 1, 1, 1, 0, 0];
+        let pre_agg_nums = [15, 12, 16, 13, 14, 11, 0, 0];
+        let support_col_nums = [0, 0, 0, 0, 0, 1, 2, 3]; // recall these need to be ordered for the support IOP gadget to pass
+        let support_sel_nums = [0, 0, 0, 0, 0, 1, 1, 1];
+        let support_mult_nums = [0, 0, 0, 0, 0, 3, 2, 1];
+        let agg_nums = [0, 0, 0, 0, 0, 11, 14, 16]; // group 1 has 12, 13, 11, group 2 has 15, 14 and group 3 has 16
+
+        let pre_group_vals = pre_group_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let pre_group_sel_vals = pre_sel_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let pre_agg_vals = pre_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_col_vals = support_col_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_sel_vals = support_sel_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let support_mult_vals = support_mult_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+        let agg_vals = agg_nums.iter().map(|x| Fr::from(*x as u64)).collect::<Vec<Fr>>();
+
+        let pre_group_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_group_vals.clone());
+        let pre_group_sel_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_group_sel_vals.clone());
+        let pre_agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, pre_agg_vals.clone());
+        let support_col_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_col_vals.clone());
+        let support_sel_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_sel_vals.clone());
+        let support_mult_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, support_mult_vals.clone());
+        let agg_mle = DenseMultilinearExtension::from_evaluations_vec(pre_nv, agg_vals.clone());
+
+        let table_vals = vec![pre_group_mle.clone(), pre_agg_mle.clone()];
+        let table_sel = pre_group_sel_mle.clone();
+        let grouping_cols = vec![0];
+        let agg_mle_instructions = vec![(1, AggregationType::Min, agg_mle.clone())];
+
+        print!("Testing group by min good path: ");
+        test_group_by_helper(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        )?;
+        println!("passed");
+
+        // Test bad path 1: a value of the group that is not the min
+        print!("Testing group by min bad path 1: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 12, 14, 16];
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Min, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 2: a value smaller than the min that is not in the group
+        print!("Testing group by min bad path 2: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 10, 14, 16];
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Min, bad_agg_mle.clone())];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &table_sel, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad path 3: the max instead of the min
+        print!("Testing group by min bad path 3: ");
+        let bad_agg_nums = [0, 0, 0, 0, 0, 13, 15, 16];
+        let bad_agg_vals = bad_agg_nums.iter().map(|x| Fr::from(*x as u64)).collect();
+        let bad_agg_mle = DenseMultilinearExtension::from_evaluations_vec(3, bad_agg_vals);
+        let bad_agg_mle_instructions = vec![(1, AggregationType::Min, bad_agg_mle.clone())];
         let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
             &mut prover_tracker, 
             &mut verifier_tracker, 
@@ -294,6 +736,434 @@ mod test {
         Ok(())
     }
 
+    fn test_group_by_multi_col() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // Test grouping by two columns, with groups (1, 5), (1, 6) and (2, 5)
+        let pre_nv = 3;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(pre_nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let pre_group_a_mle = to_mle(&[1, 2, 1, 2, 1, 1, 0, 0]);
+        let pre_group_b_mle = to_mle(&[5, 5, 6, 5, 5, 6, 0, 0]);
+        let pre_agg_mle = to_mle(&[10, 20, 30, 40, 50, 60, 0, 0]);
+        let pre_sel_mle = to_mle(&[1, 1, 1, 1, 1, 1, 0, 0]);
+        let support_a_mle = to_mle(&[1, 1, 2, 0, 0, 0, 0, 0]); // rows need to be ordered lexicographically, the second column can go down when the first goes up
+        let support_b_mle = to_mle(&[5, 6, 5, 0, 0, 0, 0, 0]);
+        let support_sel_mle = to_mle(&[1, 1, 1, 0, 0, 0, 0, 0]);
+        let support_mult_mle = to_mle(&[2, 2, 2, 0, 0, 0, 0, 0]);
+        let sum_mle = to_mle(&[60, 90, 60, 0, 0, 0, 0, 0]);
+        let max_mle = to_mle(&[50, 60, 40, 0, 0, 0, 0, 0]);
+
+        let table_vals = vec![pre_group_a_mle.clone(), pre_agg_mle.clone(), pre_group_b_mle.clone()];
+        let grouping_cols = vec![0, 2];
+        let agg_mle_instructions = vec![
+            (0, AggregationType::Count, support_mult_mle.clone()),
+            (1, AggregationType::Sum, sum_mle.clone()),
+            (1, AggregationType::Max, max_mle.clone()),
+        ];
+
+        print!("Testing group by multiple columns good path: ");
+        test_group_by_helper(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &pre_sel_mle, 
+            &grouping_cols, 
+            &vec![support_a_mle.clone(), support_b_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &agg_mle_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        )?;
+        println!("passed");
+
+        // Test bad paths with bad supports, with a count aggregation
+        let count_instructions = vec![(0, AggregationType::Count, support_mult_mle.clone())];
+        let bad_supports = [
+            // (1, 5) is split into two groups
+            ([1, 1, 1, 2, 0, 0, 0, 0], [5, 5, 6, 5, 0, 0, 0, 0], [1, 1, 1, 1, 0, 0, 0, 0], [1, 1, 2, 2, 0, 0, 0, 0]),
+            // the groups are not sorted
+            ([1, 2, 1, 0, 0, 0, 0, 0], [5, 5, 6, 0, 0, 0, 0, 0], [1, 1, 1, 0, 0, 0, 0, 0], [2, 2, 2, 0, 0, 0, 0, 0]),
+            // (2, 5) is missing
+            ([1, 1, 0, 0, 0, 0, 0, 0], [5, 6, 0, 0, 0, 0, 0, 0], [1, 1, 0, 0, 0, 0, 0, 0], [2, 2, 0, 0, 0, 0, 0, 0]),
+            // (2, 5) is replaced with (2, 6)
+            ([1, 1, 2, 0, 0, 0, 0, 0], [5, 6, 6, 0, 0, 0, 0, 0], [1, 1, 1, 0, 0, 0, 0, 0], [2, 2, 2, 0, 0, 0, 0, 0]),
+            // the groups are not at the front
+            ([0, 0, 0, 0, 0, 1, 1, 2], [0, 0, 0, 0, 0, 5, 6, 5], [0, 0, 0, 0, 0, 1, 1, 1], [0, 0, 0, 0, 0, 2, 2, 2]),
+        ];
+        for (i, (bad_a, bad_b, bad_sel, bad_mult)) in bad_supports.iter().enumerate() {
+            print!("Testing group by multiple columns bad support {}: ", i + 1);
+            let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+                &mut prover_tracker, 
+                &mut verifier_tracker, 
+                &table_vals, 
+                &pre_sel_mle, 
+                &grouping_cols, 
+                &vec![to_mle(bad_a), to_mle(bad_b)], 
+                &to_mle(bad_sel), 
+                &to_mle(bad_mult), 
+                &count_instructions, 
+                &range_mle, 
+                &range_sel_mle, 
+                range_nv,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        // Test bad path with a sum that is right for a single grouping column
+        print!("Testing group by multiple columns bad sum: ");
+        let bad_agg_instructions = vec![(1, AggregationType::Sum, to_mle(&[150, 90, 60, 0, 0, 0, 0, 0]))];
+        let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &pre_sel_mle, 
+            &grouping_cols, 
+            &vec![support_a_mle.clone(), support_b_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &bad_agg_instructions, 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    fn test_group_by_count_distinct() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // Test the count distinct aggregation, with most (group, value) pairs repeated
+        let pre_nv = 4;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(pre_nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let pre_group_mle = to_mle(&[1, 2, 1, 1, 2, 3, 1, 2, 1, 1, 3, 2, 1, 3, 0, 0]);
+        let pre_agg_mle = to_mle(&[7, 5, 7, 7, 5, 9, 8, 6, 7, 8, 9, 5, 7, 9, 0, 0]);
+        let pre_sel_mle = to_mle(&[1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0]);
+        let support_col_mle = to_mle(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3]);
+        let support_sel_mle = to_mle(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1]);
+        let support_mult_mle = to_mle(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 4, 3]);
+        let agg_mle = to_mle(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 2, 1]); // group 1 has 7 and 8, group 2 has 5 and 6, group 3 has 9
+
+        let table_vals = vec![pre_group_mle.clone(), pre_agg_mle.clone()];
+        let grouping_cols = vec![0];
+
+        print!("Testing group by count distinct good path: ");
+        test_group_by_helper(
+            &mut prover_tracker, 
+            &mut verifier_tracker, 
+            &table_vals, 
+            &pre_sel_mle, 
+            &grouping_cols, 
+            &vec![support_col_mle.clone()], 
+            &support_sel_mle, 
+            &support_mult_mle, 
+            &vec![(1, AggregationType::CountDistinct, agg_mle.clone())], 
+            &range_mle, 
+            &range_sel_mle, 
+            range_nv,
+        )?;
+        println!("passed");
+
+        // Test count distinct without advice, next to a count, on one and on two grouping columns
+        print!("Testing group by count distinct without advice: ");
+        let to_vals = |nums: &[u64]| nums.iter().map(|x| Fr::from(*x)).collect::<Vec<Fr>>();
+        let instructions = GroupByInstruction {
+            grouping_cols: vec![0],
+            agg_instr: vec![(1, AggregationType::CountDistinct), (1, AggregationType::Count)],
+        };
+        let res_cols = test_group_by_without_advice_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &table_vals,
+            &pre_sel_mle,
+            &instructions,
+            &range_mle,
+            &range_sel_mle,
+            range_nv,
+        )?;
+        assert_eq!(res_cols[1], agg_mle.evaluations);
+        assert_eq!(res_cols[2], support_mult_mle.evaluations);
+
+        let pre_subgroup_mle = to_mle(&[1, 1, 1, 2, 1, 1, 2, 1, 1, 2, 1, 1, 1, 1, 0, 0]);
+        let multi_col_table_vals = vec![pre_group_mle.clone(), pre_agg_mle.clone(), pre_subgroup_mle.clone()];
+        let instructions = GroupByInstruction {
+            grouping_cols: vec![0, 2],
+            agg_instr: vec![(1, AggregationType::CountDistinct)],
+        };
+        let res_cols = test_group_by_without_advice_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &multi_col_table_vals,
+            &pre_sel_mle,
+            &instructions,
+            &range_mle,
+            &range_sel_mle,
+            range_nv,
+        )?;
+        // group (1, 1) has 7, group (1, 2) has 7 and 8, group (2, 1) has 5 and 6, group (3, 1) has 9
+        assert_eq!(res_cols[0], to_vals(&[1, 1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(res_cols[1], to_vals(&[1, 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(res_cols[2], to_vals(&[1, 2, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        println!("passed");
+
+        // Test bad paths: the count with duplicates, a count that is one too many, and a count moved to another group
+        for (i, bad_agg_nums) in [[7, 4, 3], [2, 3, 1], [2, 1, 2]].iter().enumerate() {
+            print!("Testing group by count distinct bad path {}: ", i + 1);
+            let mut bad_agg_nums_padded = [0; 16];
+            bad_agg_nums_padded[13..].copy_from_slice(bad_agg_nums);
+            let bad_result = test_group_by_helper::<Bls12_381, MultilinearKzgPCS::<Bls12_381>>(
+                &mut prover_tracker, 
+                &mut verifier_tracker, 
+                &table_vals, 
+                &pre_sel_mle, 
+                &grouping_cols, 
+                &vec![support_col_mle.clone()], 
+                &support_sel_mle, 
+                &support_mult_mle, 
+                &vec![(1, AggregationType::CountDistinct, to_mle(&bad_agg_nums_padded))], 
+                &range_mle, 
+                &range_sel_mle, 
+                range_nv,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        Ok(())
+    }
+
+    fn test_group_by_without_advice() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(10))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        let pre_nv = 3;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(pre_nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let to_vals = |nums: &[u64]| nums.iter().map(|x| Fr::from(*x)).collect::<Vec<Fr>>();
+
+        // Test every aggregation on a single grouping column
+        print!("Testing group by without advice on one column: ");
+        let table_vals = vec![to_mle(&[2, 1, 3, 1, 2, 1, 0, 0]), to_mle(&[15, 12, 16, 13, 14, 11, 0, 0])];
+        let table_sel = to_mle(&[1, 1, 1, 1, 1, 1, 0, 0]);
+        let instructions = GroupByInstruction {
+            grouping_cols: vec![0],
+            agg_instr: vec![
+                (1, AggregationType::Count),
+                (1, AggregationType::Sum),
+                (1, AggregationType::Avg),
+                (1, AggregationType::Min),
+                (1, AggregationType::Max),
+            ],
+        };
+        let mut res_cols = test_group_by_without_advice_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &table_vals,
+            &table_sel,
+            &instructions,
+            &range_mle,
+            &range_sel_mle,
+            range_nv,
+        )?;
+        let res_sel = res_cols.pop().unwrap();
+        assert_eq!(res_sel, to_vals(&[0, 0, 0, 0, 0, 1, 1, 1]));
+        assert_eq!(res_cols, vec![
+            to_vals(&[0, 0, 0, 0, 0, 1, 2, 3]),
+            to_vals(&[0, 0, 0, 0, 0, 3, 2, 1]),
+            to_vals(&[0, 0, 0, 0, 0, 36, 29, 16]),
+            to_vals(&[0, 0, 0, 0, 0, 12, 14, 16]),
+            to_vals(&[0, 0, 0, 0, 0, 11, 14, 16]),
+            to_vals(&[0, 0, 0, 0, 0, 13, 15, 16]),
+        ]);
+        println!("passed");
+
+        // Test two grouping columns
+        print!("Testing group by without advice on two columns: ");
+        let table_vals = vec![to_mle(&[1, 2, 1, 2, 1, 1, 0, 0]), to_mle(&[10, 20, 30, 40, 50, 60, 0, 0]), to_mle(&[5, 5, 6, 5, 5, 6, 0, 0])];
+        let instructions = GroupByInstruction {
+            grouping_cols: vec![0, 2],
+            agg_instr: vec![(1, AggregationType::Sum), (1, AggregationType::Max)],
+        };
+        let mut res_cols = test_group_by_without_advice_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &table_vals,
+            &table_sel,
+            &instructions,
+            &range_mle,
+            &range_sel_mle,
+            range_nv,
+        )?;
+        let res_sel = res_cols.pop().unwrap();
+        assert_eq!(res_sel, to_vals(&[1, 1, 1, 0, 0, 0, 0, 0]));
+        assert_eq!(res_cols, vec![
+            to_vals(&[1, 1, 2, 0, 0, 0, 0, 0]),
+            to_vals(&[5, 6, 5, 0, 0, 0, 0, 0]),
+            to_vals(&[60, 90, 60, 0, 0, 0, 0, 0]),
+            to_vals(&[50, 60, 40, 0, 0, 0, 0, 0]),
+        ]);
+        println!("passed");
+
+        // Test bad instructions
+        print!("Testing group by without advice bad instructions: ");
+        for bad_grouping_cols in [vec![], vec![3], vec![0, 3]] {
+            let bad_instructions = GroupByInstruction {
+                grouping_cols: bad_grouping_cols,
+                agg_instr: vec![(1, AggregationType::Sum)],
+            };
+            let bad_result = test_group_by_without_advice_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker,
+                &mut verifier_tracker,
+                &table_vals,
+                &table_sel,
+                &bad_instructions,
+                &range_mle,
+                &range_sel_mle,
+                range_nv,
+            );
+            assert!(bad_result.is_err());
+        }
+        println!("passed");
+
+        Ok(())
+    }
+
+    // proves and verifies the group by without advice
+    // returns the evaluations of the result table columns, followed by the evaluations of its selector
+    fn test_group_by_without_advice_helper<E: Pairing, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_vals: &Vec<DenseMultilinearExtension<E::ScalarField>>,
+        table_sel: &DenseMultilinearExtension<E::ScalarField>,
+        group_by_instructions: &GroupByInstruction,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_nv: usize,
+    ) -> Result<Vec<Vec<E::ScalarField>>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let mut table_val_polys = Vec::new();
+        for vals_mle in table_vals {
+            let poly = prover_tracker.track_and_commit_poly(vals_mle.clone())?;
+            table_val_polys.push(poly);
+        }
+        let table_sel_poly = prover_tracker.track_and_commit_poly(table_sel.clone())?;
+        let table = Table::new(table_val_polys.clone(), table_sel_poly.clone());
+        let range_poly = prover_tracker.track_and_commit_poly(range_mle.clone())?;
+        let range_sel_poly = prover_tracker.track_and_commit_poly(range_sel_mle.clone())?;
+        let range_bag = Bag::new(range_poly.clone(), range_sel_poly.clone());
+
+        let res_table = GroupByIOP::<E, PCS>::prove(
+            prover_tracker,
+            &table,
+            group_by_instructions,
+            &range_bag,
+        )?;
+        // read the result before compiling the proof, which pads the polys to the max number of variables
+        let mut res_evals: Vec<Vec<E::ScalarField>> = res_table.col_vals.iter().map(|col| col.evaluations()).collect();
+        res_evals.push(res_table.selector.evaluations());
+        let proof = prover_tracker.compile_proof()?;
+
+        verifier_tracker.set_compiled_proof(proof);
+        let mut table_val_comms = Vec::new();
+        for vals_poly in table_val_polys {
+            let comm = verifier_tracker.transfer_prover_comm(vals_poly.id)?;
+            table_val_comms.push(comm);
+        }
+        let table_sel_comm = verifier_tracker.transfer_prover_comm(table_sel_poly.id)?;
+        let table_comm = TableComm::new(table_val_comms, table_sel_comm, table.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_poly.id)?, verifier_tracker.transfer_prover_comm(range_sel_poly.id)?, range_nv);
+        let (res_table_comm, verifying_advice) = GroupByIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &table_comm,
+            group_by_instructions,
+            &range_bag_comm,
+        )?;
+        verifier_tracker.verify_claims()?;
+
+        // the result table and the advice match on both sides
+        assert_eq!(res_table_comm.col_vals.len(), res_table.col_vals.len());
+        for (comm, poly) in res_table_comm.col_vals.iter().zip(res_table.col_vals.iter()) {
+            assert_eq!(comm.id, poly.id);
+        }
+        assert_eq!(res_table_comm.selector.id, res_table.selector.id);
+
+        // the verifying advice is made of the result table's commitments
+        let num_grouping_cols = group_by_instructions.grouping_cols.len();
+        assert_eq!(verifying_advice.grouping_cols, group_by_instructions.grouping_cols);
+        assert_eq!(verifying_advice.support_sel.id, res_table_comm.selector.id);
+        for (support_comm, res_comm) in verifying_advice.support_cols.iter().zip(res_table_comm.col_vals.iter()) {
+            assert_eq!(support_comm.id, res_comm.id);
+        }
+        assert_eq!(verifying_advice.agg_instr.len(), group_by_instructions.agg_instr.len());
+        for (((col_idx, agg_type, agg_comm), instr), res_comm) in verifying_advice.agg_instr.iter()
+            .zip(group_by_instructions.agg_instr.iter())
+            .zip(res_table_comm.col_vals[num_grouping_cols..].iter())
+        {
+            assert_eq!((*col_idx, *agg_type), *instr);
+            assert_eq!(agg_comm.id, res_comm.id);
+        }
+
+        // check that the ProverTracker and VerifierTracker are in the same state        
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_evals)
+    }
+
     fn test_group_by_helper<E: Pairing, PCS>(
         prover_tracker: &mut ProverTrackerRef<E, PCS>,
         verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
@@ -342,32 +1212,33 @@ mod test {
         let range_sel_poly = prover_tracker.track_and_commit_poly(range_sel_mle.clone())?;
         let range_bag = Bag::new(range_poly.clone(), range_sel_poly.clone());
     
-        GroupByIOP::<E, PCS>::prove_with_advice(
+        let res_table = GroupByIOP::<E, PCS>::prove_with_advice(
             prover_tracker,
             &table,
             &group_by_instructions,
             &range_bag,
         )?;
+        assert_eq!(res_table.col_vals.len(), grouping_cols.len() + agg_mle_instructions.len());
         let proof = prover_tracker.compile_proof()?;
 
         verifier_tracker.set_compiled_proof(proof);
         let mut table_val_comms = Vec::new();
         for vals_poly in table_val_polys {
-            let comm = verifier_tracker.transfer_prover_comm(vals_poly.id);
+            let comm = verifier_tracker.transfer_prover_comm(vals_poly.id)?;
             table_val_comms.push(comm);
         }
-        let table_sel_comm = verifier_tracker.transfer_prover_comm(table_sel_poly.id);
+        let table_sel_comm = verifier_tracker.transfer_prover_comm(table_sel_poly.id)?;
         let table_comm = TableComm::new(table_val_comms, table_sel_comm, table.num_vars());
         let mut support_comms = Vec::new();
         for support_poly in support_polys {
-            let comm = verifier_tracker.transfer_prover_comm(support_poly.id);
+            let comm = verifier_tracker.transfer_prover_comm(support_poly.id)?;
             support_comms.push(comm);
         }
-        let support_sel_comm = verifier_tracker.transfer_prover_comm(support_sel_poly.id);
-        let support_mult_comm = verifier_tracker.transfer_prover_comm(support_mult_poly.id);
+        let support_sel_comm = verifier_tracker.transfer_prover_comm(support_sel_poly.id)?;
+        let support_mult_comm = verifier_tracker.transfer_prover_comm(support_mult_poly.id)?;
         let mut verifier_agg_instructions: Vec<(usize, AggregationType, TrackedComm<E, PCS>)> = Vec::new();
         for (col_idx, agg_type, agg_poly) in prover_agg_instructions {
-            let comm = verifier_tracker.transfer_prover_comm(agg_poly.id);
+            let comm = verifier_tracker.transfer_prover_comm(agg_poly.id)?;
             verifier_agg_instructions.push((col_idx, agg_type.clone(), comm));
         }
         let group_by_instructions = GroupByInstructionWithVerifyingAdvice {
@@ -377,13 +1248,14 @@ mod test {
             support_multiplicity: support_mult_comm,
             agg_instr: verifier_agg_instructions,
         };
-        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_poly.id), verifier_tracker.transfer_prover_comm(range_sel_poly.id), range_nv);
-        GroupByIOP::<E, PCS>::verify_with_advice(
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_poly.id)?, verifier_tracker.transfer_prover_comm(range_sel_poly.id)?, range_nv);
+        let res_table_comm = GroupByIOP::<E, PCS>::verify_with_advice(
             verifier_tracker,
             &table_comm,
             &group_by_instructions,
             &range_bag_comm,
         )?;
+        assert_eq!(res_table_comm.col_vals.len(), res_table.col_vals.len());
         verifier_tracker.verify_claims()?;
 
         // check that the ProverTracker and VerifierTracker are in the same state        
@@ -414,4 +1286,40 @@ mod test {
         res.unwrap();
     }
 
+    #[test]
+    fn group_by_avg_test() {
+        let res = test_group_by_avg();
+        res.unwrap();
+    }
+
+    #[test]
+    fn group_by_max_test() {
+        let res = test_group_by_max();
+        res.unwrap();
+    }
+
+    #[test]
+    fn group_by_min_test() {
+        let res = test_group_by_min();
+        res.unwrap();
+    }
+
+    #[test]
+    fn group_by_multi_col_test() {
+        let res = test_group_by_multi_col();
+        res.unwrap();
+    }
+
+    #[test]
+    fn group_by_count_distinct_test() {
+        let res = test_group_by_count_distinct();
+        res.unwrap();
+    }
+
+    #[test]
+    fn group_by_without_advice_test() {
+        let res = test_group_by_without_advice();
+        res.unwrap();
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,231 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{One, Zero};
+use std::collections::HashMap;
+
+use crate::tracker::prelude::*;
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::zksql_poly_iop::group_by::group_by::AggregationType;
+
+// Calculates the advice for an AVG aggregation: the sum of each group and the remainder rem = sum - avg * count
+// both are aligned with the rows of the grouped bag, with zeros where its selector is zero
+pub fn calc_avg_advice<E, PCS>(
+    pre_grouping_bag: &Bag<E, PCS>,
+    pre_agg_poly: &TrackedPoly<E, PCS>,
+    grouped_bag: &Bag<E, PCS>,
+    count_poly: &TrackedPoly<E, PCS>,
+    avg_poly: &TrackedPoly<E, PCS>,
+) -> (DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>) // (sum, rem)
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    // sum up the values of each group
+    let pre_grouping_evals = pre_grouping_bag.poly.evaluations();
+    let pre_grouping_sel_evals = pre_grouping_bag.selector.evaluations();
+    let pre_agg_evals = pre_agg_poly.evaluations();
+    let mut group_sums = HashMap::<E::ScalarField, E::ScalarField>::new();
+    for i in 0..pre_grouping_evals.len() {
+        if pre_grouping_sel_evals[i] == E::ScalarField::zero() {
+            continue;
+        }
+        *group_sums.entry(pre_grouping_evals[i]).or_insert(E::ScalarField::zero()) += pre_agg_evals[i];
+    }
+
+    // line the sums up with the groups and calculate the remainders
+    let grouped_nv = grouped_bag.num_vars();
+    let grouped_evals = grouped_bag.poly.evaluations();
+    let grouped_sel_evals = grouped_bag.selector.evaluations();
+    let count_evals = count_poly.evaluations();
+    let avg_evals = avg_poly.evaluations();
+    let mut sum_evals = Vec::<E::ScalarField>::with_capacity(grouped_evals.len());
+    let mut rem_evals = Vec::<E::ScalarField>::with_capacity(grouped_evals.len());
+    for i in 0..grouped_evals.len() {
+        if grouped_sel_evals[i] == E::ScalarField::zero() {
+            sum_evals.push(E::ScalarField::zero());
+            rem_evals.push(E::ScalarField::zero());
+        } else {
+            let sum = group_sums.get(&grouped_evals[i]).copied().unwrap_or(E::ScalarField::zero());
+            sum_evals.push(sum);
+            rem_evals.push(sum - avg_evals[i] * count_evals[i]);
+        }
+    }
+
+    let sum_mle = DenseMultilinearExtension::from_evaluations_vec(grouped_nv, sum_evals);
+    let rem_mle = DenseMultilinearExtension::from_evaluations_vec(grouped_nv, rem_evals);
+    (sum_mle, rem_mle)
+}
+
+// Calculates the advice for a MIN or MAX aggregation: the selected (group, value) pairs sorted by group,
+// then by value with the extremum of each group last, and a column marking the last row of each group.
+// The selected rows come first, followed by the unselected ones as zeros
+pub fn calc_extremum_advice<E, PCS>(
+    pre_grouping_bag: &Bag<E, PCS>,
+    pre_agg_poly: &TrackedPoly<E, PCS>,
+    is_max: bool,
+) -> [DenseMultilinearExtension<E::ScalarField>; 4] // [sorted_group, sorted_agg, sorted_sel, group_end]
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let nv = pre_grouping_bag.num_vars();
+    let len = 2_usize.pow(nv as u32);
+    let pre_grouping_evals = pre_grouping_bag.poly.evaluations();
+    let pre_grouping_sel_evals = pre_grouping_bag.selector.evaluations();
+    let pre_agg_evals = pre_agg_poly.evaluations();
+
+    let mut pairs = (0..len)
+        .filter(|i| pre_grouping_sel_evals[*i] != E::ScalarField::zero())
+        .map(|i| (pre_grouping_evals[i], pre_agg_evals[i]))
+        .collect::<Vec<_>>();
+    if is_max {
+        pairs.sort();
+    } else {
+        pairs.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+    }
+
+    let mut sorted_group_evals = vec![E::ScalarField::zero(); len];
+    let mut sorted_agg_evals = vec![E::ScalarField::zero(); len];
+    let mut sorted_sel_evals = vec![E::ScalarField::zero(); len];
+    let mut group_end_evals = vec![E::ScalarField::zero(); len];
+    for (i, (group, val)) in pairs.iter().enumerate() {
+        sorted_group_evals[i] = *group;
+        sorted_agg_evals[i] = *val;
+        sorted_sel_evals[i] = E::ScalarField::one();
+        if i + 1 == pairs.len() || pairs[i + 1].0 != *group {
+            group_end_evals[i] = E::ScalarField::one();
+        }
+    }
+
+    [
+        DenseMultilinearExtension::from_evaluations_vec(nv, sorted_group_evals),
+        DenseMultilinearExtension::from_evaluations_vec(nv, sorted_agg_evals),
+        DenseMultilinearExtension::from_evaluations_vec(nv, sorted_sel_evals),
+        DenseMultilinearExtension::from_evaluations_vec(nv, group_end_evals),
+    ]
+}
+
+// Calculates the support advice for grouping a table by several columns: the distinct keys sorted lexicographically
+// and the multiplicity of each key. Unlike calc_bag_supp_advice the keys come first, followed by zeros,
+// since TableSortIOP proves the order with the selected rows first
+// returns (supp_cols, supp_sel, multiplicities)
+pub fn calc_multi_col_supp_advice<E, PCS>(
+    table: &Table<E, PCS>,
+    grouping_cols: &[usize],
+) -> (Vec<DenseMultilinearExtension<E::ScalarField>>, DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>)
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let nv = table.num_vars();
+    let len = 2_usize.pow(nv as u32);
+    let mut group_counts = HashMap::<Vec<E::ScalarField>, u64>::new();
+    for key in selected_keys(table, grouping_cols) {
+        *group_counts.entry(key).or_insert(0) += 1;
+    }
+    let mut keys = group_counts.keys().cloned().collect::<Vec<_>>();
+    keys.sort();
+
+    let mut supp_col_evals = vec![vec![E::ScalarField::zero(); len]; grouping_cols.len()];
+    let mut supp_sel_evals = vec![E::ScalarField::zero(); len];
+    let mut multiplicity_evals = vec![E::ScalarField::zero(); len];
+    for (i, key) in keys.iter().enumerate() {
+        for (j, val) in key.iter().enumerate() {
+            supp_col_evals[j][i] = *val;
+        }
+        supp_sel_evals[i] = E::ScalarField::one();
+        multiplicity_evals[i] = E::ScalarField::from(group_counts[key]);
+    }
+
+    (
+        supp_col_evals.into_iter().map(|evals| DenseMultilinearExtension::from_evaluations_vec(nv, evals)).collect(),
+        DenseMultilinearExtension::from_evaluations_vec(nv, supp_sel_evals),
+        DenseMultilinearExtension::from_evaluations_vec(nv, multiplicity_evals),
+    )
+}
+
+// Calculates the column of a SUM, AVG, MIN, MAX or COUNT(DISTINCT) aggregation, aligned with the rows of the support.
+// AVG is rounded down, so it needs the values to be non-negative integers with sums below 2^128.
+// GroupByIOP range checks the averages, so it can only prove averages smaller than the size of the range bag
+pub fn calc_agg_advice<E, PCS>(
+    table: &Table<E, PCS>,
+    grouping_cols: &[usize],
+    agg_col: usize,
+    agg_type: AggregationType,
+    supp_cols: &[DenseMultilinearExtension<E::ScalarField>],
+    supp_sel: &DenseMultilinearExtension<E::ScalarField>,
+) -> Result<DenseMultilinearExtension<E::ScalarField>, PolyIOPErrors>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    // collect the values of each group
+    let agg_evals = table.col_vals[agg_col].evaluations();
+    let sel_evals = table.selector.evaluations();
+    let mut group_vals = HashMap::<Vec<E::ScalarField>, Vec<E::ScalarField>>::new();
+    let selected_rows = (0..agg_evals.len()).filter(|i| sel_evals[*i] != E::ScalarField::zero());
+    for (key, i) in selected_keys(table, grouping_cols).into_iter().zip(selected_rows) {
+        group_vals.entry(key).or_default().push(agg_evals[i]);
+    }
+
+    let mut res_evals = Vec::<E::ScalarField>::with_capacity(supp_sel.evaluations.len());
+    for i in 0..supp_sel.evaluations.len() {
+        if supp_sel.evaluations[i] == E::ScalarField::zero() {
+            res_evals.push(E::ScalarField::zero());
+            continue;
+        }
+        let key = supp_cols.iter().map(|col| col.evaluations[i]).collect::<Vec<_>>();
+        let vals = group_vals.get(&key).ok_or_else(|| {
+            PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: support row {} is not a group of the table", i))
+        })?;
+        let res = match agg_type {
+            AggregationType::Sum => vals.iter().sum(),
+            AggregationType::Avg => {
+                let sum = vals.iter().sum::<E::ScalarField>();
+                let sum_int = field_to_u128(sum).ok_or_else(|| {
+                    PolyIOPErrors::InvalidParameters(format!("GroupByIOP Error: the sum of support row {} does not fit in 128 bits", i))
+                })?;
+                E::ScalarField::from(sum_int / vals.len() as u128)
+            },
+            AggregationType::Min => *vals.iter().min().unwrap(),
+            AggregationType::Max => *vals.iter().max().unwrap(),
+            AggregationType::Count => E::ScalarField::from(vals.len() as u64),
+            AggregationType::CountDistinct => {
+                let mut distinct_vals = vals.clone();
+                distinct_vals.sort();
+                distinct_vals.dedup();
+                E::ScalarField::from(distinct_vals.len() as u64)
+            },
+        };
+        res_evals.push(res);
+    }
+
+    Ok(DenseMultilinearExtension::from_evaluations_vec(supp_sel.num_vars, res_evals))
+}
+
+// the keys of the selected rows, in row order
+fn selected_keys<E, PCS>(
+    table: &Table<E, PCS>,
+    grouping_cols: &[usize],
+) -> Vec<Vec<E::ScalarField>>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let col_evals = grouping_cols.iter().map(|j| table.col_vals[*j].evaluations()).collect::<Vec<_>>();
+    let sel_evals = table.selector.evaluations();
+    (0..sel_evals.len())
+        .filter(|i| sel_evals[*i] != E::ScalarField::zero())
+        .map(|i| col_evals.iter().map(|evals| evals[i]).collect())
+        .collect()
+}
+
+fn field_to_u128<F: PrimeField>(x: F) -> Option<u128> {
+    let limbs = x.into_bigint();
+    let limbs = limbs.as_ref();
+    if limbs[2..].iter().any(|limb| *limb != 0) {
+        return None;
+    }
+    Some(limbs[0] as u128 | (limbs[1] as u128) << 64)
+}
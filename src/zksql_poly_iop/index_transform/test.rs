@@ -142,17 +142,17 @@ mod test {
 
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let table_in_sel_comm = verifier_tracker.transfer_prover_comm(table_in_sel.id);
+        let table_in_sel_comm = verifier_tracker.transfer_prover_comm(table_in_sel.id)?;
         let mut table_in_cols_comm = Vec::<TrackedComm<E, PCS>>::new();
         for col in table_in_cols {
-            let tracked_col = verifier_tracker.transfer_prover_comm(col.id);
+            let tracked_col = verifier_tracker.transfer_prover_comm(col.id)?;
             table_in_cols_comm.push(tracked_col);
         }
         let table_in_comm = TableComm::new(table_in_cols_comm, table_in_sel_comm, table_in.num_vars());
-        let table_out_sel_comm = verifier_tracker.transfer_prover_comm(table_out_sel.id);
+        let table_out_sel_comm = verifier_tracker.transfer_prover_comm(table_out_sel.id)?;
         let mut table_out_cols_comm = Vec::<TrackedComm<E, PCS>>::new();
         for col in table_out_cols {
-            let tracked_col = verifier_tracker.transfer_prover_comm(col.id);
+            let tracked_col = verifier_tracker.transfer_prover_comm(col.id)?;
             table_out_cols_comm.push(tracked_col);
         }
         let table_out_comm = TableComm::new(table_out_cols_comm, table_out_sel_comm, table_out.num_vars());
@@ -1,4 +1,5 @@
 use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
 use std::marker::PhantomData;
 use ark_std::One;
 use std::ops::Neg;
@@ -9,14 +10,33 @@ use crate::{
     zksql_poly_iop::{
         bag_inclusion::bag_inclusion::BagInclusionIOP, 
         bag_disjoint::bag_disjoint::BagDisjointIOP,
+        join_reduction::utils::calc_join_reduction_mid_inclusion_advice,
         selector_valid::selector_valid::SelectorValidIOP,
     },
 };
 
+#[derive(Clone)]
+pub struct JoinReductionProvingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub l_sel: TrackedPoly<E, PCS>,
+    pub r_sel: TrackedPoly<E, PCS>,
+    pub mid_a_inclusion_m: TrackedPoly<E, PCS>, // multiplicity of each mid_b element in mid_a
+    pub mid_b_inclusion_m: TrackedPoly<E, PCS>, // multiplicity of each mid_a element in mid_b
+}
+
+#[derive(Clone)]
+pub struct JoinReductionVerifyingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub l_sel: TrackedComm<E, PCS>,
+    pub r_sel: TrackedComm<E, PCS>,
+    pub mid_a_inclusion_m: TrackedComm<E, PCS>,
+    pub mid_b_inclusion_m: TrackedComm<E, PCS>,
+}
+
 pub struct JoinReductionIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
 
 impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> JoinReductionIOP<E, PCS> 
 where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the inclusion multiplicities, and proves the reduction with them
+    // returns the advice
     pub fn prove(
         prover_tracker: &mut ProverTrackerRef<E, PCS>,
         bag_a: &Bag<E, PCS>,
@@ -24,7 +44,40 @@ where PCS: PolynomialCommitmentScheme<E> {
         l_sel: &TrackedPoly<E, PCS>,
         r_sel: &TrackedPoly<E, PCS>,
         range_bag: &Bag<E, PCS>, // needed for SetDisjointIOP
+    ) -> Result<JoinReductionProvingAdvice<E, PCS>, PolyIOPErrors> {
+        // calculate and commit to the multiplicities showing mid_a and mid_b have the same support
+        // the selectors are computed from the evaluations, so no polys are tracked before the commitments
+        let to_mle = |poly: &TrackedPoly<E, PCS>| DenseMultilinearExtension::from_evaluations_vec(poly.num_vars(), poly.evaluations());
+        let mid_sel_mle = |sel: &TrackedPoly<E, PCS>, dangling_sel: &TrackedPoly<E, PCS>| {
+            let evals = sel.evaluations().iter().zip(dangling_sel.evaluations().iter()).map(|(s, d)| *s * (E::ScalarField::one() - d)).collect();
+            DenseMultilinearExtension::from_evaluations_vec(sel.num_vars(), evals)
+        };
+        let (mid_a_inclusion_m_mle, mid_b_inclusion_m_mle) = calc_join_reduction_mid_inclusion_advice::<E>(
+            &to_mle(&bag_a.poly),
+            &mid_sel_mle(&bag_a.selector, l_sel),
+            &to_mle(&bag_b.poly),
+            &mid_sel_mle(&bag_b.selector, r_sel),
+        );
+        let advice = JoinReductionProvingAdvice {
+            l_sel: l_sel.clone(),
+            r_sel: r_sel.clone(),
+            mid_a_inclusion_m: prover_tracker.track_and_commit_poly(mid_a_inclusion_m_mle)?,
+            mid_b_inclusion_m: prover_tracker.track_and_commit_poly(mid_b_inclusion_m_mle)?,
+        };
+
+        Self::prove_with_advice(prover_tracker, bag_a, bag_b, &advice, range_bag)?;
+        Ok(advice)
+    }
+
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        bag_a: &Bag<E, PCS>,
+        bag_b: &Bag<E, PCS>,
+        advice: &JoinReductionProvingAdvice<E, PCS>,
+        range_bag: &Bag<E, PCS>, // needed for SetDisjointIOP
     ) -> Result<(), PolyIOPErrors> {
+        let l_sel = &advice.l_sel;
+        let r_sel = &advice.r_sel;
         let ma_sel = &l_sel.mul_scalar(E::ScalarField::one().neg()).add_scalar(E::ScalarField::one());
         let mb_sel = &r_sel.mul_scalar(E::ScalarField::one().neg()).add_scalar(E::ScalarField::one());
         let l_bag = Bag::new(bag_a.poly.clone(), bag_a.selector.mul_poly(l_sel));
@@ -67,20 +120,24 @@ where PCS: PolynomialCommitmentScheme<E> {
         )?;
 
         // prove mid_a and mid_b have the same support
-        BagInclusionIOP::<E, PCS>::prove(
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
             prover_tracker,
             &ma_bag,
             &mb_bag,
+            &advice.mid_b_inclusion_m,
         )?;
-        BagInclusionIOP::<E, PCS>::prove(
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
             prover_tracker,
             &mb_bag,
             &ma_bag,
+            &advice.mid_a_inclusion_m,
         )?;
         
         Ok(())
     }
 
+    // takes the commitments to the inclusion multiplicities from the proof
+    // returns the advice
     pub fn verify(
         verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
         bag_a: &BagComm<E, PCS>,
@@ -88,8 +145,32 @@ where PCS: PolynomialCommitmentScheme<E> {
         l_sel: &TrackedComm<E, PCS>,
         r_sel: &TrackedComm<E, PCS>,
         range_bag: &BagComm<E, PCS>,
-    ) -> Result<(), PolyIOPErrors> {
+    ) -> Result<JoinReductionVerifyingAdvice<E, PCS>, PolyIOPErrors> {
+        // transfer the multiplicities showing mid_a and mid_b have the same support
+        let mid_a_inclusion_m_id = verifier_tracker.get_next_id();
+        let mid_a_inclusion_m = verifier_tracker.transfer_prover_comm(mid_a_inclusion_m_id)?;
+        let mid_b_inclusion_m_id = verifier_tracker.get_next_id();
+        let mid_b_inclusion_m = verifier_tracker.transfer_prover_comm(mid_b_inclusion_m_id)?;
+        let advice = JoinReductionVerifyingAdvice {
+            l_sel: l_sel.clone(),
+            r_sel: r_sel.clone(),
+            mid_a_inclusion_m,
+            mid_b_inclusion_m,
+        };
+
+        Self::verify_with_advice(verifier_tracker, bag_a, bag_b, &advice, range_bag)?;
+        Ok(advice)
+    }
 
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        bag_a: &BagComm<E, PCS>,
+        bag_b: &BagComm<E, PCS>,
+        advice: &JoinReductionVerifyingAdvice<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        let l_sel = &advice.l_sel;
+        let r_sel = &advice.r_sel;
         let ma_sel = &l_sel.mul_scalar(E::ScalarField::one().neg()).add_scalar(E::ScalarField::one());
         let mb_sel = &r_sel.mul_scalar(E::ScalarField::one().neg()).add_scalar(E::ScalarField::one());
         let l_bag = BagComm::new(bag_a.poly.clone(), bag_a.selector.mul_comms(l_sel), bag_a.num_vars());
@@ -132,15 +213,17 @@ where PCS: PolynomialCommitmentScheme<E> {
         )?;
 
         // verify mid_a and mid_b have the same support
-        BagInclusionIOP::<E, PCS>::verify(
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
             verifier_tracker,
             &ma_bag,
             &mb_bag,
+            &advice.mid_b_inclusion_m,
         )?;
-        BagInclusionIOP::<E, PCS>::verify(
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
             verifier_tracker,
             &mb_bag,
             &ma_bag,
+            &advice.mid_a_inclusion_m,
         )?;
 
         Ok(())
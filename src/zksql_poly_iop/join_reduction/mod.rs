@@ -1,3 +1,3 @@
 pub mod join_reduction;
 mod test;
-mod utils;
\ No newline at end of file
+pub mod utils;
\ No newline at end of file
@@ -404,11 +404,11 @@ mod test {
 
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id), verifier_tracker.transfer_prover_comm(bag_a.selector.id), bag_a.num_vars()); 
-        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id), verifier_tracker.transfer_prover_comm(bag_b.selector.id), bag_b.num_vars()); 
-        let l_sel_comm = verifier_tracker.transfer_prover_comm(l_sel.id);
-        let r_sel_comm = verifier_tracker.transfer_prover_comm(r_sel.id);
-        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id), verifier_tracker.transfer_prover_comm(range_bag.selector.id), range_bag.num_vars());
+        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id)?, verifier_tracker.transfer_prover_comm(bag_a.selector.id)?, bag_a.num_vars()); 
+        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id)?, verifier_tracker.transfer_prover_comm(bag_b.selector.id)?, bag_b.num_vars()); 
+        let l_sel_comm = verifier_tracker.transfer_prover_comm(l_sel.id)?;
+        let r_sel_comm = verifier_tracker.transfer_prover_comm(r_sel.id)?;
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_bag.num_vars());
 
         JoinReductionIOP::<E, PCS>::verify(
             verifier_tracker,
@@ -2,6 +2,7 @@ use ark_ec::pairing::Pairing;
 use ark_std::{Zero, One};
 use ark_poly::DenseMultilinearExtension;
 use crate::zksql_poly_iop::util::prelude::mle_multiplicity_count;
+use crate::zksql_poly_iop::bag_inclusion::utils::calc_bag_inclusion_advice_from_mle;
 
 
 pub fn calc_join_reduction_lr_sel_advice<E>(
@@ -59,17 +60,20 @@ where
    (l_sel_mle, r_sel_mle)
 }
 
-// pub fn calc_join_reduction_mid_inclusion_advice<E>(
-//     mid_a_poly:  &DenseMultilinearExtension<E::ScalarField>,
-//     mid_a_sel: &DenseMultilinearExtension<E::ScalarField>,
-//     mid_b_poly: &DenseMultilinearExtension<E::ScalarField>,
-//     mid_b_sel: &DenseMultilinearExtension<E::ScalarField>,
-// ) -> (DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>)
-// where
-//     E: Pairing,
-// {
-//     let mid_a_inclusion_m = calc_bag_inclusion_advice::<E>(mid_b_poly, mid_b_sel, mid_a_poly, mid_a_sel);
-//     let mid_b_inclusion_m = calc_bag_inclusion_advice::<E>(mid_a_poly, mid_a_sel, mid_b_poly, mid_b_sel);
+// Calculates the multiplicities for the two bag inclusions that show mid_a and mid_b have the same support
+// returns (mid_a_inclusion_m, mid_b_inclusion_m), where mid_a_inclusion_m is the multiplicity of each mid_b element in mid_a
+// and mid_b_inclusion_m is the multiplicity of each mid_a element in mid_b
+pub fn calc_join_reduction_mid_inclusion_advice<E>(
+    mid_a_poly:  &DenseMultilinearExtension<E::ScalarField>,
+    mid_a_sel: &DenseMultilinearExtension<E::ScalarField>,
+    mid_b_poly: &DenseMultilinearExtension<E::ScalarField>,
+    mid_b_sel: &DenseMultilinearExtension<E::ScalarField>,
+) -> (DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>)
+where
+    E: Pairing,
+{
+    let mid_a_inclusion_m = calc_bag_inclusion_advice_from_mle::<E>(mid_b_poly, mid_b_sel, mid_a_poly, mid_a_sel);
+    let mid_b_inclusion_m = calc_bag_inclusion_advice_from_mle::<E>(mid_a_poly, mid_a_sel, mid_b_poly, mid_b_sel);
 
-//     (mid_a_inclusion_m, mid_b_inclusion_m)
-// }
\ No newline at end of file
+    (mid_a_inclusion_m, mid_b_inclusion_m)
+}
@@ -13,17 +13,27 @@ mod bag_sort;
 mod bag_sum;
 mod bag_supp;
 
+mod column_compare;
 mod cross_product;
+mod equi_join;
+mod filter;
 mod group_by;
+mod final_join_many_to_many;
 mod final_join_one_to_many;
 mod index_transform;
 mod join_reduction;
+mod outer_join;
+mod semi_join;
+mod table_sort;
+mod top_k;
+mod unique_column;
 
 mod set_disjoint;
 mod set_union;
 mod set_diff;
 mod set_intersect;
 
+mod selector_expr;
 mod selector_valid;
 
 mod util;
\ No newline at end of file
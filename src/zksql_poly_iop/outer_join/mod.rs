@@ -0,0 +1,3 @@
+pub mod outer_join;
+pub mod utils;
+mod test;
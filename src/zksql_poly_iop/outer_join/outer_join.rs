@@ -0,0 +1,238 @@
+// Proves the result table is the LEFT, RIGHT or FULL outer join of table_a and table_b on one column from each,
+// where the selected rows of table_a have distinct keys.
+// 1. EquiJoinIOP partitions the selected rows of each table into dangling rows and middle rows,
+//    and the middle rows are joined by FinalJoinOneToManyIOP into the inner join
+// 2. The dangling rows of the preserved tables are padded with NULLs for the columns of the other table
+// 3. BagMultitoolIOP proves the selected rows of the result are the rows of the inner join and of the padded dangling rows,
+//    where each row is aggregated along with a flag for whether its table_a and table_b columns are valid
+
+use ark_ec::pairing::Pairing;
+use ark_std::{One, Zero};
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        bag_multitool::bag_multitool::BagMultitoolIOP,
+        equi_join::equi_join::EquiJoinIOP,
+        final_join_one_to_many::final_join_one_to_many::FinalJoinOneToManyIOP,
+        index_transform::utils::{prover_sample_rand_powers, table_row_prover_agg, table_row_verifier_agg, verifier_sample_rand_powers},
+        outer_join::utils::calc_outer_join_res_mles,
+        selector_valid::selector_valid::SelectorValidIOP,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OuterJoinType {
+    Left,
+    Right,
+    Full,
+}
+
+impl OuterJoinType {
+    /// Whether the dangling rows of table_a are in the result
+    pub fn keeps_a(&self) -> bool {
+        matches!(self, OuterJoinType::Left | OuterJoinType::Full)
+    }
+
+    /// Whether the dangling rows of table_b are in the result
+    pub fn keeps_b(&self) -> bool {
+        matches!(self, OuterJoinType::Right | OuterJoinType::Full)
+    }
+}
+
+pub struct OuterJoinIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> OuterJoinIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // proves the inner join, then computes and commits to the result table and proves it has the rows of the inner join
+    // and the padded dangling rows. The result has one more variable than the larger input table, so every row fits
+    // returns the result table, where the columns of table_a are followed by the columns of table_b
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>, // primary key table, no duplicates among the selected rows
+        table_b: &Table<E, PCS>, // foreign key table, has duplicates
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        join_type: OuterJoinType,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls())?;
+
+        // prove the inner join of the middle rows
+        let advice = EquiJoinIOP::<E, PCS>::prove_partition(prover_tracker, table_a, table_b, a_join_col_index, b_join_col_index, range_bag)?;
+        let (mid_table_a, mid_table_b) = EquiJoinIOP::<E, PCS>::prover_mid_tables(table_a, table_b, &advice);
+        let inner_table = FinalJoinOneToManyIOP::<E, PCS>::prove(prover_tracker, &mid_table_a, &mid_table_b, a_join_col_index, b_join_col_index, range_bag)?;
+
+        // the dangling rows of each table
+        let dangling_a_table = Table::new(table_a.col_vals.clone(), table_a.selector.mul_poly(&advice.l_sel));
+        let dangling_b_table = Table::new(table_b.col_vals.clone(), table_b.selector.mul_poly(&advice.r_sel));
+
+        // calculate the result table from the rows of the inner join and the padded dangling rows,
+        // with the validity flags of the table_a and table_b columns as the last two columns
+        let (num_a_cols, num_b_cols) = (table_a.col_vals.len(), table_b.col_vals.len());
+        let (zero, one) = (E::ScalarField::zero(), E::ScalarField::one());
+        let constant_evals = |nv: usize, val: E::ScalarField| vec![val; 1 << nv];
+        let mut inner_cols = inner_table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+        inner_cols.extend([constant_evals(table_b.num_vars(), one), constant_evals(table_b.num_vars(), one)]);
+        let mut parts = vec![(inner_cols, inner_table.selector.evaluations())];
+        if join_type.keeps_a() {
+            let mut cols = table_a.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+            cols.extend(vec![constant_evals(table_a.num_vars(), zero); num_b_cols]);
+            cols.extend([constant_evals(table_a.num_vars(), one), constant_evals(table_a.num_vars(), zero)]);
+            parts.push((cols, dangling_a_table.selector.evaluations()));
+        }
+        if join_type.keeps_b() {
+            let mut cols = vec![constant_evals(table_b.num_vars(), zero); num_a_cols];
+            cols.extend(table_b.col_vals.iter().map(|col| col.evaluations()));
+            cols.extend([constant_evals(table_b.num_vars(), zero), constant_evals(table_b.num_vars(), one)]);
+            parts.push((cols, dangling_b_table.selector.evaluations()));
+        }
+        let res_nv = table_a.num_vars().max(table_b.num_vars()) + 1;
+        let (mut res_col_mles, res_sel_mle) = calc_outer_join_res_mles(&parts, res_nv)?;
+        let b_valid_mle = res_col_mles.pop().unwrap();
+        let a_valid_mle = res_col_mles.pop().unwrap();
+
+        // commit to the result table. The table_a columns can only be NULL if the dangling rows of table_b are kept, and vice versa
+        let mut res_col_polys = Vec::<TrackedPoly<E, PCS>>::with_capacity(res_col_mles.len());
+        for col_mle in res_col_mles {
+            res_col_polys.push(prover_tracker.track_and_commit_poly(col_mle)?);
+        }
+        let a_valid = if join_type.keeps_b() { Some(prover_tracker.track_and_commit_poly(a_valid_mle)?) } else { None };
+        let b_valid = if join_type.keeps_a() { Some(prover_tracker.track_and_commit_poly(b_valid_mle)?) } else { None };
+        let res_sel = prover_tracker.track_and_commit_poly(res_sel_mle)?;
+        let mut res_col_validity = vec![a_valid.clone(); num_a_cols];
+        res_col_validity.extend(vec![b_valid.clone(); num_b_cols]);
+        let res_table = Table::new_with_validity(res_col_polys, res_col_validity, res_sel);
+
+        // aggregate the rows of each table along with their validity flags
+        let num_cols = num_a_cols + num_b_cols;
+        let coeffs = prover_sample_rand_powers(prover_tracker, num_cols + 2)?;
+        let (a_flag_coeff, b_flag_coeff) = (coeffs[num_cols], coeffs[num_cols + 1]);
+        let inner_agg = table_row_prover_agg(&inner_table, &coeffs[..num_cols].to_vec())?;
+        let mut part_aggs = vec![Bag::new(inner_agg.poly.add_scalar(a_flag_coeff + b_flag_coeff), inner_agg.selector)];
+        if join_type.keeps_a() {
+            let dangling_a_agg = table_row_prover_agg(&dangling_a_table, &coeffs[..num_a_cols].to_vec())?;
+            part_aggs.push(Bag::new(dangling_a_agg.poly.add_scalar(a_flag_coeff), dangling_a_agg.selector));
+        }
+        if join_type.keeps_b() {
+            let dangling_b_agg = table_row_prover_agg(&dangling_b_table, &coeffs[num_a_cols..num_cols].to_vec())?;
+            part_aggs.push(Bag::new(dangling_b_agg.poly.add_scalar(b_flag_coeff), dangling_b_agg.selector));
+        }
+        let mut res_agg_poly = table_row_prover_agg(&res_table, &coeffs[..num_cols].to_vec())?.poly;
+        res_agg_poly = match &a_valid {
+            Some(valid) => res_agg_poly.add_poly(&valid.mul_scalar(a_flag_coeff)),
+            None => res_agg_poly.add_scalar(a_flag_coeff),
+        };
+        res_agg_poly = match &b_valid {
+            Some(valid) => res_agg_poly.add_poly(&valid.mul_scalar(b_flag_coeff)),
+            None => res_agg_poly.add_scalar(b_flag_coeff),
+        };
+        let res_agg = Bag::new(res_agg_poly, res_table.selector.clone());
+
+        // prove the selected rows of the result are the selected rows of the parts
+        let mut part_ms = Vec::<TrackedPoly<E, PCS>>::with_capacity(part_aggs.len());
+        for part_agg in part_aggs.iter() {
+            part_ms.push(prover_tracker.track_public_poly(PublicPoly::Constant { num_vars: part_agg.num_vars(), value: one }));
+        }
+        let res_m = prover_tracker.track_public_poly(PublicPoly::Constant { num_vars: res_nv, value: one });
+        BagMultitoolIOP::<E, PCS>::prove(prover_tracker, &part_aggs, &[res_agg], &part_ms, &[res_m])?;
+        SelectorValidIOP::<E, PCS>::prove(prover_tracker, &res_table.selector)?;
+
+        Ok(res_table)
+    }
+
+    // takes the commitments to the result table from the proof
+    // returns the result table
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_join_col_index: usize,
+        b_join_col_index: usize,
+        join_type: OuterJoinType,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(table_a.has_nulls(), table_b.has_nulls())?;
+
+        // verify the inner join of the middle rows
+        let advice = EquiJoinIOP::<E, PCS>::verify_partition(verifier_tracker, table_a, table_b, a_join_col_index, b_join_col_index, range_bag)?;
+        let (mid_table_a, mid_table_b) = EquiJoinIOP::<E, PCS>::verifier_mid_tables(table_a, table_b, &advice);
+        let inner_table = FinalJoinOneToManyIOP::<E, PCS>::verify(verifier_tracker, &mid_table_a, &mid_table_b, a_join_col_index, b_join_col_index, range_bag)?;
+
+        // the dangling rows of each table
+        let dangling_a_table = TableComm::new(table_a.col_vals.clone(), table_a.selector.mul_comms(&advice.l_sel), table_a.num_vars());
+        let dangling_b_table = TableComm::new(table_b.col_vals.clone(), table_b.selector.mul_comms(&advice.r_sel), table_b.num_vars());
+
+        // transfer the result table
+        let (num_a_cols, num_b_cols) = (table_a.col_vals.len(), table_b.col_vals.len());
+        let num_cols = num_a_cols + num_b_cols;
+        let res_nv = table_a.num_vars().max(table_b.num_vars()) + 1;
+        let mut res_col_comms = Vec::<TrackedComm<E, PCS>>::with_capacity(num_cols);
+        for _ in 0..num_cols {
+            let next_id = verifier_tracker.get_next_id();
+            res_col_comms.push(verifier_tracker.transfer_prover_comm(next_id)?);
+        }
+        let a_valid = if join_type.keeps_b() {
+            let next_id = verifier_tracker.get_next_id();
+            Some(verifier_tracker.transfer_prover_comm(next_id)?)
+        } else {
+            None
+        };
+        let b_valid = if join_type.keeps_a() {
+            let next_id = verifier_tracker.get_next_id();
+            Some(verifier_tracker.transfer_prover_comm(next_id)?)
+        } else {
+            None
+        };
+        let res_sel_id = verifier_tracker.get_next_id();
+        let res_sel = verifier_tracker.transfer_prover_comm(res_sel_id)?;
+        let mut res_col_validity = vec![a_valid.clone(); num_a_cols];
+        res_col_validity.extend(vec![b_valid.clone(); num_b_cols]);
+        let res_table = TableComm::new_with_validity(res_col_comms, res_col_validity, res_sel, res_nv);
+
+        // aggregate the rows of each table along with their validity flags
+        let one = E::ScalarField::one();
+        let coeffs = verifier_sample_rand_powers(verifier_tracker, num_cols + 2)?;
+        let (a_flag_coeff, b_flag_coeff) = (coeffs[num_cols], coeffs[num_cols + 1]);
+        let inner_agg = table_row_verifier_agg(&inner_table, &coeffs[..num_cols].to_vec())?;
+        let mut part_aggs = vec![BagComm::new(inner_agg.poly.add_scalar(a_flag_coeff + b_flag_coeff), inner_agg.selector, inner_table.num_vars())];
+        if join_type.keeps_a() {
+            let dangling_a_agg = table_row_verifier_agg(&dangling_a_table, &coeffs[..num_a_cols].to_vec())?;
+            part_aggs.push(BagComm::new(dangling_a_agg.poly.add_scalar(a_flag_coeff), dangling_a_agg.selector, dangling_a_table.num_vars()));
+        }
+        if join_type.keeps_b() {
+            let dangling_b_agg = table_row_verifier_agg(&dangling_b_table, &coeffs[num_a_cols..num_cols].to_vec())?;
+            part_aggs.push(BagComm::new(dangling_b_agg.poly.add_scalar(b_flag_coeff), dangling_b_agg.selector, dangling_b_table.num_vars()));
+        }
+        let mut res_agg_poly = table_row_verifier_agg(&res_table, &coeffs[..num_cols].to_vec())?.poly;
+        res_agg_poly = match &a_valid {
+            Some(valid) => res_agg_poly.add_comms(&valid.mul_scalar(a_flag_coeff)),
+            None => res_agg_poly.add_scalar(a_flag_coeff),
+        };
+        res_agg_poly = match &b_valid {
+            Some(valid) => res_agg_poly.add_comms(&valid.mul_scalar(b_flag_coeff)),
+            None => res_agg_poly.add_scalar(b_flag_coeff),
+        };
+        let res_agg = BagComm::new(res_agg_poly, res_table.selector.clone(), res_nv);
+
+        // verify the selected rows of the result are the selected rows of the parts
+        let mut part_ms = Vec::<TrackedComm<E, PCS>>::with_capacity(part_aggs.len());
+        for part_agg in part_aggs.iter() {
+            part_ms.push(verifier_tracker.track_public_poly(PublicPoly::Constant { num_vars: part_agg.num_vars(), value: one }));
+        }
+        let res_m = verifier_tracker.track_public_poly(PublicPoly::Constant { num_vars: res_nv, value: one });
+        BagMultitoolIOP::<E, PCS>::verify(verifier_tracker, &part_aggs, &[res_agg], &part_ms, &[res_m])?;
+        SelectorValidIOP::<E, PCS>::verify(verifier_tracker, &res_table.selector)?;
+
+        Ok(res_table)
+    }
+
+    fn validate_params(a_has_nulls: bool, b_has_nulls: bool) -> Result<(), PolyIOPErrors> {
+        if a_has_nulls || b_has_nulls {
+            return Err(PolyIOPErrors::InvalidParameters("OuterJoinIOP Error: the input tables can not have NULLs".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::outer_join::outer_join::{OuterJoinIOP, OuterJoinType},
+    };
+
+    fn test_outer_join() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // customers (cust_id, region) and orders (order_id, cust_id), joined on cust_id.
+        // customers 3, 4 and 6 have no selected orders and customer 7 is not selected,
+        // so the orders of customers 7 and 9 are dangling along with customers 3, 4 and 6
+        let nv = 3;
+        let to_evals = |nums: &[u64]| nums.iter().map(|x| Fr::from(*x)).collect::<Vec<_>>();
+        let to_mle = |nv: usize, nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, to_evals(nums));
+        let a_cols = vec![to_mle(nv, &[1, 2, 3, 4, 5, 6, 7, 8]), to_mle(nv, &[31, 32, 33, 34, 35, 36, 37, 38])];
+        let a_sel = to_mle(nv, &[1, 1, 1, 1, 1, 1, 0, 0]);
+        let b_cols = vec![to_mle(nv, &[40, 41, 42, 43, 44, 45, 46, 47]), to_mle(nv, &[2, 2, 5, 9, 1, 7, 5, 3])];
+        let b_sel = to_mle(nv, &[1, 1, 1, 1, 1, 1, 1, 0]);
+
+        // the result has the rows of the inner join first, then the dangling customers, then the dangling orders
+        let zeros = [0; 8];
+        let expected_cols = |dangling_a: bool, dangling_b: bool| {
+            let mut cols = [vec![2, 2, 5, 1, 5], vec![32, 32, 35, 31, 35], vec![40, 41, 42, 44, 46], vec![2, 2, 5, 1, 5]];
+            if dangling_a {
+                for (col, vals) in cols.iter_mut().zip([[3, 4, 6], [33, 34, 36], [0, 0, 0], [0, 0, 0]]) {
+                    col.extend(vals);
+                }
+            }
+            if dangling_b {
+                for (col, vals) in cols.iter_mut().zip([[0, 0], [0, 0], [43, 45], [9, 7]]) {
+                    col.extend(vals);
+                }
+            }
+            cols.iter().map(|col| {
+                let mut col = col.clone();
+                col.resize(16, 0);
+                to_evals(&col)
+            }).collect::<Vec<_>>()
+        };
+
+        // Test the good path for each join type
+        let validity_cases = [
+            // only the table_b columns can be NULL
+            (OuterJoinType::Left, true, false, vec![to_evals(&[[1, 1, 1, 1, 1, 0, 0, 0], zeros].concat()); 2], 8),
+            // only the table_a columns can be NULL
+            (OuterJoinType::Right, false, true, vec![to_evals(&[[1, 1, 1, 1, 1, 0, 0, 0], zeros].concat()); 2], 7),
+            (
+                OuterJoinType::Full,
+                true,
+                true,
+                [
+                    vec![to_evals(&[[1, 1, 1, 1, 1, 1, 1, 1], zeros].concat()); 2],
+                    vec![to_evals(&[[1, 1, 1, 1, 1, 0, 0, 0], [1, 1, 0, 0, 0, 0, 0, 0]].concat()); 2],
+                ].concat(),
+                10,
+            ),
+        ];
+        for (join_type, dangling_a, dangling_b, validity, num_rows) in validity_cases {
+            print!("OuterJoinIOP {:?} good path test: ", join_type);
+            let res_evals = test_outer_join_helper(
+                &mut prover_tracker,
+                &mut verifier_tracker,
+                &a_cols,
+                &a_sel,
+                &b_cols,
+                &b_sel,
+                (0, 1),
+                join_type,
+                &range_mle,
+                &range_sel_mle,
+            )?;
+            let mut expected_evals = expected_cols(dangling_a, dangling_b);
+            expected_evals.push(to_evals(&[vec![1; num_rows], vec![0; 16 - num_rows]].concat()));
+            expected_evals.extend(validity);
+            assert_eq!(res_evals, expected_evals);
+            println!("passed");
+        }
+
+        // Test the good path with a smaller table_a and no dangling rows, where no row is NULL
+        print!("OuterJoinIOP full join with no dangling rows test: ");
+        let small_a_cols = vec![to_mle(2, &[1, 2, 3, 4]), to_mle(2, &[31, 32, 33, 34])];
+        let small_b_cols = vec![to_mle(nv, &[40, 41, 42, 43, 44, 45, 0, 0]), to_mle(nv, &[3, 1, 3, 3, 2, 4, 0, 0])];
+        let res_evals = test_outer_join_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &small_a_cols,
+            &to_mle(2, &[1, 1, 1, 1]),
+            &small_b_cols,
+            &to_mle(nv, &[1, 1, 1, 1, 1, 1, 0, 0]),
+            (0, 1),
+            OuterJoinType::Full,
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        assert_eq!(res_evals[1], to_evals(&[[33, 31, 33, 33, 32, 34, 0, 0], zeros].concat()));
+        assert_eq!(res_evals[5..], vec![to_evals(&[[1, 1, 1, 1, 1, 1, 0, 0], zeros].concat()); 4]);
+        println!("passed");
+
+        // Test join columns that are out of bounds
+        for (i, join_col_indices) in [(2, 1), (0, 2)].into_iter().enumerate() {
+            print!("OuterJoinIOP bad join column {} test: ", i + 1);
+            let bad_result = test_outer_join_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &a_cols,
+                &a_sel,
+                &b_cols,
+                &b_sel,
+                join_col_indices,
+                OuterJoinType::Full,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        // Test an input table that can have NULLs, e.g. the result of another outer join
+        print!("OuterJoinIOP nullable input test: ");
+        let mut bad_prover_tracker = prover_tracker.deep_copy();
+        let mut a_col_polys = Vec::new();
+        for col in a_cols.iter() {
+            a_col_polys.push(bad_prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let a_validity = bad_prover_tracker.track_and_commit_poly(to_mle(nv, &[1, 1, 0, 1, 1, 1, 1, 1]))?;
+        let table_a = Table::new_with_validity(a_col_polys, vec![Some(a_validity), None], bad_prover_tracker.track_and_commit_poly(a_sel.clone())?);
+        let mut b_col_polys = Vec::new();
+        for col in b_cols.iter() {
+            b_col_polys.push(bad_prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_b = Table::new(b_col_polys, bad_prover_tracker.track_and_commit_poly(b_sel.clone())?);
+        let range_bag = Bag::new(bad_prover_tracker.track_and_commit_poly(range_mle.clone())?, bad_prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let bad_result = OuterJoinIOP::prove(&mut bad_prover_tracker, &table_a, &table_b, 0, 1, OuterJoinType::Left, &range_bag);
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies OuterJoinIOP
+    // returns the evaluations of the result columns followed by the result selector and the validity of each column that can be NULL
+    fn test_outer_join_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        a_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        a_sel: &DenseMultilinearExtension<E::ScalarField>,
+        b_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        b_sel: &DenseMultilinearExtension<E::ScalarField>,
+        join_col_indices: (usize, usize),
+        join_type: OuterJoinType,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<Vec<E::ScalarField>>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let (a_join_col_index, b_join_col_index) = join_col_indices;
+
+        // set up the prover tracker and prove
+        let mut a_col_polys = Vec::new();
+        for col in a_cols.iter() {
+            a_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_a = Table::new(a_col_polys, prover_tracker.track_and_commit_poly(a_sel.clone())?);
+        let mut b_col_polys = Vec::new();
+        for col in b_cols.iter() {
+            b_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_b = Table::new(b_col_polys, prover_tracker.track_and_commit_poly(b_sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let res_table = OuterJoinIOP::<E, PCS>::prove(prover_tracker, &table_a, &table_b, a_join_col_index, b_join_col_index, join_type, &range_bag)?;
+        let mut res_evals = res_table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+        res_evals.push(res_table.selector.evaluations());
+        res_evals.extend(res_table.col_validity.iter().flatten().map(|validity| validity.evaluations()));
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut a_col_comms = Vec::new();
+        for col_poly in table_a.col_vals.iter() {
+            a_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_a_comm = TableComm::new(a_col_comms, verifier_tracker.transfer_prover_comm(table_a.selector.id)?, a_sel.num_vars);
+        let mut b_col_comms = Vec::new();
+        for col_poly in table_b.col_vals.iter() {
+            b_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_b_comm = TableComm::new(b_col_comms, verifier_tracker.transfer_prover_comm(table_b.selector.id)?, b_sel.num_vars);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let res_table_comm = OuterJoinIOP::<E, PCS>::verify(verifier_tracker, &table_a_comm, &table_b_comm, a_join_col_index, b_join_col_index, join_type, &range_bag_comm)?;
+        verifier_tracker.verify_claims()?;
+        assert_eq!(res_table_comm.selector.id, res_table.selector.id);
+        assert_eq!(res_table_comm.num_vars(), res_table.num_vars());
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_evals)
+    }
+
+    #[test]
+    fn outer_join_test() {
+        let res = test_outer_join();
+        res.unwrap();
+    }
+}
@@ -0,0 +1,37 @@
+use ark_ff::PrimeField;
+use ark_poly::DenseMultilinearExtension;
+
+use crate::tracker::prelude::*;
+
+// Calculates the result table of OuterJoinIOP by stacking the selected rows of each part at the front of the result,
+// with unselected zero rows after them.
+// each part is given as the evaluations of its columns and its selector, where every part has the same number of columns
+// returns (res_cols, res_sel)
+pub fn calc_outer_join_res_mles<F: PrimeField>(
+    parts: &[(Vec<Vec<F>>, Vec<F>)],
+    res_nv: usize,
+) -> Result<(Vec<DenseMultilinearExtension<F>>, DenseMultilinearExtension<F>), PolyIOPErrors> {
+    let num_cols = parts[0].0.len();
+    let res_len = 1 << res_nv;
+    let mut res_cols = vec![Vec::<F>::with_capacity(res_len); num_cols];
+    for (cols, sel) in parts.iter() {
+        for (i, _) in sel.iter().enumerate().filter(|(_, s)| !s.is_zero()) {
+            for (res_col, col) in res_cols.iter_mut().zip(cols.iter()) {
+                res_col.push(col[i]);
+            }
+        }
+    }
+
+    let num_rows = res_cols[0].len();
+    if num_rows > res_len {
+        return Err(PolyIOPErrors::InvalidParameters("OuterJoinIOP Error: the result has more rows than fit in res_nv".to_string()));
+    }
+    let mut res_sel = vec![F::one(); num_rows];
+    res_sel.resize(res_len, F::zero());
+    let res_col_mles = res_cols.into_iter().map(|mut col| {
+        col.resize(res_len, F::zero());
+        DenseMultilinearExtension::from_evaluations_vec(res_nv, col)
+    }).collect();
+
+    Ok((res_col_mles, DenseMultilinearExtension::from_evaluations_vec(res_nv, res_sel)))
+}
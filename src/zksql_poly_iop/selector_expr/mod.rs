@@ -0,0 +1,2 @@
+pub mod selector_expr;
+mod test;
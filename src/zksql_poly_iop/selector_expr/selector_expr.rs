@@ -0,0 +1,174 @@
+// Combines selectors with boolean operators, as in `WHERE a < 5 AND (b = 3 OR NOT c = 7)`.
+// On boolean selectors, AND is a * b, OR is a + b - a * b and NOT is 1 - a.
+// Each product raises the degree of the expression, so before it would go over the max degree
+// the operand of highest degree is committed and a zerocheck shows it equals the expression it replaces.
+// The expressions are written against `IOPTracker`, so the prover and the verifier build them with the same calls
+
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::One;
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::selector_valid::selector_valid::SelectorValidIOP,
+};
+
+// A boolean expression over selectors, with the degree of its poly in the leaf selectors
+#[derive(Clone)]
+pub struct SelectorExpr<P> {
+    poly: P,
+    num_vars: usize,
+    degree: usize,
+}
+
+impl<P: Clone> SelectorExpr<P> {
+    // the selector the expression stands for, e.g. for `Table::new`
+    pub fn into_poly(self) -> P {
+        self.poly
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+}
+
+pub struct SelectorExprIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> SelectorExprIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    pub const DEFAULT_MAX_DEGREE: usize = 4;
+
+    // proves the selector is boolean and starts an expression from it
+    // the degree is the one of the selector in committed polys, e.g. 2 for the selector SemiJoinIOP returns,
+    // since the trackers do not keep the degree of a virtual poly on both sides
+    pub fn leaf<T: IOPTracker<E>>(
+        tracker: &mut T,
+        selector: &T::Poly,
+        num_vars: usize,
+        degree: usize,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors> {
+        if degree == 0 {
+            return Err(PolyIOPErrors::InvalidParameters("SelectorExprIOP Error: a leaf selector has degree at least 1".to_string()));
+        }
+        SelectorValidIOP::<E, PCS>::circuit(tracker, selector)?;
+        Ok(SelectorExpr { poly: selector.clone(), num_vars, degree })
+    }
+
+    pub fn and<T: IOPTracker<E>>(
+        tracker: &mut T,
+        a: &SelectorExpr<T::Poly>,
+        b: &SelectorExpr<T::Poly>,
+        max_degree: usize,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors> {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        Self::reduce_operands(tracker, &mut a, &mut b, max_degree)?;
+        Ok(SelectorExpr {
+            poly: a.poly.mul_poly(&b.poly),
+            num_vars: a.num_vars,
+            degree: a.degree + b.degree,
+        })
+    }
+
+    pub fn or<T: IOPTracker<E>>(
+        tracker: &mut T,
+        a: &SelectorExpr<T::Poly>,
+        b: &SelectorExpr<T::Poly>,
+        max_degree: usize,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors> {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        Self::reduce_operands(tracker, &mut a, &mut b, max_degree)?;
+        Ok(SelectorExpr {
+            poly: a.poly.add_poly(&b.poly).sub_poly(&a.poly.mul_poly(&b.poly)),
+            num_vars: a.num_vars,
+            degree: a.degree + b.degree,
+        })
+    }
+
+    // 1 - a keeps the degree, so it never needs a commitment
+    pub fn not<P: IOPPoly<E::ScalarField>>(a: &SelectorExpr<P>) -> SelectorExpr<P> {
+        let one = E::ScalarField::one();
+        SelectorExpr {
+            poly: a.poly.mul_scalar(-one).add_scalar(one),
+            num_vars: a.num_vars,
+            degree: a.degree,
+        }
+    }
+
+    // ANDs all the expressions, left to right
+    pub fn and_all<T: IOPTracker<E>>(
+        tracker: &mut T,
+        exprs: &[SelectorExpr<T::Poly>],
+        max_degree: usize,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors> {
+        if exprs.is_empty() {
+            return Err(PolyIOPErrors::InvalidParameters("SelectorExprIOP Error: and_all of no expressions".to_string()));
+        }
+        let mut res = exprs[0].clone();
+        for expr in exprs.iter().skip(1) {
+            res = Self::and(tracker, &res, expr, max_degree)?;
+        }
+        Ok(res)
+    }
+
+    // ORs all the expressions, left to right
+    pub fn or_all<T: IOPTracker<E>>(
+        tracker: &mut T,
+        exprs: &[SelectorExpr<T::Poly>],
+        max_degree: usize,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors> {
+        if exprs.is_empty() {
+            return Err(PolyIOPErrors::InvalidParameters("SelectorExprIOP Error: or_all of no expressions".to_string()));
+        }
+        let mut res = exprs[0].clone();
+        for expr in exprs.iter().skip(1) {
+            res = Self::or(tracker, &res, expr, max_degree)?;
+        }
+        Ok(res)
+    }
+
+    // commits to the expression and zerochecks that the commitment equals it, leaving an expression of degree one
+    pub fn materialize<T: IOPTracker<E>>(
+        tracker: &mut T,
+        expr: &SelectorExpr<T::Poly>,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors> {
+        if expr.degree <= 1 {
+            return Ok(expr.clone());
+        }
+        let nv = expr.num_vars;
+        let committed = tracker.commit_witness(|token| {
+            Ok(DenseMultilinearExtension::from_evaluations_vec(nv, expr.poly.witness_evaluations(token)))
+        })?;
+        tracker.add_zerocheck(&committed.sub_poly(&expr.poly));
+        Ok(SelectorExpr { poly: committed, num_vars: nv, degree: 1 })
+    }
+
+    // materializes the operand of highest degree until the product of the two fits under the max degree
+    fn reduce_operands<T: IOPTracker<E>>(
+        tracker: &mut T,
+        a: &mut SelectorExpr<T::Poly>,
+        b: &mut SelectorExpr<T::Poly>,
+        max_degree: usize,
+    ) -> Result<(), PolyIOPErrors> {
+        if max_degree < 2 {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "SelectorExprIOP Error: max degree {} is less than 2, the degree of a product of two selectors", max_degree
+            )));
+        }
+        if a.num_vars != b.num_vars {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "SelectorExprIOP Error: selectors have different number of variables {} and {}", a.num_vars, b.num_vars
+            )));
+        }
+
+        while a.degree + b.degree > max_degree {
+            if a.degree >= b.degree {
+                *a = Self::materialize(tracker, a)?;
+            } else {
+                *b = Self::materialize(tracker, b)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::selector_expr::selector_expr::{SelectorExpr, SelectorExprIOP},
+    };
+
+    fn test_selector_expr() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let nv = 4;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // the four leaf selectors cover every combination of bits
+        let leaf_bits: Vec<Vec<u64>> = (0..4).map(|j| (0..2_u64.pow(nv as u32)).map(|i| (i >> j) & 1).collect()).collect();
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let leaves: Vec<DenseMultilinearExtension<Fr>> = leaf_bits.iter().map(|bits| to_mle(bits)).collect();
+        let col = to_mle(&(0..2_u64.pow(nv as u32)).map(|i| 100 + i).collect::<Vec<u64>>());
+
+        // ((a AND b) OR (NOT c AND d)) AND (b OR c OR NOT d)
+        let expected_sel: Vec<Fr> = (0..2_usize.pow(nv as u32)).map(|i| {
+            let (a, b, c, d) = (leaf_bits[0][i] == 1, leaf_bits[1][i] == 1, leaf_bits[2][i] == 1, leaf_bits[3][i] == 1);
+            let lhs = (a && b) || (!c && d);
+            let rhs = b || c || !d;
+            Fr::from(lhs && rhs)
+        }).collect();
+
+        // Test good paths, with a max degree that forces commitments and with the default one
+        for max_degree in [2, 3, SelectorExprIOP::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::DEFAULT_MAX_DEGREE] {
+            print!("Testing selector expression with max degree {} good path: ", max_degree);
+            let sel_evals = test_selector_expr_helper(&mut prover_tracker, &mut verifier_tracker, &leaves, &col, max_degree)?;
+            assert_eq!(sel_evals, expected_sel);
+            println!("passed");
+        }
+
+        // Test bad path, a leaf selector that is not boolean
+        print!("Testing selector expression with a non-boolean leaf: ");
+        let mut bad_leaves = leaves.clone();
+        bad_leaves[1].evaluations[3] = Fr::from(2_u64);
+        let bad_result = test_selector_expr_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &bad_leaves,
+            &col,
+            2,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad parameters, a max degree that cannot hold a product and selectors of different sizes
+        print!("Testing selector expression with bad parameters: ");
+        let bad_result = test_selector_expr_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &leaves,
+            &col,
+            1,
+        );
+        assert!(bad_result.is_err());
+        let mut bad_tracker = prover_tracker.deep_copy();
+        let small_leaf = DenseMultilinearExtension::from_evaluations_vec(nv - 1, leaves[0].evaluations[..2_usize.pow(nv as u32 - 1)].to_vec());
+        let small_sel = bad_tracker.track_and_commit_poly(small_leaf)?;
+        let sel = bad_tracker.track_and_commit_poly(leaves[0].clone())?;
+        let small_expr = SelectorExprIOP::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::leaf(&mut bad_tracker, &small_sel, nv - 1, 1)?;
+        let expr = SelectorExprIOP::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::leaf(&mut bad_tracker, &sel, nv, 1)?;
+        assert!(SelectorExprIOP::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::and(&mut bad_tracker, &small_expr, &expr, 2).is_err());
+        assert!(SelectorExprIOP::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::or_all(&mut bad_tracker, &[], 2).is_err());
+        assert!(SelectorExprIOP::<Bls12_381, MultilinearKzgPCS<Bls12_381>>::leaf(&mut bad_tracker, &sel, nv, 0).is_err());
+        println!("passed");
+
+        // Test a leaf that is a virtual selector of degree 2, like the one SemiJoinIOP returns,
+        // ANDed with a leaf of degree 1 under max degree 2, which has to commit to the virtual selector first
+        print!("Testing selector expression with a degree 2 leaf: ");
+        let expected_sel: Vec<Fr> = (0..2_usize.pow(nv as u32)).map(|i| Fr::from(leaf_bits[0][i] * leaf_bits[1][i] * leaf_bits[2][i])).collect();
+        let sel_evals = test_selector_expr_degree_two_leaf_helper(&mut prover_tracker, &mut verifier_tracker, &leaves[..3], &col)?;
+        assert_eq!(sel_evals, expected_sel);
+        println!("passed");
+
+        Ok(())
+    }
+
+    // ANDs the leaf a * b, of degree 2, with the leaf c under max degree 2, the same way on both sides
+    fn build_degree_two_leaf_expr<E, PCS, T>(
+        tracker: &mut T,
+        leaves: &[T::Poly],
+        nv: usize,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    T: IOPTracker<E>,
+    {
+        let a_and_b = SelectorExprIOP::<E, PCS>::leaf(tracker, &leaves[0].mul_poly(&leaves[1]), nv, 2)?;
+        let c = SelectorExprIOP::<E, PCS>::leaf(tracker, &leaves[2], nv, 1)?;
+        let res = SelectorExprIOP::<E, PCS>::and(tracker, &a_and_b, &c, 2)?;
+        assert_eq!(res.degree(), 2);
+        Ok(res)
+    }
+
+    // Given the leaf selectors a, b and c, builds (a * b) AND c on both sides and uses it as the selector of a table
+    // returns the evaluations of the table's selector
+    fn test_selector_expr_degree_two_leaf_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        leaves: &[DenseMultilinearExtension<E::ScalarField>],
+        col: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<E::ScalarField>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let nv = col.num_vars;
+
+        // set up the prover tracker and prove
+        let col_poly = prover_tracker.track_and_commit_poly(col.clone())?;
+        let mut leaf_polys = Vec::new();
+        for leaf in leaves.iter() {
+            leaf_polys.push(prover_tracker.track_and_commit_poly(leaf.clone())?);
+        }
+        let num_zerochecks = prover_tracker.clone_underlying_tracker().zero_check_claims.len();
+        let sel = build_degree_two_leaf_expr::<E, PCS, _>(prover_tracker, &leaf_polys, nv)?;
+        // two leaf selector checks and the zerocheck of the committed a * b
+        assert_eq!(prover_tracker.clone_underlying_tracker().zero_check_claims.len(), num_zerochecks + 3);
+        let table = Table::new(vec![col_poly.clone()], sel.into_poly());
+        let sel_evals = table.selector.evaluations();
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let col_comm = verifier_tracker.transfer_prover_comm(col_poly.id)?;
+        let mut leaf_comms = Vec::new();
+        for leaf_poly in leaf_polys.iter() {
+            leaf_comms.push(verifier_tracker.transfer_prover_comm(leaf_poly.id)?);
+        }
+        let sel_comm = build_degree_two_leaf_expr::<E, PCS, _>(verifier_tracker, &leaf_comms, nv)?;
+        let table_comm = TableComm::new(vec![col_comm], sel_comm.into_poly(), nv);
+        verifier_tracker.verify_claims()?;
+        assert_eq!(table_comm.selector.id, table.selector.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(sel_evals)
+    }
+
+    // builds ((a AND b) OR (NOT c AND d)) AND (b OR c OR NOT d), the same way on both sides
+    fn build_expr<E, PCS, T>(
+        tracker: &mut T,
+        leaves: &[T::Poly],
+        nv: usize,
+        max_degree: usize,
+    ) -> Result<SelectorExpr<T::Poly>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    T: IOPTracker<E>,
+    {
+        let mut exprs = Vec::new();
+        for leaf in leaves.iter() {
+            exprs.push(SelectorExprIOP::<E, PCS>::leaf(tracker, leaf, nv, 1)?);
+        }
+        let (a, b, c, d) = (&exprs[0], &exprs[1], &exprs[2], &exprs[3]);
+        let a_and_b = SelectorExprIOP::<E, PCS>::and(tracker, a, b, max_degree)?;
+        let not_c_and_d = SelectorExprIOP::<E, PCS>::and(tracker, &SelectorExprIOP::<E, PCS>::not(c), d, max_degree)?;
+        let lhs = SelectorExprIOP::<E, PCS>::or(tracker, &a_and_b, &not_c_and_d, max_degree)?;
+        let rhs = SelectorExprIOP::<E, PCS>::or_all(tracker, &[b.clone(), c.clone(), SelectorExprIOP::<E, PCS>::not(d)], max_degree)?;
+        let res = SelectorExprIOP::<E, PCS>::and_all(tracker, &[lhs, rhs], max_degree)?;
+        assert!(res.degree() <= max_degree);
+        Ok(res)
+    }
+
+    // Given the leaf selectors, builds the expression on both sides and uses it as the selector of a table
+    // returns the evaluations of the table's selector
+    fn test_selector_expr_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        leaves: &[DenseMultilinearExtension<E::ScalarField>],
+        col: &DenseMultilinearExtension<E::ScalarField>,
+        max_degree: usize,
+    ) -> Result<Vec<E::ScalarField>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let nv = col.num_vars;
+
+        // set up the prover tracker and prove
+        let col_poly = prover_tracker.track_and_commit_poly(col.clone())?;
+        let mut leaf_polys = Vec::new();
+        for leaf in leaves.iter() {
+            leaf_polys.push(prover_tracker.track_and_commit_poly(leaf.clone())?);
+        }
+        let sel = build_expr::<E, PCS, _>(prover_tracker, &leaf_polys, nv, max_degree)?;
+        let table = Table::new(vec![col_poly.clone()], sel.into_poly());
+        let sel_evals = table.selector.evaluations();
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let col_comm = verifier_tracker.transfer_prover_comm(col_poly.id)?;
+        let mut leaf_comms = Vec::new();
+        for leaf_poly in leaf_polys.iter() {
+            leaf_comms.push(verifier_tracker.transfer_prover_comm(leaf_poly.id)?);
+        }
+        let sel_comm = build_expr::<E, PCS, _>(verifier_tracker, &leaf_comms, nv, max_degree)?;
+        let table_comm = TableComm::new(vec![col_comm], sel_comm.into_poly(), nv);
+        verifier_tracker.verify_claims()?;
+        assert_eq!(table_comm.selector.id, table.selector.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(sel_evals)
+    }
+
+    #[test]
+    fn selector_expr_test() {
+        let res = test_selector_expr();
+        res.unwrap();
+    }
+}
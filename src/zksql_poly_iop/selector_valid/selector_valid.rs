@@ -16,23 +16,24 @@ where PCS: PolynomialCommitmentScheme<E>
         prover_tracker: &mut ProverTrackerRef<E, PCS>,
         selector: &TrackedPoly<E, PCS>,
     ) -> Result<(),PolyIOPErrors> {
-
-        // set up the tracker and add a zerocheck claim
-        let one_minus_sel = selector.mul_scalar(E::ScalarField::one().neg()).add_scalar(E::ScalarField::one());
-        let check_poly = selector.mul_poly(&one_minus_sel);
-        
-        prover_tracker.add_zerocheck_claim(check_poly.id);
-
-        Ok(())
+        Self::circuit(prover_tracker, selector)
     }
 
     pub fn verify(
         verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
         selector: &TrackedComm<E, PCS>,
     ) -> Result<(), PolyIOPErrors> {
+        Self::circuit(verifier_tracker, selector)
+    }
+
+    pub fn circuit<T: IOPTracker<E>>(
+        tracker: &mut T,
+        selector: &T::Poly,
+    ) -> Result<(), PolyIOPErrors> {
+        // zerocheck sel * (1 - sel)
         let one_minus_sel = selector.mul_scalar(E::ScalarField::one().neg()).add_scalar(E::ScalarField::one());
-        let check_poly = selector.mul_comms(&one_minus_sel);
-        verifier_tracker.add_zerocheck_claim(check_poly.id);
+        let check_poly = selector.mul_poly(&one_minus_sel);
+        tracker.add_zerocheck(&check_poly);
 
         Ok(())
     }
@@ -0,0 +1,3 @@
+pub mod semi_join;
+pub mod utils;
+mod test;
@@ -0,0 +1,320 @@
+// Proves which rows of table_a have a key, given by one or more columns, that is also the key of a selected row of table_b,
+// as in `WHERE (a.k1, a.k2) IN (SELECT k1, k2 FROM b)` or `WHERE EXISTS` for SemiJoinIOP, and `NOT IN` or `NOT EXISTS` for AntiJoinIOP.
+// The keys of each row are aggregated with random coefficients, and the split of the selected rows of table_a is committed to
+// 1. the semi-join selector is boolean, so each selected row of table_a is either semi-joined or anti-joined
+// 2. the keys of the semi-joined rows are included in the keys of table_b
+// 3. the keys of the anti-joined rows are disjoint from the keys of table_b, as in BagDisjointIOP,
+//    except the distinct keys are a table shown to be strictly sorted with TableSortIOP, so keys of several columns work
+
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::One;
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        bag_inclusion::bag_inclusion::BagInclusionIOP,
+        bag_sort::bag_sort::SortOrder,
+        index_transform::utils::{prover_sample_rand_powers, table_row_prover_agg, table_row_verifier_agg, verifier_sample_rand_powers},
+        selector_valid::selector_valid::SelectorValidIOP,
+        semi_join::utils::{calc_semi_join_disjoint_advice, calc_semi_join_inclusion_advice, calc_semi_join_sel_advice},
+        table_sort::table_sort::TableSortIOP,
+    },
+};
+
+#[derive(Clone)]
+pub struct SemiJoinProvingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub semi_sel: TrackedPoly<E, PCS>, // one on the rows of table_a whose key is in table_b
+    pub b_inclusion_m: TrackedPoly<E, PCS>, // multiplicity of each table_b key in the semi-joined rows of table_a
+    pub key_table: Table<E, PCS>, // the distinct keys of the anti-joined rows of table_a and of table_b
+    pub anti_m: TrackedPoly<E, PCS>, // multiplicity of each key in the anti-joined rows of table_a
+    pub b_disjoint_m: TrackedPoly<E, PCS>, // multiplicity of each key in table_b
+}
+
+#[derive(Clone)]
+pub struct SemiJoinVerifyingAdvice<E: Pairing, PCS: PolynomialCommitmentScheme<E>> {
+    pub semi_sel: TrackedComm<E, PCS>,
+    pub b_inclusion_m: TrackedComm<E, PCS>,
+    pub key_table: TableComm<E, PCS>,
+    pub anti_m: TrackedComm<E, PCS>,
+    pub b_disjoint_m: TrackedComm<E, PCS>,
+}
+
+pub struct SemiJoinIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> SemiJoinIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the advice, and proves the semi-join
+    // returns the selector of the rows of table_a whose key is in table_b
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        range_bag: &Bag<E, PCS>, // needed for TableSortIOP
+    ) -> Result<TrackedPoly<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(&table_a.col_validity, &table_b.col_validity, a_key_col_indices, b_key_col_indices)?;
+
+        // calculate the advice from the evaluations, so no polys are tracked before the commitments
+        let to_mle = |poly: &TrackedPoly<E, PCS>| DenseMultilinearExtension::from_evaluations_vec(poly.num_vars(), poly.evaluations());
+        let a_key_cols = a_key_col_indices.iter().map(|i| to_mle(&table_a.col_vals[*i])).collect::<Vec<_>>();
+        let b_key_cols = b_key_col_indices.iter().map(|i| to_mle(&table_b.col_vals[*i])).collect::<Vec<_>>();
+        let (a_sel, b_sel) = (to_mle(&table_a.selector), to_mle(&table_b.selector));
+        let semi_sel_mle = calc_semi_join_sel_advice::<E>(&a_key_cols, &b_key_cols, &b_sel);
+        let (semi_a_sel_evals, anti_a_sel_evals) = a_sel.evaluations.iter().zip(semi_sel_mle.evaluations.iter()).map(|(sel, semi)| {
+            (*sel * semi, *sel * (E::ScalarField::one() - semi))
+        }).unzip();
+        let semi_a_sel = DenseMultilinearExtension::from_evaluations_vec(a_sel.num_vars, semi_a_sel_evals);
+        let anti_a_sel = DenseMultilinearExtension::from_evaluations_vec(a_sel.num_vars, anti_a_sel_evals);
+        let b_inclusion_m_mle = calc_semi_join_inclusion_advice::<E>(&a_key_cols, &semi_a_sel, &b_key_cols, &b_sel);
+        let (key_col_mles, key_sel_mle, anti_m_mle, b_disjoint_m_mle) = calc_semi_join_disjoint_advice::<E>(&a_key_cols, &anti_a_sel, &b_key_cols, &b_sel);
+
+        // commit to the advice
+        let semi_sel = prover_tracker.track_and_commit_poly(semi_sel_mle)?;
+        let b_inclusion_m = prover_tracker.track_and_commit_poly(b_inclusion_m_mle)?;
+        let mut key_cols = Vec::<TrackedPoly<E, PCS>>::with_capacity(key_col_mles.len());
+        for mle in key_col_mles {
+            key_cols.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        let key_table = Table::new(key_cols, prover_tracker.track_and_commit_poly(key_sel_mle)?);
+        let advice = SemiJoinProvingAdvice {
+            semi_sel,
+            b_inclusion_m,
+            key_table,
+            anti_m: prover_tracker.track_and_commit_poly(anti_m_mle)?,
+            b_disjoint_m: prover_tracker.track_and_commit_poly(b_disjoint_m_mle)?,
+        };
+
+        Self::prove_with_advice(prover_tracker, table_a, table_b, a_key_col_indices, b_key_col_indices, &advice, range_bag)
+    }
+
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        advice: &SemiJoinProvingAdvice<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<TrackedPoly<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(&table_a.col_validity, &table_b.col_validity, a_key_col_indices, b_key_col_indices)?;
+        let num_keys = a_key_col_indices.len();
+
+        // 1. split the selected rows of table_a with the boolean semi-join selector
+        SelectorValidIOP::<E, PCS>::prove(prover_tracker, &advice.semi_sel)?;
+        let semi_a_sel = table_a.selector.mul_poly(&advice.semi_sel);
+        let anti_a_sel = table_a.selector.sub_poly(&semi_a_sel);
+
+        // aggregate the keys of each row
+        let rand_coeffs = prover_sample_rand_powers(prover_tracker, num_keys)?;
+        let key_agg = |table: &Table<E, PCS>, key_col_indices: &[usize]| {
+            let key_cols = key_col_indices.iter().map(|i| table.col_vals[*i].clone()).collect();
+            table_row_prover_agg(&Table::new(key_cols, table.selector.clone()), &rand_coeffs)
+        };
+        let a_key_agg = key_agg(table_a, a_key_col_indices)?.poly;
+        let b_key_bag = key_agg(table_b, b_key_col_indices)?;
+        let key_table_bag = table_row_prover_agg(&advice.key_table, &rand_coeffs)?;
+
+        // 2. the keys of the semi-joined rows are in table_b
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &Bag::new(a_key_agg.clone(), semi_a_sel.clone()),
+            &b_key_bag,
+            &advice.b_inclusion_m,
+        )?;
+
+        // 3. the keys of the anti-joined rows and of table_b are in the table of distinct keys at disjoint rows
+        let keys = (0..num_keys).map(|j| (j, SortOrder::Asc)).collect::<Vec<_>>();
+        TableSortIOP::<E, PCS>::prove_sorted(prover_tracker, &advice.key_table, &keys, range_bag, true)?;
+        let m_mul = advice.anti_m.mul_poly(&advice.b_disjoint_m);
+        prover_tracker.add_zerocheck_claim(m_mul.id);
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &Bag::new(a_key_agg, anti_a_sel),
+            &key_table_bag,
+            &advice.anti_m,
+        )?;
+        BagInclusionIOP::<E, PCS>::prove_with_advice(
+            prover_tracker,
+            &b_key_bag,
+            &key_table_bag,
+            &advice.b_disjoint_m,
+        )?;
+
+        Ok(semi_a_sel)
+    }
+
+    // takes the commitments to the advice from the proof
+    // returns the selector of the rows of table_a whose key is in table_b
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(&table_a.col_validity, &table_b.col_validity, a_key_col_indices, b_key_col_indices)?;
+
+        // transfer the advice
+        let mut next_comm = || {
+            let next_id = verifier_tracker.get_next_id();
+            verifier_tracker.transfer_prover_comm(next_id)
+        };
+        let semi_sel = next_comm()?;
+        let b_inclusion_m = next_comm()?;
+        let mut key_cols = Vec::<TrackedComm<E, PCS>>::with_capacity(a_key_col_indices.len());
+        for _ in 0..a_key_col_indices.len() {
+            key_cols.push(next_comm()?);
+        }
+        let key_nv = table_a.num_vars().max(table_b.num_vars()) + 1;
+        let key_table = TableComm::new(key_cols, next_comm()?, key_nv);
+        let advice = SemiJoinVerifyingAdvice {
+            semi_sel,
+            b_inclusion_m,
+            key_table,
+            anti_m: next_comm()?,
+            b_disjoint_m: next_comm()?,
+        };
+
+        Self::verify_with_advice(verifier_tracker, table_a, table_b, a_key_col_indices, b_key_col_indices, &advice, range_bag)
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        advice: &SemiJoinVerifyingAdvice<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_params(&table_a.col_validity, &table_b.col_validity, a_key_col_indices, b_key_col_indices)?;
+        let num_keys = a_key_col_indices.len();
+
+        // 1. split the selected rows of table_a with the boolean semi-join selector
+        SelectorValidIOP::<E, PCS>::verify(verifier_tracker, &advice.semi_sel)?;
+        let semi_a_sel = table_a.selector.mul_comms(&advice.semi_sel);
+        let anti_a_sel = table_a.selector.sub_comms(&semi_a_sel);
+
+        // aggregate the keys of each row
+        let rand_coeffs = verifier_sample_rand_powers(verifier_tracker, num_keys)?;
+        let key_agg = |table: &TableComm<E, PCS>, key_col_indices: &[usize]| {
+            let key_cols = key_col_indices.iter().map(|i| table.col_vals[*i].clone()).collect();
+            table_row_verifier_agg(&TableComm::new(key_cols, table.selector.clone(), table.num_vars()), &rand_coeffs)
+        };
+        let a_key_agg = key_agg(table_a, a_key_col_indices)?.poly;
+        let b_key_bag = key_agg(table_b, b_key_col_indices)?;
+        let key_table_bag = table_row_verifier_agg(&advice.key_table, &rand_coeffs)?;
+
+        // 2. the keys of the semi-joined rows are in table_b
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &BagComm::new(a_key_agg.clone(), semi_a_sel.clone(), table_a.num_vars()),
+            &b_key_bag,
+            &advice.b_inclusion_m,
+        )?;
+
+        // 3. the keys of the anti-joined rows and of table_b are in the table of distinct keys at disjoint rows
+        let keys = (0..num_keys).map(|j| (j, SortOrder::Asc)).collect::<Vec<_>>();
+        TableSortIOP::<E, PCS>::verify_sorted(verifier_tracker, &advice.key_table, &keys, range_bag, true)?;
+        let m_mul = advice.anti_m.mul_comms(&advice.b_disjoint_m);
+        verifier_tracker.add_zerocheck_claim(m_mul.id);
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &BagComm::new(a_key_agg, anti_a_sel, table_a.num_vars()),
+            &key_table_bag,
+            &advice.anti_m,
+        )?;
+        BagInclusionIOP::<E, PCS>::verify_with_advice(
+            verifier_tracker,
+            &b_key_bag,
+            &key_table_bag,
+            &advice.b_disjoint_m,
+        )?;
+
+        Ok(semi_a_sel)
+    }
+
+    // the key columns have to be in bounds, the same number for both tables, and can not be NULL
+    fn validate_params<T>(
+        a_col_validity: &[Option<T>],
+        b_col_validity: &[Option<T>],
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+    ) -> Result<(), PolyIOPErrors> {
+        if a_key_col_indices.is_empty() || a_key_col_indices.len() != b_key_col_indices.len() {
+            return Err(PolyIOPErrors::InvalidParameters("SemiJoinIOP Error: table_a and table_b need the same nonzero number of key columns".to_string()));
+        }
+        for (col_validity, key_col_indices) in [(a_col_validity, a_key_col_indices), (b_col_validity, b_key_col_indices)] {
+            for i in key_col_indices.iter() {
+                match col_validity.get(*i) {
+                    None => return Err(PolyIOPErrors::InvalidParameters("SemiJoinIOP Error: key column index out of bounds".to_string())),
+                    Some(Some(_)) => return Err(PolyIOPErrors::InvalidParameters("SemiJoinIOP Error: key columns can not be NULL".to_string())),
+                    Some(None) => (),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct AntiJoinIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> AntiJoinIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // proves the semi-join, whose selector splits the selected rows of table_a
+    // returns the selector of the rows of table_a whose key is not in table_b
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<TrackedPoly<E, PCS>, PolyIOPErrors> {
+        let semi_a_sel = SemiJoinIOP::<E, PCS>::prove(prover_tracker, table_a, table_b, a_key_col_indices, b_key_col_indices, range_bag)?;
+        Ok(table_a.selector.sub_poly(&semi_a_sel))
+    }
+
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        table_a: &Table<E, PCS>,
+        table_b: &Table<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        advice: &SemiJoinProvingAdvice<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<TrackedPoly<E, PCS>, PolyIOPErrors> {
+        let semi_a_sel = SemiJoinIOP::<E, PCS>::prove_with_advice(prover_tracker, table_a, table_b, a_key_col_indices, b_key_col_indices, advice, range_bag)?;
+        Ok(table_a.selector.sub_poly(&semi_a_sel))
+    }
+
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
+        let semi_a_sel = SemiJoinIOP::<E, PCS>::verify(verifier_tracker, table_a, table_b, a_key_col_indices, b_key_col_indices, range_bag)?;
+        Ok(table_a.selector.sub_comms(&semi_a_sel))
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        table_a: &TableComm<E, PCS>,
+        table_b: &TableComm<E, PCS>,
+        a_key_col_indices: &[usize],
+        b_key_col_indices: &[usize],
+        advice: &SemiJoinVerifyingAdvice<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TrackedComm<E, PCS>, PolyIOPErrors> {
+        let semi_a_sel = SemiJoinIOP::<E, PCS>::verify_with_advice(verifier_tracker, table_a, table_b, a_key_col_indices, b_key_col_indices, advice, range_bag)?;
+        Ok(table_a.selector.sub_comms(&semi_a_sel))
+    }
+}
@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::semi_join::{
+            semi_join::{AntiJoinIOP, SemiJoinIOP, SemiJoinProvingAdvice, SemiJoinVerifyingAdvice},
+            utils::{calc_semi_join_disjoint_advice, calc_semi_join_inclusion_advice},
+        },
+    };
+
+    fn test_semi_join() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // table_a is (k1, k2, val) and table_b is (k2, k1), where the row (4, 10) of table_a and the row (5, 10) of table_b are not selected,
+        // and the key (1, 10) is in table_b twice
+        let nv = 3;
+        let to_mle = |nv: usize, nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let a_cols = [to_mle(nv, &[1, 1, 2, 2, 3, 3, 4, 5]), to_mle(nv, &[10, 20, 10, 20, 10, 20, 10, 10]), to_mle(nv, &[100, 101, 102, 103, 104, 105, 106, 107])];
+        let a_sel = to_mle(nv, &[1, 1, 1, 1, 1, 1, 0, 1]);
+        let b_cols = [to_mle(nv, &[10, 20, 10, 10, 10, 9, 0, 0]), to_mle(nv, &[1, 2, 3, 5, 1, 9, 0, 0])];
+        let b_sel = to_mle(nv, &[1, 1, 1, 0, 1, 1, 0, 0]);
+        let small_b_cols = [to_mle(1, &[20, 10]), to_mle(1, &[2, 4])];
+        let small_b_sel = to_mle(1, &[1, 1]);
+
+        // Test the good paths, with each output being the semi-join or anti-join selector
+        let good_cases = [
+            ("two column key", &b_cols, &b_sel, [0, 1].as_slice(), [1, 0].as_slice(), [1, 0, 0, 1, 1, 0, 0, 0]),
+            ("one column key", &b_cols, &b_sel, [0].as_slice(), [1].as_slice(), [1, 1, 1, 1, 1, 1, 0, 0]),
+            ("smaller table_b", &small_b_cols, &small_b_sel, [0, 1].as_slice(), [1, 0].as_slice(), [0, 0, 0, 1, 0, 0, 0, 0]),
+        ];
+        for (name, b_cols, b_sel, a_key_cols, b_key_cols, semi_evals) in good_cases {
+            let anti_evals = a_sel.evaluations.iter().zip(semi_evals.iter()).map(|(sel, semi)| *sel - Fr::from(*semi)).collect::<Vec<_>>();
+            for (anti, expected_evals) in [(false, to_mle(nv, &semi_evals).evaluations), (true, anti_evals)] {
+                print!("{} {} good path test: ", if anti { "AntiJoinIOP" } else { "SemiJoinIOP" }, name);
+                let res_evals = test_semi_join_helper(
+                    &mut prover_tracker,
+                    &mut verifier_tracker,
+                    &a_cols,
+                    &a_sel,
+                    b_cols,
+                    b_sel,
+                    (a_key_cols, b_key_cols),
+                    anti,
+                    None,
+                    &range_mle,
+                    &range_sel_mle,
+                )?;
+                assert_eq!(res_evals, expected_evals);
+                println!("passed");
+            }
+        }
+
+        // Test the good path with the given semi-join selector, which is arbitrary on the unselected rows
+        for (anti, expected_nums) in [(false, [1, 0, 0, 1, 1, 0, 0, 0]), (true, [0, 1, 1, 0, 0, 1, 0, 1])] {
+            print!("{} with advice good path test: ", if anti { "AntiJoinIOP" } else { "SemiJoinIOP" });
+            let res_evals = test_semi_join_helper(
+                &mut prover_tracker,
+                &mut verifier_tracker,
+                &a_cols,
+                &a_sel,
+                &b_cols,
+                &b_sel,
+                (&[0, 1], &[1, 0]),
+                anti,
+                Some(&to_mle(nv, &[1, 0, 0, 1, 1, 0, 1, 0])),
+                &range_mle,
+                &range_sel_mle,
+            )?;
+            assert_eq!(res_evals, to_mle(nv, &expected_nums).evaluations);
+            println!("passed");
+        }
+
+        // Test bad paths with the given semi-join selector
+        let bad_semi_sels = [
+            // (1, 20) is marked, but is only in table_b as (1, 10) and (2, 20)
+            to_mle(nv, &[1, 1, 0, 1, 1, 0, 0, 0]),
+            // (1, 10) is not marked
+            to_mle(nv, &[0, 0, 0, 1, 1, 0, 0, 0]),
+            // (5, 10) is marked, but is not selected in table_b
+            to_mle(nv, &[1, 0, 0, 1, 1, 0, 0, 1]),
+            // the selector is not boolean
+            to_mle(nv, &[2, 0, 0, 1, 1, 0, 0, 0]),
+        ];
+        for (i, bad_semi_sel) in bad_semi_sels.iter().enumerate() {
+            for anti in [false, true] {
+                print!("{} bad path {} test: ", if anti { "AntiJoinIOP" } else { "SemiJoinIOP" }, i + 1);
+                let bad_result = test_semi_join_helper(
+                    &mut prover_tracker.deep_copy(),
+                    &mut verifier_tracker.deep_copy(),
+                    &a_cols,
+                    &a_sel,
+                    &b_cols,
+                    &b_sel,
+                    (&[0, 1], &[1, 0]),
+                    anti,
+                    Some(bad_semi_sel),
+                    &range_mle,
+                    &range_sel_mle,
+                );
+                assert!(bad_result.is_err());
+                println!("passed");
+            }
+        }
+
+        // Test key columns that are out of bounds or do not match
+        let bad_key_cols = [([0, 1].as_slice(), [1, 2].as_slice()), (&[3], &[1]), (&[0, 1], &[1]), (&[], &[])];
+        for (i, key_col_indices) in bad_key_cols.into_iter().enumerate() {
+            print!("SemiJoinIOP bad key columns {} test: ", i + 1);
+            let bad_result = test_semi_join_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &a_cols,
+                &a_sel,
+                &b_cols,
+                &b_sel,
+                key_col_indices,
+                false,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        // Test a key column that can be NULL
+        print!("SemiJoinIOP nullable key column test: ");
+        let mut bad_prover_tracker = prover_tracker.deep_copy();
+        let mut a_col_polys = Vec::new();
+        for col in a_cols.iter() {
+            a_col_polys.push(bad_prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let a_validity = bad_prover_tracker.track_and_commit_poly(to_mle(nv, &[1, 1, 1, 1, 1, 1, 1, 0]))?;
+        let table_a = Table::new_with_validity(a_col_polys, vec![None, Some(a_validity), None], bad_prover_tracker.track_and_commit_poly(a_sel.clone())?);
+        let mut b_col_polys = Vec::new();
+        for col in b_cols.iter() {
+            b_col_polys.push(bad_prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_b = Table::new(b_col_polys, bad_prover_tracker.track_and_commit_poly(b_sel.clone())?);
+        let range_bag = Bag::new(bad_prover_tracker.track_and_commit_poly(range_mle.clone())?, bad_prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let bad_result = SemiJoinIOP::prove(&mut bad_prover_tracker, &table_a, &table_b, &[0, 1], &[1, 0], &range_bag);
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies SemiJoinIOP or AntiJoinIOP, with the computed semi-join selector or the given one
+    // returns the evaluations of the output selector
+    fn test_semi_join_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        a_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        a_sel: &DenseMultilinearExtension<E::ScalarField>,
+        b_cols: &[DenseMultilinearExtension<E::ScalarField>],
+        b_sel: &DenseMultilinearExtension<E::ScalarField>,
+        key_col_indices: (&[usize], &[usize]),
+        anti: bool,
+        semi_sel: Option<&DenseMultilinearExtension<E::ScalarField>>,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<E::ScalarField>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let (a_key_col_indices, b_key_col_indices) = key_col_indices;
+
+        // set up the prover tracker and prove
+        let mut a_col_polys = Vec::new();
+        for col in a_cols.iter() {
+            a_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_a = Table::new(a_col_polys, prover_tracker.track_and_commit_poly(a_sel.clone())?);
+        let mut b_col_polys = Vec::new();
+        for col in b_cols.iter() {
+            b_col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table_b = Table::new(b_col_polys, prover_tracker.track_and_commit_poly(b_sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let mut given_advice = None;
+        let res_sel = match semi_sel {
+            None if anti => AntiJoinIOP::<E, PCS>::prove(prover_tracker, &table_a, &table_b, a_key_col_indices, b_key_col_indices, &range_bag)?,
+            None => SemiJoinIOP::<E, PCS>::prove(prover_tracker, &table_a, &table_b, a_key_col_indices, b_key_col_indices, &range_bag)?,
+            Some(semi_sel_mle) => {
+                // calculate the rest of the advice for the given semi-join selector, committed in the same order as SemiJoinIOP::prove
+                let a_key_cols = a_key_col_indices.iter().map(|i| a_cols[*i].clone()).collect::<Vec<_>>();
+                let b_key_cols = b_key_col_indices.iter().map(|i| b_cols[*i].clone()).collect::<Vec<_>>();
+                let split_sel = |anti: bool| {
+                    let evals = a_sel.evaluations.iter().zip(semi_sel_mle.evaluations.iter()).map(|(sel, semi)| {
+                        if anti { *sel * (E::ScalarField::one() - semi) } else { *sel * semi }
+                    }).collect();
+                    DenseMultilinearExtension::from_evaluations_vec(a_sel.num_vars, evals)
+                };
+                let b_inclusion_m_mle = calc_semi_join_inclusion_advice::<E>(&a_key_cols, &split_sel(false), &b_key_cols, b_sel);
+                let (key_col_mles, key_sel_mle, anti_m_mle, b_disjoint_m_mle) = calc_semi_join_disjoint_advice::<E>(&a_key_cols, &split_sel(true), &b_key_cols, b_sel);
+                let semi_sel = prover_tracker.track_and_commit_poly(semi_sel_mle.clone())?;
+                let b_inclusion_m = prover_tracker.track_and_commit_poly(b_inclusion_m_mle)?;
+                let mut key_cols = Vec::new();
+                for mle in key_col_mles {
+                    key_cols.push(prover_tracker.track_and_commit_poly(mle)?);
+                }
+                let key_table = Table::new(key_cols, prover_tracker.track_and_commit_poly(key_sel_mle)?);
+                let advice = SemiJoinProvingAdvice {
+                    semi_sel,
+                    b_inclusion_m,
+                    key_table,
+                    anti_m: prover_tracker.track_and_commit_poly(anti_m_mle)?,
+                    b_disjoint_m: prover_tracker.track_and_commit_poly(b_disjoint_m_mle)?,
+                };
+                let res_sel = if anti {
+                    AntiJoinIOP::<E, PCS>::prove_with_advice(prover_tracker, &table_a, &table_b, a_key_col_indices, b_key_col_indices, &advice, &range_bag)?
+                } else {
+                    SemiJoinIOP::<E, PCS>::prove_with_advice(prover_tracker, &table_a, &table_b, a_key_col_indices, b_key_col_indices, &advice, &range_bag)?
+                };
+                given_advice = Some(advice);
+                res_sel
+            },
+        };
+        let res_evals = res_sel.evaluations();
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut a_col_comms = Vec::new();
+        for col_poly in table_a.col_vals.iter() {
+            a_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_a_comm = TableComm::new(a_col_comms, verifier_tracker.transfer_prover_comm(table_a.selector.id)?, a_sel.num_vars);
+        let mut b_col_comms = Vec::new();
+        for col_poly in table_b.col_vals.iter() {
+            b_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_b_comm = TableComm::new(b_col_comms, verifier_tracker.transfer_prover_comm(table_b.selector.id)?, b_sel.num_vars);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let res_sel_comm = match given_advice {
+            None if anti => AntiJoinIOP::<E, PCS>::verify(verifier_tracker, &table_a_comm, &table_b_comm, a_key_col_indices, b_key_col_indices, &range_bag_comm)?,
+            None => SemiJoinIOP::<E, PCS>::verify(verifier_tracker, &table_a_comm, &table_b_comm, a_key_col_indices, b_key_col_indices, &range_bag_comm)?,
+            Some(advice) => {
+                // transfer the advice in the order it was committed
+                let semi_sel = verifier_tracker.transfer_prover_comm(advice.semi_sel.id)?;
+                let b_inclusion_m = verifier_tracker.transfer_prover_comm(advice.b_inclusion_m.id)?;
+                let mut key_col_comms = Vec::new();
+                for col_poly in advice.key_table.col_vals.iter() {
+                    key_col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+                }
+                let advice_comm = SemiJoinVerifyingAdvice {
+                    semi_sel,
+                    b_inclusion_m,
+                    key_table: TableComm::new(key_col_comms, verifier_tracker.transfer_prover_comm(advice.key_table.selector.id)?, advice.key_table.num_vars()),
+                    anti_m: verifier_tracker.transfer_prover_comm(advice.anti_m.id)?,
+                    b_disjoint_m: verifier_tracker.transfer_prover_comm(advice.b_disjoint_m.id)?,
+                };
+                if anti {
+                    AntiJoinIOP::<E, PCS>::verify_with_advice(verifier_tracker, &table_a_comm, &table_b_comm, a_key_col_indices, b_key_col_indices, &advice_comm, &range_bag_comm)?
+                } else {
+                    SemiJoinIOP::<E, PCS>::verify_with_advice(verifier_tracker, &table_a_comm, &table_b_comm, a_key_col_indices, b_key_col_indices, &advice_comm, &range_bag_comm)?
+                }
+            },
+        };
+        verifier_tracker.verify_claims()?;
+        assert_eq!(res_sel_comm.id, res_sel.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_evals)
+    }
+
+    #[test]
+    fn semi_join_test() {
+        let res = test_semi_join();
+        res.unwrap();
+    }
+}
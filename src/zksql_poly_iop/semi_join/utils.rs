@@ -0,0 +1,111 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_std::{Zero, One};
+use ark_poly::DenseMultilinearExtension;
+use std::collections::{BTreeSet, HashMap};
+
+// the key of a row, with one value per key column
+fn row_key<F: PrimeField>(key_cols: &[DenseMultilinearExtension<F>], i: usize) -> Vec<F> {
+    key_cols.iter().map(|col| col.evaluations[i]).collect()
+}
+
+// counts the selected rows of each key
+fn key_multiplicity_count<F: PrimeField>(
+    key_cols: &[DenseMultilinearExtension<F>],
+    sel: &DenseMultilinearExtension<F>,
+) -> HashMap<Vec<F>, u64> {
+    let mut mults_map = HashMap::<Vec<F>, u64>::new();
+    for i in 0..sel.evaluations.len() {
+        if !sel.evaluations[i].is_zero() {
+            *mults_map.entry(row_key(key_cols, i)).or_insert(0) += 1;
+        }
+    }
+    mults_map
+}
+
+// Calculates the semi-join selector, which is one on the rows of table_a whose key is one of the keys of the selected rows of table_b
+pub fn calc_semi_join_sel_advice<E>(
+    a_key_cols: &[DenseMultilinearExtension<E::ScalarField>],
+    b_key_cols: &[DenseMultilinearExtension<E::ScalarField>],
+    b_sel: &DenseMultilinearExtension<E::ScalarField>,
+) -> DenseMultilinearExtension<E::ScalarField>
+where
+    E: Pairing,
+{
+    let b_mults_map = key_multiplicity_count(b_key_cols, b_sel);
+    let a_nv = a_key_cols[0].num_vars;
+    let semi_sel_evals = (0..2_usize.pow(a_nv as u32)).map(|i| {
+        if b_mults_map.contains_key(&row_key(a_key_cols, i)) { E::ScalarField::one() } else { E::ScalarField::zero() }
+    }).collect();
+
+    DenseMultilinearExtension::from_evaluations_vec(a_nv, semi_sel_evals)
+}
+
+// Calculates the multiplicities for the inclusion of the semi-joined rows of table_a in table_b,
+// where the count of each key is put on the first selected row of table_b with that key
+pub fn calc_semi_join_inclusion_advice<E>(
+    a_key_cols: &[DenseMultilinearExtension<E::ScalarField>],
+    semi_a_sel: &DenseMultilinearExtension<E::ScalarField>,
+    b_key_cols: &[DenseMultilinearExtension<E::ScalarField>],
+    b_sel: &DenseMultilinearExtension<E::ScalarField>,
+) -> DenseMultilinearExtension<E::ScalarField>
+where
+    E: Pairing,
+{
+    let mut a_mults_map = key_multiplicity_count(a_key_cols, semi_a_sel);
+    let b_nv = b_sel.num_vars;
+    let b_inclusion_m_evals = (0..2_usize.pow(b_nv as u32)).map(|i| {
+        if b_sel.evaluations[i].is_zero() {
+            return E::ScalarField::zero();
+        }
+        // take the count out of the map, so later rows with the same key get zero
+        E::ScalarField::from(a_mults_map.remove(&row_key(b_key_cols, i)).unwrap_or(0))
+    }).collect();
+
+    DenseMultilinearExtension::from_evaluations_vec(b_nv, b_inclusion_m_evals)
+}
+
+// Calculates the advice for the disjointness of the anti-joined rows of table_a and table_b, like calc_bag_disjoint_advice:
+// the distinct keys of both as a table sorted lexicographically, with the selected keys at the front,
+// and the multiplicities of the keys of each
+// returns (key_cols, key_sel, anti_m, b_disjoint_m)
+pub fn calc_semi_join_disjoint_advice<E>(
+    a_key_cols: &[DenseMultilinearExtension<E::ScalarField>],
+    anti_a_sel: &DenseMultilinearExtension<E::ScalarField>,
+    b_key_cols: &[DenseMultilinearExtension<E::ScalarField>],
+    b_sel: &DenseMultilinearExtension<E::ScalarField>,
+) -> (Vec<DenseMultilinearExtension<E::ScalarField>>, DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>)
+where
+    E: Pairing,
+{
+    let a_mults_map = key_multiplicity_count(a_key_cols, anti_a_sel);
+    let b_mults_map = key_multiplicity_count(b_key_cols, b_sel);
+    let keys = a_mults_map.keys().chain(b_mults_map.keys()).cloned().collect::<BTreeSet<_>>();
+
+    let key_nv = anti_a_sel.num_vars.max(b_sel.num_vars) + 1;
+    let key_len = 2_usize.pow(key_nv as u32);
+    let mut key_col_evals = vec![Vec::<E::ScalarField>::with_capacity(key_len); a_key_cols.len()];
+    let mut anti_m_evals = Vec::<E::ScalarField>::with_capacity(key_len);
+    let mut b_disjoint_m_evals = Vec::<E::ScalarField>::with_capacity(key_len);
+    for key in keys.iter() {
+        for (col_evals, val) in key_col_evals.iter_mut().zip(key.iter()) {
+            col_evals.push(*val);
+        }
+        anti_m_evals.push(E::ScalarField::from(*a_mults_map.get(key).unwrap_or(&0)));
+        b_disjoint_m_evals.push(E::ScalarField::from(*b_mults_map.get(key).unwrap_or(&0)));
+    }
+    let mut key_sel_evals = vec![E::ScalarField::one(); keys.len()];
+
+    // pad with unselected zero rows
+    for evals in key_col_evals.iter_mut().chain([&mut key_sel_evals, &mut anti_m_evals, &mut b_disjoint_m_evals]) {
+        evals.resize(key_len, E::ScalarField::zero());
+    }
+    let key_col_mles = key_col_evals.into_iter().map(|evals| DenseMultilinearExtension::from_evaluations_vec(key_nv, evals)).collect();
+
+    (
+        key_col_mles,
+        DenseMultilinearExtension::from_evaluations_vec(key_nv, key_sel_evals),
+        DenseMultilinearExtension::from_evaluations_vec(key_nv, anti_m_evals),
+        DenseMultilinearExtension::from_evaluations_vec(key_nv, b_disjoint_m_evals),
+    )
+}
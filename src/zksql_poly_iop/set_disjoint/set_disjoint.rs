@@ -84,9 +84,9 @@ where PCS: PolynomialCommitmentScheme<E> {
         // move the sum_bag into the verifier tracker
         let sum_nv = max(bag_a.num_vars(), bag_b.num_vars()) + 1;
         let sum_id = verifier_tracker.get_next_id();
-        let sum_comm = verifier_tracker.transfer_prover_comm(sum_id);
+        let sum_comm = verifier_tracker.transfer_prover_comm(sum_id)?;
         let sum_sel_id = verifier_tracker.get_next_id();
-        let sum_sel_comm = verifier_tracker.transfer_prover_comm(sum_sel_id);
+        let sum_sel_comm = verifier_tracker.transfer_prover_comm(sum_sel_id)?;
         let sum_bag = &BagComm::new(sum_comm, sum_sel_comm, sum_nv);
 
         // verify the bag_sum was created correctly
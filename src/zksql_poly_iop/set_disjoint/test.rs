@@ -135,9 +135,9 @@ mod test {
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
         // let one_closure = |_: &[E::ScalarField]| -> Result<<E as Pairing>::ScalarField, PolyIOPErrors> {Ok(E::ScalarField::one())};
-        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id), verifier_tracker.transfer_prover_comm(bag_a.selector.id), bag_a.num_vars());
-        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id), verifier_tracker.transfer_prover_comm(bag_b.selector.id), bag_b.num_vars());
-        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id), verifier_tracker.transfer_prover_comm(range_bag.selector.id), range_bag.num_vars());
+        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id)?, verifier_tracker.transfer_prover_comm(bag_a.selector.id)?, bag_a.num_vars());
+        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id)?, verifier_tracker.transfer_prover_comm(bag_b.selector.id)?, bag_b.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_bag.num_vars());
         SetDisjointIOP::<E, PCS>::verify(
             verifier_tracker,
             &bag_a_comm,
@@ -324,12 +324,12 @@ mod test {
 
         // set up verifier tracker, create subclaims, and verify IOPProofs
         verifier_tracker.set_compiled_proof(proof);
-        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id), verifier_tracker.transfer_prover_comm(bag_a.selector.id), bag_a.num_vars());
-        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id), verifier_tracker.transfer_prover_comm(bag_b.selector.id), bag_b.num_vars());
-        let bag_l_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_l.poly.id), verifier_tracker.transfer_prover_comm(bag_l.selector.id), bag_l.num_vars());
-        let bag_m_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_m.poly.id), verifier_tracker.transfer_prover_comm(bag_m.selector.id), bag_m.num_vars());
-        let bag_r_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_r.poly.id), verifier_tracker.transfer_prover_comm(bag_r.selector.id), bag_r.num_vars());
-        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id), verifier_tracker.transfer_prover_comm(range_bag.selector.id), range_bag.num_vars());
+        let bag_a_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_a.poly.id)?, verifier_tracker.transfer_prover_comm(bag_a.selector.id)?, bag_a.num_vars());
+        let bag_b_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_b.poly.id)?, verifier_tracker.transfer_prover_comm(bag_b.selector.id)?, bag_b.num_vars());
+        let bag_l_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_l.poly.id)?, verifier_tracker.transfer_prover_comm(bag_l.selector.id)?, bag_l.num_vars());
+        let bag_m_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_m.poly.id)?, verifier_tracker.transfer_prover_comm(bag_m.selector.id)?, bag_m.num_vars());
+        let bag_r_comm = BagComm::new(verifier_tracker.transfer_prover_comm(bag_r.poly.id)?, verifier_tracker.transfer_prover_comm(bag_r.selector.id)?, bag_r.num_vars());
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_bag.num_vars());
         SetIntersectIOP::<E, PCS>::verify(
             verifier_tracker,
             &bag_a_comm,
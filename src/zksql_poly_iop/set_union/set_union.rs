@@ -77,9 +77,9 @@ where PCS: PolynomialCommitmentScheme<E> {
         // verify a + b = sum_bag
         let sum_nv = max(bag_a.num_vars(), bag_b.num_vars()) + 1;
         let sum_comm_id = verifier_tracker.get_next_id();
-        let sum_comm = verifier_tracker.transfer_prover_comm(sum_comm_id);
+        let sum_comm = verifier_tracker.transfer_prover_comm(sum_comm_id)?;
         let sum_sel_comm_id = verifier_tracker.get_next_id();
-        let sum_sel_comm = verifier_tracker.transfer_prover_comm(sum_sel_comm_id);
+        let sum_sel_comm = verifier_tracker.transfer_prover_comm(sum_sel_comm_id)?;
         let sum_bag = &BagComm::new(sum_comm, sum_sel_comm, sum_nv);
         BagSumIOP::<E, PCS>::verify(
             verifier_tracker,
@@ -0,0 +1,3 @@
+pub mod table_sort;
+pub mod utils;
+mod test;
@@ -0,0 +1,332 @@
+// Proves a table is the input table sorted by several key columns, as in `ORDER BY a ASC, b DESC`.
+// The selected rows of the sorted table come first, sorted lexicographically by the keys, and ties are allowed
+// 1. the sorted table is a row permutation of the input, shown with a bag equality of the rows compressed with random coefficients
+// 2. the sorted selector is boolean, and a selected row is never after an unselected one
+// 3. like BagStrictSortIOP, every row is compared with the next one through shifted copies of the key columns.
+//    first_diff[j] marks key j as the first key that differs from the next row, and when none is marked the keys are equal.
+//    the keys before the marked one are equal and the marked one increases (or decreases for a descending key), which is range checked.
+//    the steps of each key column are bounded by the range bag, see prove_range_check
+// 4. for a strict sort, a compared row always has a marked key, so no two selected rows have equal keys
+
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::One;
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        bag_eq::bag_eq::BagEqIOP,
+        bag_presc_perm::bag_presc_perm::BagPrescPermIOP,
+        bag_sort::bag_sort::SortOrder,
+        index_transform::utils::{prover_sample_rand_powers, table_row_prover_agg, table_row_verifier_agg, verifier_sample_rand_powers},
+        selector_valid::selector_valid::SelectorValidIOP,
+        table_sort::utils::calc_table_sort_advice,
+        util::prelude::{calc_lex_sort_advice, prove_range_check, shift_mle_left, verify_range_check},
+    },
+};
+
+pub struct TableSortIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> TableSortIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the sorted table, and proves it is sorted by the keys, given as (col_idx, order)
+    // returns the sorted table
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_keys(input_table.col_vals.len(), input_table.has_nulls(), keys)?;
+        let (sorted_col_mles, sorted_sel_mle) = calc_table_sort_advice(input_table, keys);
+        let mut sorted_cols = Vec::<TrackedPoly<E, PCS>>::with_capacity(sorted_col_mles.len());
+        for mle in sorted_col_mles {
+            sorted_cols.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+        let sorted_sel = prover_tracker.track_and_commit_poly(sorted_sel_mle)?;
+        let sorted_table = Table::new(sorted_cols, sorted_sel);
+
+        Self::prove_with_advice(prover_tracker, input_table, &sorted_table, keys, range_bag)?;
+        Ok(sorted_table)
+    }
+
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        sorted_table: &Table<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        Self::validate_keys(input_table.col_vals.len(), input_table.has_nulls(), keys)?;
+        Self::validate_shapes(input_table.col_vals.len(), input_table.num_vars(), sorted_table.col_vals.len(), sorted_table.num_vars())?;
+
+        // 1. the sorted table is a row permutation of the input
+        let rand_coeffs = prover_sample_rand_powers(prover_tracker, input_table.col_vals.len())?;
+        BagEqIOP::<E, PCS>::prove(
+            prover_tracker,
+            &table_row_prover_agg(input_table, &rand_coeffs)?,
+            &table_row_prover_agg(sorted_table, &rand_coeffs)?,
+        )?;
+
+        // 2. and 3. the sorted table is in order
+        Self::prove_sorted(prover_tracker, sorted_table, keys, range_bag, false)
+    }
+
+    // proves the selected rows of the table come first and are sorted by the keys, on its own without an input table.
+    // with strict, no two selected rows have equal keys
+    pub fn prove_sorted(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        sorted_table: &Table<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        range_bag: &Bag<E, PCS>,
+        strict: bool,
+    ) -> Result<(), PolyIOPErrors> {
+        Self::validate_keys(sorted_table.col_vals.len(), sorted_table.has_nulls(), keys)?;
+        let nv = sorted_table.num_vars();
+        let one = E::ScalarField::one();
+
+        // 2. the sorted selector is boolean
+        let sel = &sorted_table.selector;
+        SelectorValidIOP::<E, PCS>::prove(prover_tracker, sel)?;
+
+        // commit to the shifted key columns and selector, and the first difference markers
+        let key_cols = keys.iter().map(|(col_idx, _)| sorted_table.col_vals[*col_idx].clone()).collect::<Vec<_>>();
+        let mut next_cols = Vec::<TrackedPoly<E, PCS>>::with_capacity(keys.len());
+        for col in key_cols.iter() {
+            let next_col_mle = shift_mle_left(&DenseMultilinearExtension::from_evaluations_vec(nv, col.evaluations()));
+            next_cols.push(prover_tracker.track_and_commit_poly(next_col_mle)?);
+        }
+        let next_sel = prover_tracker.track_and_commit_poly(shift_mle_left(&DenseMultilinearExtension::from_evaluations_vec(nv, sel.evaluations())))?;
+        let mut first_diff = Vec::<TrackedPoly<E, PCS>>::with_capacity(keys.len());
+        for mle in calc_lex_sort_advice(&Table::new(key_cols.clone(), sel.clone())) {
+            first_diff.push(prover_tracker.track_and_commit_poly(mle)?);
+        }
+
+        // show the next columns are the columns shifted by one, all at once with a random combination
+        let delta = prover_tracker.get_and_append_challenge(b"delta")?;
+        let one_poly = prover_tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: one });
+        let shift_perm_poly = prover_tracker.track_public_poly(PublicPoly::ShiftedIdentity { num_vars: nv, shift: 1 });
+        let mut combined = sel.clone();
+        let mut next_combined = next_sel.clone();
+        let mut delta_pow = one;
+        for (col, next_col) in key_cols.iter().zip(next_cols.iter()) {
+            delta_pow *= delta;
+            combined = combined.add_poly(&col.mul_scalar(delta_pow));
+            next_combined = next_combined.add_poly(&next_col.mul_scalar(delta_pow));
+        }
+        BagPrescPermIOP::<E, PCS>::prove(
+            prover_tracker,
+            &Bag::new(combined, one_poly.clone()),
+            &Bag::new(next_combined, one_poly.clone()),
+            &shift_perm_poly,
+        )?;
+
+        // a row is compared with the next one when the next one is selected, skipping the last row since its next row wraps around.
+        // a selected next row needs a selected row before it, so the selected rows come first
+        let last_row = prover_tracker.track_public_poly(PublicPoly::Eq { r: vec![one; nv] });
+        let compared = next_sel.sub_poly(&next_sel.mul_poly(&last_row));
+        prover_tracker.add_zerocheck_claim(compared.sub_poly(&compared.mul_poly(sel)).id);
+
+        // 3. the markers are boolean with at most one set, i.e. rows_equal = 1 - sum(first_diff) is boolean too
+        let mut rows_equal = one_poly.clone();
+        for marker in first_diff.iter() {
+            prover_tracker.add_zerocheck_claim(marker.mul_poly(marker).sub_poly(marker).id);
+            rows_equal = rows_equal.sub_poly(marker);
+        }
+        prover_tracker.add_zerocheck_claim(rows_equal.mul_poly(&rows_equal).sub_poly(&rows_equal).id);
+        if strict {
+            prover_tracker.add_zerocheck_claim(compared.mul_poly(&rows_equal).id);
+        }
+
+        // keys before the first difference are equal, i.e. key j is equal when the marker is after j or the keys are equal
+        let mut marked_after = rows_equal.clone();
+        for j in (0..keys.len()).rev() {
+            let col_diff = next_cols[j].sub_poly(&key_cols[j]);
+            prover_tracker.add_zerocheck_claim(compared.mul_poly(&marked_after).mul_poly(&col_diff).id);
+            marked_after = marked_after.add_poly(&first_diff[j]);
+        }
+
+        // the first differing key moves in its order
+        let col_steps = key_cols.iter().zip(next_cols.iter()).zip(keys.iter()).map(|((col, next_col), (_, order))| match order {
+            SortOrder::Asc => next_col.sub_poly(col),
+            SortOrder::Desc => col.sub_poly(next_col),
+        }).collect::<Vec<_>>();
+        let mut step = first_diff[0].mul_poly(&col_steps[0].add_scalar(-one));
+        for (marker, col_step) in first_diff.iter().zip(col_steps.iter()).skip(1) {
+            step = step.add_poly(&marker.mul_poly(&col_step.add_scalar(-one)));
+        }
+        prove_range_check(prover_tracker, &Bag::new(step, compared), range_bag)?;
+
+        Ok(())
+    }
+
+    // takes the commitments to the sorted table from the proof
+    // returns the sorted table
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_keys(input_table.col_vals.len(), input_table.has_nulls(), keys)?;
+        let mut sorted_cols = Vec::<TrackedComm<E, PCS>>::with_capacity(input_table.col_vals.len());
+        for _ in 0..input_table.col_vals.len() {
+            let id = verifier_tracker.get_next_id();
+            sorted_cols.push(verifier_tracker.transfer_prover_comm(id)?);
+        }
+        let sorted_sel_id = verifier_tracker.get_next_id();
+        let sorted_sel = verifier_tracker.transfer_prover_comm(sorted_sel_id)?;
+        let sorted_table = TableComm::new(sorted_cols, sorted_sel, input_table.num_vars());
+
+        Self::verify_with_advice(verifier_tracker, input_table, &sorted_table, keys, range_bag)?;
+        Ok(sorted_table)
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        sorted_table: &TableComm<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        Self::validate_keys(input_table.col_vals.len(), input_table.has_nulls(), keys)?;
+        Self::validate_shapes(input_table.col_vals.len(), input_table.num_vars(), sorted_table.col_vals.len(), sorted_table.num_vars())?;
+
+        // 1. the sorted table is a row permutation of the input
+        let rand_coeffs = verifier_sample_rand_powers(verifier_tracker, input_table.col_vals.len())?;
+        BagEqIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &table_row_verifier_agg(input_table, &rand_coeffs)?,
+            &table_row_verifier_agg(sorted_table, &rand_coeffs)?,
+        )?;
+
+        // 2. and 3. the sorted table is in order
+        Self::verify_sorted(verifier_tracker, sorted_table, keys, range_bag, false)
+    }
+
+    pub fn verify_sorted(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        sorted_table: &TableComm<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        range_bag: &BagComm<E, PCS>,
+        strict: bool,
+    ) -> Result<(), PolyIOPErrors> {
+        Self::validate_keys(sorted_table.col_vals.len(), sorted_table.has_nulls(), keys)?;
+        let nv = sorted_table.num_vars();
+        let one = E::ScalarField::one();
+
+        // 2. the sorted selector is boolean
+        let sel = &sorted_table.selector;
+        SelectorValidIOP::<E, PCS>::verify(verifier_tracker, sel)?;
+
+        // get the shifted key columns and selector, and the first difference markers
+        let key_cols = keys.iter().map(|(col_idx, _)| sorted_table.col_vals[*col_idx].clone()).collect::<Vec<_>>();
+        let mut next_cols = Vec::<TrackedComm<E, PCS>>::with_capacity(keys.len());
+        for _ in 0..keys.len() {
+            let id = verifier_tracker.get_next_id();
+            next_cols.push(verifier_tracker.transfer_prover_comm(id)?);
+        }
+        let next_sel_id = verifier_tracker.get_next_id();
+        let next_sel = verifier_tracker.transfer_prover_comm(next_sel_id)?;
+        let mut first_diff = Vec::<TrackedComm<E, PCS>>::with_capacity(keys.len());
+        for _ in 0..keys.len() {
+            let id = verifier_tracker.get_next_id();
+            first_diff.push(verifier_tracker.transfer_prover_comm(id)?);
+        }
+
+        // the next columns are the columns shifted by one
+        let delta = verifier_tracker.get_and_append_challenge(b"delta")?;
+        let one_comm = verifier_tracker.track_public_poly(PublicPoly::Constant { num_vars: nv, value: one });
+        let shift_perm_comm = verifier_tracker.track_public_poly(PublicPoly::ShiftedIdentity { num_vars: nv, shift: 1 });
+        let mut combined = sel.clone();
+        let mut next_combined = next_sel.clone();
+        let mut delta_pow = one;
+        for (col, next_col) in key_cols.iter().zip(next_cols.iter()) {
+            delta_pow *= delta;
+            combined = combined.add_comms(&col.mul_scalar(delta_pow));
+            next_combined = next_combined.add_comms(&next_col.mul_scalar(delta_pow));
+        }
+        BagPrescPermIOP::<E, PCS>::verify(
+            verifier_tracker,
+            &BagComm::new(combined, one_comm.clone(), nv),
+            &BagComm::new(next_combined, one_comm.clone(), nv),
+            &shift_perm_comm,
+        )?;
+
+        // the selected rows come first
+        let last_row = verifier_tracker.track_public_poly(PublicPoly::Eq { r: vec![one; nv] });
+        let compared = next_sel.sub_comms(&next_sel.mul_comms(&last_row));
+        verifier_tracker.add_zerocheck_claim(compared.sub_comms(&compared.mul_comms(sel)).id);
+
+        // 3. the markers are boolean with at most one set
+        let mut rows_equal = one_comm.clone();
+        for marker in first_diff.iter() {
+            verifier_tracker.add_zerocheck_claim(marker.mul_comms(marker).sub_comms(marker).id);
+            rows_equal = rows_equal.sub_comms(marker);
+        }
+        verifier_tracker.add_zerocheck_claim(rows_equal.mul_comms(&rows_equal).sub_comms(&rows_equal).id);
+        if strict {
+            verifier_tracker.add_zerocheck_claim(compared.mul_comms(&rows_equal).id);
+        }
+
+        // keys before the first difference are equal
+        let mut marked_after = rows_equal.clone();
+        for j in (0..keys.len()).rev() {
+            let col_diff = next_cols[j].sub_comms(&key_cols[j]);
+            verifier_tracker.add_zerocheck_claim(compared.mul_comms(&marked_after).mul_comms(&col_diff).id);
+            marked_after = marked_after.add_comms(&first_diff[j]);
+        }
+
+        // the first differing key moves in its order
+        let col_steps = key_cols.iter().zip(next_cols.iter()).zip(keys.iter()).map(|((col, next_col), (_, order))| match order {
+            SortOrder::Asc => next_col.sub_comms(col),
+            SortOrder::Desc => col.sub_comms(next_col),
+        }).collect::<Vec<_>>();
+        let mut step = first_diff[0].mul_comms(&col_steps[0].add_scalar(-one));
+        for (marker, col_step) in first_diff.iter().zip(col_steps.iter()).skip(1) {
+            step = step.add_comms(&marker.mul_comms(&col_step.add_scalar(-one)));
+        }
+        verify_range_check(verifier_tracker, &BagComm::new(step, compared, nv), range_bag)?;
+
+        Ok(())
+    }
+
+    // the sorted table is built without validity selectors, so the table can not have NULLs
+    fn validate_keys(
+        num_cols: usize,
+        has_nulls: bool,
+        keys: &[(usize, SortOrder)],
+    ) -> Result<(), PolyIOPErrors> {
+        if has_nulls {
+            return Err(PolyIOPErrors::InvalidParameters("TableSortIOP Error: the table can not have NULLs".to_string()));
+        }
+        if keys.is_empty() {
+            return Err(PolyIOPErrors::InvalidParameters("TableSortIOP Error: no sort keys".to_string()));
+        }
+        for (col_idx, _) in keys.iter() {
+            if *col_idx >= num_cols {
+                return Err(PolyIOPErrors::InvalidParameters(format!(
+                    "TableSortIOP Error: sort key column {} is out of bounds for a table with {} columns", col_idx, num_cols
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_shapes(
+        input_num_cols: usize,
+        input_nv: usize,
+        sorted_num_cols: usize,
+        sorted_nv: usize,
+    ) -> Result<(), PolyIOPErrors> {
+        if input_num_cols != sorted_num_cols || input_nv != sorted_nv {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "TableSortIOP Error: the sorted table has {} columns with {} variables, but the input has {} columns with {} variables",
+                sorted_num_cols, sorted_nv, input_num_cols, input_nv
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::{
+            bag_sort::bag_sort::SortOrder,
+            table_sort::table_sort::TableSortIOP,
+        },
+    };
+
+    fn test_table_sort() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // a table of (category, price, id) with ties in the first two columns, where rows 3 and 7 are not selected
+        let nv = 3;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let to_evals = |nums: &[u64]| nums.iter().map(|x| Fr::from(*x)).collect::<Vec<Fr>>();
+        let category = to_mle(&[2, 1, 2, 1, 3, 1, 2, 9]);
+        let price = to_mle(&[5, 7, 5, 3, 1, 7, 8, 0]);
+        let id = to_mle(&[10, 11, 12, 13, 14, 15, 16, 17]);
+        let cols = vec![category.clone(), price.clone(), id.clone()];
+        let sel = to_mle(&[1, 1, 1, 0, 1, 1, 1, 0]);
+
+        // Test good paths, checking the order of the ids and that the selected rows come first
+        let good_cases = [
+            (vec![(0, SortOrder::Asc), (1, SortOrder::Desc)], [11, 15, 16, 10, 12, 14, 13, 17]),
+            (vec![(1, SortOrder::Asc)], [14, 10, 12, 11, 15, 16, 13, 17]),
+            (vec![(2, SortOrder::Desc)], [16, 15, 14, 12, 11, 10, 13, 17]),
+            (vec![(0, SortOrder::Desc), (1, SortOrder::Asc), (2, SortOrder::Desc)], [14, 12, 10, 16, 15, 11, 13, 17]),
+        ];
+        for (keys, expected_ids) in good_cases.iter() {
+            print!("Testing table sort by {:?} good path: ", keys);
+            let res_evals = test_table_sort_helper(
+                &mut prover_tracker,
+                &mut verifier_tracker,
+                &cols,
+                &sel,
+                keys,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            )?;
+            assert_eq!(res_evals[2], to_evals(expected_ids));
+            assert_eq!(res_evals[3], to_evals(&[1, 1, 1, 1, 1, 1, 0, 0]));
+            println!("passed");
+        }
+
+        // Test the given advice good path
+        let keys = vec![(0, SortOrder::Asc), (1, SortOrder::Desc)];
+        let sorted_category = [1, 1, 2, 2, 2, 3, 1, 9];
+        let sorted_price = [7, 7, 8, 5, 5, 1, 3, 0];
+        let sorted_id = [11, 15, 16, 10, 12, 14, 13, 17];
+        let sorted_sel = [1, 1, 1, 1, 1, 1, 0, 0];
+        let sorted_cols = vec![to_mle(&sorted_category), to_mle(&sorted_price), to_mle(&sorted_id)];
+        print!("Testing table sort with advice good path: ");
+        test_table_sort_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &cols,
+            &sel,
+            &keys,
+            Some([sorted_cols.clone(), vec![to_mle(&sorted_sel)]].concat()),
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // Test bad paths, each a wrong sorted table
+        let swap_rows = |i: usize, j: usize| {
+            sorted_cols.iter().map(|col| {
+                let mut evals = col.evaluations.clone();
+                evals.swap(i, j);
+                DenseMultilinearExtension::from_evaluations_vec(nv, evals)
+            }).collect::<Vec<_>>()
+        };
+        let mut changed_id = sorted_id;
+        changed_id[0] = 99;
+        let bad_cases = [
+            // the first key is out of order
+            (swap_rows(0, 2), to_mle(&sorted_sel)),
+            // the second key is ascending within a tie of the first
+            (swap_rows(2, 3), to_mle(&sorted_sel)),
+            // a selected row after an unselected one
+            (swap_rows(5, 6), to_mle(&[1, 1, 1, 1, 1, 0, 1, 0])),
+            // a row that is not in the input
+            (vec![to_mle(&sorted_category), to_mle(&sorted_price), to_mle(&changed_id)], to_mle(&sorted_sel)),
+            // a dropped row
+            (sorted_cols.clone(), to_mle(&[1, 1, 1, 1, 1, 0, 0, 0])),
+            // a selector that is not boolean
+            (sorted_cols.clone(), to_mle(&[1, 1, 1, 1, 1, 1, 2, 0])),
+        ];
+        for (i, (bad_cols, bad_sel)) in bad_cases.into_iter().enumerate() {
+            print!("Testing table sort bad path {}: ", i + 1);
+            let bad_result = test_table_sort_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &cols,
+                &sel,
+                &keys,
+                Some([bad_cols, vec![bad_sel]].concat()),
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        // Test a strict sort on the sorted table, which holds with the ids as the last key but not with the first two keys alone
+        for (strict_keys, holds) in [(vec![(0, SortOrder::Asc), (1, SortOrder::Desc), (2, SortOrder::Asc)], true), (keys.clone(), false)] {
+            print!("Testing strict table sort by {:?}: ", strict_keys);
+            let res = test_strict_table_sort_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &sorted_cols,
+                &to_mle(&sorted_sel),
+                &strict_keys,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert_eq!(res.is_ok(), holds);
+            println!("passed");
+        }
+
+        // Test bad keys, which are rejected before proving
+        print!("Testing table sort with bad keys: ");
+        for bad_keys in [vec![], vec![(3, SortOrder::Asc)]] {
+            let bad_result = test_table_sort_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &cols,
+                &sel,
+                &bad_keys,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+        }
+        println!("passed");
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies TableSortIOP, with the computed sorted table or the given one,
+    // which is given as the sorted columns followed by the sorted selector
+    // returns the evaluations of the sorted columns followed by the sorted selector
+    fn test_table_sort_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        cols: &[DenseMultilinearExtension<E::ScalarField>],
+        sel: &DenseMultilinearExtension<E::ScalarField>,
+        keys: &[(usize, SortOrder)],
+        sorted_advice: Option<Vec<DenseMultilinearExtension<E::ScalarField>>>,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<Vec<E::ScalarField>>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let nv = sel.num_vars;
+
+        // set up the prover tracker and prove
+        let mut col_polys = Vec::new();
+        for col in cols.iter() {
+            col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let input_table = Table::new(col_polys, prover_tracker.track_and_commit_poly(sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let given_advice = sorted_advice.is_some();
+        let sorted_table = match sorted_advice {
+            None => TableSortIOP::<E, PCS>::prove(prover_tracker, &input_table, keys, &range_bag)?,
+            Some(mut sorted_mles) => {
+                let sorted_sel_mle = sorted_mles.pop().unwrap();
+                let mut sorted_cols = Vec::new();
+                for mle in sorted_mles {
+                    sorted_cols.push(prover_tracker.track_and_commit_poly(mle)?);
+                }
+                let sorted_table = Table::new(sorted_cols, prover_tracker.track_and_commit_poly(sorted_sel_mle)?);
+                TableSortIOP::<E, PCS>::prove_with_advice(prover_tracker, &input_table, &sorted_table, keys, &range_bag)?;
+                sorted_table
+            },
+        };
+        let mut res_evals = sorted_table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+        res_evals.push(sorted_table.selector.evaluations());
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut col_comms = Vec::new();
+        for col_poly in input_table.col_vals.iter() {
+            col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let input_table_comm = TableComm::new(col_comms, verifier_tracker.transfer_prover_comm(input_table.selector.id)?, nv);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let sorted_table_comm = if given_advice {
+            let mut sorted_comms = Vec::new();
+            for col_poly in sorted_table.col_vals.iter() {
+                sorted_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+            }
+            let sorted_table_comm = TableComm::new(sorted_comms, verifier_tracker.transfer_prover_comm(sorted_table.selector.id)?, nv);
+            TableSortIOP::<E, PCS>::verify_with_advice(verifier_tracker, &input_table_comm, &sorted_table_comm, keys, &range_bag_comm)?;
+            sorted_table_comm
+        } else {
+            TableSortIOP::<E, PCS>::verify(verifier_tracker, &input_table_comm, keys, &range_bag_comm)?
+        };
+        verifier_tracker.verify_claims()?;
+        assert_eq!(sorted_table_comm.selector.id, sorted_table.selector.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_evals)
+    }
+
+    // Given a sorted table, calls and verifies the strict sort check of TableSortIOP on it
+    fn test_strict_table_sort_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        cols: &[DenseMultilinearExtension<E::ScalarField>],
+        sel: &DenseMultilinearExtension<E::ScalarField>,
+        keys: &[(usize, SortOrder)],
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<(), PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let nv = sel.num_vars;
+
+        // set up the prover tracker and prove
+        let mut col_polys = Vec::new();
+        for col in cols.iter() {
+            col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let table = Table::new(col_polys, prover_tracker.track_and_commit_poly(sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        TableSortIOP::<E, PCS>::prove_sorted(prover_tracker, &table, keys, &range_bag, true)?;
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut col_comms = Vec::new();
+        for col_poly in table.col_vals.iter() {
+            col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let table_comm = TableComm::new(col_comms, verifier_tracker.transfer_prover_comm(table.selector.id)?, nv);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        TableSortIOP::<E, PCS>::verify_sorted(verifier_tracker, &table_comm, keys, &range_bag_comm, true)?;
+        verifier_tracker.verify_claims()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn table_sort_test() {
+        let res = test_table_sort();
+        res.unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{One, Zero};
+use std::cmp::Ordering;
+
+use crate::tracker::prelude::*;
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::zksql_poly_iop::bag_sort::bag_sort::SortOrder;
+
+// Calculates the sorted table: the selected rows sorted by the keys at the front,
+// followed by the unselected rows in their original order
+// returns (sorted_cols, sorted_sel)
+pub fn calc_table_sort_advice<E, PCS>(
+    table: &Table<E, PCS>,
+    keys: &[(usize, SortOrder)],
+) -> (Vec<DenseMultilinearExtension<E::ScalarField>>, DenseMultilinearExtension<E::ScalarField>)
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let nv = table.num_vars();
+    let len = 2_usize.pow(nv as u32);
+    let col_evals = table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+    let sel_evals = table.selector.evaluations();
+
+    let mut selected_rows = (0..len).filter(|i| !sel_evals[*i].is_zero()).collect::<Vec<_>>();
+    selected_rows.sort_by(|a, b| {
+        for (col_idx, order) in keys.iter() {
+            let ord = col_evals[*col_idx][*a].cmp(&col_evals[*col_idx][*b]);
+            let ord = if *order == SortOrder::Desc { ord.reverse() } else { ord };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+        Ordering::Equal
+    });
+    let num_selected = selected_rows.len();
+    let row_order = selected_rows.into_iter().chain((0..len).filter(|i| sel_evals[*i].is_zero())).collect::<Vec<_>>();
+
+    let sorted_cols = col_evals.iter().map(|evals| {
+        DenseMultilinearExtension::from_evaluations_vec(nv, row_order.iter().map(|i| evals[*i]).collect())
+    }).collect();
+    let sorted_sel_evals = (0..len).map(|i| if i < num_selected { E::ScalarField::one() } else { E::ScalarField::zero() }).collect();
+    (sorted_cols, DenseMultilinearExtension::from_evaluations_vec(nv, sorted_sel_evals))
+}
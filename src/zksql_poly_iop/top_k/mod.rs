@@ -0,0 +1,2 @@
+pub mod top_k;
+mod test;
@@ -0,0 +1,238 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::{
+            bag_sort::bag_sort::SortOrder,
+            top_k::top_k::TopKIOP,
+        },
+    };
+
+    fn test_top_k() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // a leaderboard of (player, team, score), where player 7 is not selected
+        let nv = 3;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let to_evals = |nums: &[u64]| nums.iter().map(|x| Fr::from(*x)).collect::<Vec<Fr>>();
+        let player = to_mle(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let team = to_mle(&[1, 2, 1, 2, 1, 2, 1, 2]);
+        let score = to_mle(&[50, 80, 80, 20, 95, 60, 80, 10]);
+        let cols = vec![player.clone(), team.clone(), score.clone()];
+        let sel = to_mle(&[1, 1, 1, 1, 1, 1, 0, 1]);
+
+        // Test good paths, checking the players of the first k rows and the result selector
+        let good_cases = [
+            (vec![(2, SortOrder::Desc)], 3, vec![5, 2, 3]),
+            (vec![(1, SortOrder::Asc), (2, SortOrder::Desc)], 4, vec![5, 3, 1, 2]),
+            (vec![(2, SortOrder::Asc)], 7, vec![8, 4, 1, 6, 2, 3, 5]),
+            (vec![(2, SortOrder::Desc)], 0, vec![]),
+        ];
+        for (keys, k, expected_players) in good_cases.iter() {
+            print!("Testing top {} by {:?} good path: ", k, keys);
+            let res_evals = test_top_k_helper(
+                &mut prover_tracker,
+                &mut verifier_tracker,
+                &cols,
+                &sel,
+                keys,
+                *k,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            )?;
+            assert_eq!(res_evals[0][..*k].to_vec(), to_evals(expected_players));
+            let expected_sel = (0..8).map(|i| if i < *k { 1 } else { 0 }).collect::<Vec<_>>();
+            assert_eq!(res_evals[3], to_evals(&expected_sel));
+            println!("passed");
+        }
+
+        // Test bad paths with a given sorted table
+        let keys = vec![(2, SortOrder::Asc)];
+        let sorted_player = [8, 4, 1, 6, 2, 3, 5, 7];
+        let sorted_team = [2, 2, 1, 2, 2, 1, 1, 1];
+        let sorted_score = [10, 20, 50, 60, 80, 80, 95, 80];
+        let sorted_sel = [1, 1, 1, 1, 1, 1, 1, 0];
+        let sorted_advice = vec![to_mle(&sorted_player), to_mle(&sorted_team), to_mle(&sorted_score), to_mle(&sorted_sel)];
+
+        print!("Testing top k with advice good path: ");
+        test_top_k_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &cols,
+            &sel,
+            &keys,
+            2,
+            Some(sorted_advice.clone()),
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // asking for more rows than are selected
+        print!("Testing top k bad path 1: ");
+        let bad_result = test_top_k_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &cols,
+            &sel,
+            &keys,
+            8,
+            Some(sorted_advice.clone()),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // the top rows are not the best ones, a row of the top is swapped with an excluded one
+        print!("Testing top k bad path 2: ");
+        let swap = |nums: &[u64]| {
+            let mut nums = nums.to_vec();
+            nums.swap(1, 4);
+            to_mle(&nums)
+        };
+        let bad_advice = vec![swap(&sorted_player), swap(&sorted_team), swap(&sorted_score), to_mle(&sorted_sel)];
+        let bad_result = test_top_k_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &cols,
+            &sel,
+            &keys,
+            2,
+            Some(bad_advice),
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad parameters, which are rejected before proving
+        print!("Testing top k with bad parameters: ");
+        for bad_k in [8, 9] {
+            let bad_result = test_top_k_helper::<Bls12_381, MultilinearKzgPCS<Bls12_381>>(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &cols,
+                &sel,
+                &keys,
+                bad_k,
+                None,
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+        }
+        println!("passed");
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies TopKIOP, with the computed sorted table or the given one,
+    // which is given as the sorted columns followed by the sorted selector
+    // returns the evaluations of the result columns followed by the result selector
+    fn test_top_k_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        cols: &[DenseMultilinearExtension<E::ScalarField>],
+        sel: &DenseMultilinearExtension<E::ScalarField>,
+        keys: &[(usize, SortOrder)],
+        k: usize,
+        sorted_advice: Option<Vec<DenseMultilinearExtension<E::ScalarField>>>,
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<Vec<Vec<E::ScalarField>>, PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        let nv = sel.num_vars;
+
+        // set up the prover tracker and prove
+        let mut col_polys = Vec::new();
+        for col in cols.iter() {
+            col_polys.push(prover_tracker.track_and_commit_poly(col.clone())?);
+        }
+        let input_table = Table::new(col_polys, prover_tracker.track_and_commit_poly(sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let (res_table, sorted_table) = match sorted_advice {
+            None => (TopKIOP::<E, PCS>::prove(prover_tracker, &input_table, keys, k, &range_bag)?, None),
+            Some(mut sorted_mles) => {
+                let sorted_sel_mle = sorted_mles.pop().unwrap();
+                let mut sorted_cols = Vec::new();
+                for mle in sorted_mles {
+                    sorted_cols.push(prover_tracker.track_and_commit_poly(mle)?);
+                }
+                let sorted_table = Table::new(sorted_cols, prover_tracker.track_and_commit_poly(sorted_sel_mle)?);
+                let res_table = TopKIOP::<E, PCS>::prove_with_advice(prover_tracker, &input_table, &sorted_table, keys, k, &range_bag)?;
+                (res_table, Some(sorted_table))
+            },
+        };
+        let mut res_evals = res_table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+        res_evals.push(res_table.selector.evaluations());
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let mut col_comms = Vec::new();
+        for col_poly in input_table.col_vals.iter() {
+            col_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+        }
+        let input_table_comm = TableComm::new(col_comms, verifier_tracker.transfer_prover_comm(input_table.selector.id)?, nv);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        let res_table_comm = match sorted_table {
+            None => TopKIOP::<E, PCS>::verify(verifier_tracker, &input_table_comm, keys, k, &range_bag_comm)?,
+            Some(sorted_table) => {
+                let mut sorted_comms = Vec::new();
+                for col_poly in sorted_table.col_vals.iter() {
+                    sorted_comms.push(verifier_tracker.transfer_prover_comm(col_poly.id)?);
+                }
+                let sorted_table_comm = TableComm::new(sorted_comms, verifier_tracker.transfer_prover_comm(sorted_table.selector.id)?, nv);
+                TopKIOP::<E, PCS>::verify_with_advice(verifier_tracker, &input_table_comm, &sorted_table_comm, keys, k, &range_bag_comm)?
+            },
+        };
+        verifier_tracker.verify_claims()?;
+        assert_eq!(res_table_comm.selector.id, res_table.selector.id);
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(res_evals)
+    }
+
+    #[test]
+    fn top_k_test() {
+        let res = test_top_k();
+        res.unwrap();
+    }
+}
@@ -0,0 +1,135 @@
+// Proves a table holds the first k rows of the input table ordered by several key columns, as in `ORDER BY score DESC LIMIT k`.
+// 1. the input is sorted with TableSortIOP, so its selected rows come first in the order of the keys.
+//    the rows after the k-th one are the excluded rows, and each of them compares no better than the k-th one
+// 2. the result has the columns of the sorted table, and its selector is the sorted selector on the first k rows
+// 3. a sumcheck over the result selector shows it has exactly k rows, so the input has at least k selected rows.
+//    the sum of sel - k / 2^nv is zero, which stays zero when the proof pads the selector to more variables
+
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+use ark_std::Zero;
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        bag_sort::bag_sort::SortOrder,
+        table_sort::table_sort::TableSortIOP,
+    },
+};
+
+pub struct TopKIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> TopKIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the sorted table, and proves the first k of its rows are the top k rows of the input
+    // returns the result table
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        k: usize,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_k(input_table.num_vars(), k)?;
+        let num_selected = input_table.selector.evaluations().iter().filter(|x| !x.is_zero()).count();
+        if num_selected < k {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "TopKIOP Error: the input table has {} selected rows, fewer than k = {}", num_selected, k
+            )));
+        }
+
+        let sorted_table = TableSortIOP::<E, PCS>::prove(prover_tracker, input_table, keys, range_bag)?;
+        Self::prove_top_k(prover_tracker, &sorted_table, k)
+    }
+
+    // proves with the sorted table as advice
+    // returns the result table
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        input_table: &Table<E, PCS>,
+        sorted_table: &Table<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        k: usize,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        Self::validate_k(input_table.num_vars(), k)?;
+        TableSortIOP::<E, PCS>::prove_with_advice(prover_tracker, input_table, sorted_table, keys, range_bag)?;
+        Self::prove_top_k(prover_tracker, sorted_table, k)
+    }
+
+    // takes the commitments to the sorted table from the proof
+    // returns the result table
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        k: usize,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_k(input_table.num_vars(), k)?;
+        let sorted_table = TableSortIOP::<E, PCS>::verify(verifier_tracker, input_table, keys, range_bag)?;
+        Self::verify_top_k(verifier_tracker, &sorted_table, k)
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        input_table: &TableComm<E, PCS>,
+        sorted_table: &TableComm<E, PCS>,
+        keys: &[(usize, SortOrder)],
+        k: usize,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        Self::validate_k(input_table.num_vars(), k)?;
+        TableSortIOP::<E, PCS>::verify_with_advice(verifier_tracker, input_table, sorted_table, keys, range_bag)?;
+        Self::verify_top_k(verifier_tracker, sorted_table, k)
+    }
+
+    // keeps the first k rows of the sorted table and shows they are all selected
+    fn prove_top_k(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        sorted_table: &Table<E, PCS>,
+        k: usize,
+    ) -> Result<Table<E, PCS>, PolyIOPErrors> {
+        let nv = sorted_table.num_vars();
+        let prefix = prover_tracker.track_public_poly(PublicPoly::Prefix { num_vars: nv, len: k });
+        let top_sel = sorted_table.selector.mul_poly(&prefix);
+        let count_check_poly = top_sel.add_scalar(-Self::row_share(nv, k));
+        prover_tracker.add_sumcheck_claim(count_check_poly.id, E::ScalarField::zero());
+
+        Ok(Table::new(sorted_table.col_vals.clone(), top_sel))
+    }
+
+    fn verify_top_k(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        sorted_table: &TableComm<E, PCS>,
+        k: usize,
+    ) -> Result<TableComm<E, PCS>, PolyIOPErrors> {
+        let nv = sorted_table.num_vars();
+        let prefix = verifier_tracker.track_public_poly(PublicPoly::Prefix { num_vars: nv, len: k });
+        let top_sel = sorted_table.selector.mul_comms(&prefix);
+        let count_check_comm = top_sel.add_scalar(-Self::row_share(nv, k));
+        verifier_tracker.add_sumcheck_claim(count_check_comm.id, E::ScalarField::zero());
+
+        Ok(TableComm::new(sorted_table.col_vals.clone(), top_sel, nv))
+    }
+
+    // k / 2^nv, the share of the k rows on each of the 2^nv rows
+    fn row_share(nv: usize, k: usize) -> E::ScalarField {
+        E::ScalarField::from(k as u64) / E::ScalarField::from(2_u64).pow([nv as u64])
+    }
+
+    fn validate_k(
+        nv: usize,
+        k: usize,
+    ) -> Result<(), PolyIOPErrors> {
+        // k = 0 is LIMIT 0, whose result table has no rows
+        if k > 2_usize.pow(nv as u32) {
+            return Err(PolyIOPErrors::InvalidParameters(format!(
+                "TopKIOP Error: k = {} is more than the {} rows of the table", k, 2_usize.pow(nv as u32)
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod unique_column;
+pub mod utils;
+mod test;
@@ -0,0 +1,192 @@
+#[cfg(test)]
+mod test {
+    use ark_ec::pairing::Pairing;
+    use ark_poly::DenseMultilinearExtension;
+    use ark_std::One;
+    use crate::subroutines::{
+        pcs::PolynomialCommitmentScheme,
+        MultilinearKzgPCS
+    };
+
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_std::test_rng;
+
+    use crate::{
+        tracker::prelude::*,
+        zksql_poly_iop::unique_column::unique_column::UniqueColumnIOP,
+    };
+
+    fn test_unique_column() -> Result<(), PolyIOPErrors> {
+        // testing params
+        let range_nv = 10;
+        let mut rng = test_rng();
+
+        // PCS params
+        let srs = MultilinearKzgPCS::<Bls12_381>::gen_srs_for_testing(&mut rng, range_nv)?;
+        let (pcs_prover_param, pcs_verifier_param) = MultilinearKzgPCS::<Bls12_381>::trim(&srs, None, Some(range_nv))?;
+
+        // create trackers
+        let mut prover_tracker: ProverTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = ProverTrackerRef::new_from_pcs_params(pcs_prover_param);
+        let mut verifier_tracker: VerifierTrackerRef<Bls12_381, MultilinearKzgPCS<Bls12_381>> = VerifierTrackerRef::new_from_pcs_params(pcs_verifier_param);
+
+        // create the range poly and its multiplicity vector
+        let range_poly_evals = (0..2_usize.pow(range_nv as u32)).map(|x| Fr::from(x as u64)).collect(); // numbers are between 0 and 2^10 by construction
+        let range_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, range_poly_evals);
+        let range_sel_mle = DenseMultilinearExtension::from_evaluations_vec(range_nv, vec![Fr::one(); 2_usize.pow(range_nv as u32)]);
+
+        // a column where 3 is also on an unselected row, and the same column with that row selected
+        let nv = 3;
+        let to_mle = |nums: &[u64]| DenseMultilinearExtension::from_evaluations_vec(nv, nums.iter().map(|x| Fr::from(*x)).collect());
+        let col = to_mle(&[5, 3, 9, 1, 7, 3, 2, 0]);
+        let sel = to_mle(&[1, 1, 1, 1, 1, 0, 1, 0]);
+        let dup_sel = to_mle(&[1, 1, 1, 1, 1, 1, 1, 0]);
+
+        // Test the good path, where the duplicate is not selected
+        print!("UniqueColumnIOP good path test: ");
+        test_unique_column_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &col,
+            &sel,
+            None,
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // Test the good path with the given advice
+        print!("UniqueColumnIOP with advice good path test: ");
+        test_unique_column_helper(
+            &mut prover_tracker,
+            &mut verifier_tracker,
+            &col,
+            &sel,
+            Some(vec![to_mle(&[0, 0, 1, 2, 3, 5, 7, 9]), to_mle(&[0, 0, 1, 1, 1, 1, 1, 1])]),
+            &range_mle,
+            &range_sel_mle,
+        )?;
+        println!("passed");
+
+        // Test the bad path where the duplicate is selected, so the advice can not be computed
+        print!("UniqueColumnIOP selected duplicate test: ");
+        let bad_result = test_unique_column_helper(
+            &mut prover_tracker.deep_copy(),
+            &mut verifier_tracker.deep_copy(),
+            &col,
+            &dup_sel,
+            None,
+            &range_mle,
+            &range_sel_mle,
+        );
+        assert!(bad_result.is_err());
+        println!("passed");
+
+        // Test bad paths with the given advice
+        let bad_cases = [
+            // the sorted column is not sorted
+            (&sel, to_mle(&[0, 0, 1, 3, 2, 5, 7, 9]), to_mle(&[0, 0, 1, 1, 1, 1, 1, 1])),
+            // the sorted column has different values
+            (&sel, to_mle(&[0, 0, 1, 2, 3, 5, 7, 8]), to_mle(&[0, 0, 1, 1, 1, 1, 1, 1])),
+        ];
+        for (i, (bad_sel, sorted, sorted_sel)) in bad_cases.into_iter().enumerate() {
+            print!("UniqueColumnIOP bad path {} test: ", i + 1);
+            let bad_result = test_unique_column_helper(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &col,
+                bad_sel,
+                Some(vec![sorted, sorted_sel]),
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(bad_result.is_err());
+            println!("passed");
+        }
+
+        // Test soundness with hand-built sorted columns that try to hide the selected duplicate,
+        // which the prover can prove with but the verifier rejects
+        let duplicate_cases = [
+            // the duplicate is in the sorted column twice, so it is not strictly sorted
+            (to_mle(&[0, 1, 2, 3, 3, 5, 7, 9]), to_mle(&[0, 1, 1, 1, 1, 1, 1, 1])),
+            // the duplicate is in the sorted column once, with a selector of 2
+            (to_mle(&[0, 0, 1, 2, 3, 5, 7, 9]), to_mle(&[0, 0, 1, 1, 2, 1, 1, 1])),
+            // the duplicate is dropped and replaced with a value that is not in the column
+            (to_mle(&[0, 1, 2, 3, 4, 5, 7, 9]), to_mle(&[0, 1, 1, 1, 1, 1, 1, 1])),
+        ];
+        for (i, (sorted, sorted_sel)) in duplicate_cases.into_iter().enumerate() {
+            print!("UniqueColumnIOP selected duplicate with advice {} test: ", i + 1);
+            let bad_result = test_unique_column_helper(
+                &mut prover_tracker.deep_copy(),
+                &mut verifier_tracker.deep_copy(),
+                &col,
+                &dup_sel,
+                Some(vec![sorted, sorted_sel]),
+                &range_mle,
+                &range_sel_mle,
+            );
+            assert!(matches!(bad_result, Err(PolyIOPErrors::InvalidVerifier(_))));
+            println!("passed");
+        }
+
+        Ok(())
+    }
+
+    // Given inputs, calls and verifies UniqueColumnIOP, with the computed sorted column or the given one
+    fn test_unique_column_helper<E, PCS>(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        col: &DenseMultilinearExtension<E::ScalarField>,
+        sel: &DenseMultilinearExtension<E::ScalarField>,
+        advice: Option<Vec<DenseMultilinearExtension<E::ScalarField>>>, // the sorted column and its selector
+        range_mle: &DenseMultilinearExtension<E::ScalarField>,
+        range_sel_mle: &DenseMultilinearExtension<E::ScalarField>,
+    ) -> Result<(), PolyIOPErrors>
+    where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+    {
+        // set up the prover tracker and prove
+        let col_bag = Bag::new(prover_tracker.track_and_commit_poly(col.clone())?, prover_tracker.track_and_commit_poly(sel.clone())?);
+        let range_bag = Bag::new(prover_tracker.track_and_commit_poly(range_mle.clone())?, prover_tracker.track_and_commit_poly(range_sel_mle.clone())?);
+        let given_advice = advice.is_some();
+        match advice {
+            None => UniqueColumnIOP::<E, PCS>::prove(prover_tracker, &col_bag, &range_bag)?,
+            Some(advice_mles) => {
+                let sorted_bag = Bag::new(prover_tracker.track_and_commit_poly(advice_mles[0].clone())?, prover_tracker.track_and_commit_poly(advice_mles[1].clone())?);
+                UniqueColumnIOP::<E, PCS>::prove_with_advice(prover_tracker, &col_bag, &sorted_bag, &range_bag)?
+            },
+        };
+        let proof = prover_tracker.compile_proof()?;
+
+        // set up the verifier tracker and verify
+        verifier_tracker.set_compiled_proof(proof);
+        let col_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(col_bag.poly.id)?, verifier_tracker.transfer_prover_comm(col_bag.selector.id)?, col.num_vars);
+        let range_bag_comm = BagComm::new(verifier_tracker.transfer_prover_comm(range_bag.poly.id)?, verifier_tracker.transfer_prover_comm(range_bag.selector.id)?, range_mle.num_vars);
+        if given_advice {
+            let sorted_id = verifier_tracker.get_next_id();
+            let sorted_comm = verifier_tracker.transfer_prover_comm(sorted_id)?;
+            let sorted_sel_id = verifier_tracker.get_next_id();
+            let sorted_sel_comm = verifier_tracker.transfer_prover_comm(sorted_sel_id)?;
+            let sorted_bag_comm = BagComm::new(sorted_comm, sorted_sel_comm, col.num_vars);
+            UniqueColumnIOP::<E, PCS>::verify_with_advice(verifier_tracker, &col_bag_comm, &sorted_bag_comm, &range_bag_comm)?;
+        } else {
+            UniqueColumnIOP::<E, PCS>::verify(verifier_tracker, &col_bag_comm, &range_bag_comm)?;
+        }
+        verifier_tracker.verify_claims()?;
+
+        // check that the ProverTracker and VerifierTracker are in the same state
+        let p_tracker = prover_tracker.clone_underlying_tracker();
+        let v_tracker = verifier_tracker.clone_underlying_tracker();
+        assert_eq!(p_tracker.id_counter, v_tracker.id_counter);
+        assert_eq!(p_tracker.sum_check_claims, v_tracker.sum_check_claims);
+        assert_eq!(p_tracker.zero_check_claims, v_tracker.zero_check_claims);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unique_column_test() {
+        let res = test_unique_column();
+        res.unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+// Proves the selected values of a column are distinct, as for a primary key.
+// 1. the sorted column has the same selected values as the column, and its selector is valid, so each value is selected once
+// 2. the sorted column is strictly sorted on its selected rows, so no two of them are equal.
+//    the strict sort range checks the gaps between the values, see prove_range_check
+
+use ark_ec::pairing::Pairing;
+use std::marker::PhantomData;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::{
+    tracker::prelude::*,
+    zksql_poly_iop::{
+        bag_eq::bag_eq::BagEqIOP,
+        bag_sort::bag_sort::BagStrictSortIOP,
+        selector_valid::selector_valid::SelectorValidIOP,
+        unique_column::utils::calc_unique_column_advice,
+    },
+};
+
+pub struct UniqueColumnIOP<E: Pairing, PCS: PolynomialCommitmentScheme<E>>(PhantomData<E>, PhantomData<PCS>);
+
+impl <E: Pairing, PCS: PolynomialCommitmentScheme<E>> UniqueColumnIOP<E, PCS>
+where PCS: PolynomialCommitmentScheme<E> {
+    // computes and commits to the sorted column, and proves the column has no duplicates with it as advice
+    pub fn prove(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        col_bag: &Bag<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        let (sorted_mle, sorted_sel_mle) = calc_unique_column_advice(col_bag)?;
+        let sorted_bag = Bag::new(
+            prover_tracker.track_and_commit_poly(sorted_mle)?,
+            prover_tracker.track_and_commit_poly(sorted_sel_mle)?,
+        );
+        Self::prove_with_advice(prover_tracker, col_bag, &sorted_bag, range_bag)
+    }
+
+    pub fn prove_with_advice(
+        prover_tracker: &mut ProverTrackerRef<E, PCS>,
+        col_bag: &Bag<E, PCS>,
+        sorted_bag: &Bag<E, PCS>,
+        range_bag: &Bag<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        BagEqIOP::<E, PCS>::prove(prover_tracker, col_bag, sorted_bag)?;
+        SelectorValidIOP::<E, PCS>::prove(prover_tracker, &sorted_bag.selector)?;
+        BagStrictSortIOP::<E, PCS>::prove(prover_tracker, sorted_bag, range_bag)
+    }
+
+    // takes the commitments to the sorted column from the proof
+    pub fn verify(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        col_bag: &BagComm<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        let sorted_id = verifier_tracker.get_next_id();
+        let sorted_comm = verifier_tracker.transfer_prover_comm(sorted_id)?;
+        let sorted_sel_id = verifier_tracker.get_next_id();
+        let sorted_sel_comm = verifier_tracker.transfer_prover_comm(sorted_sel_id)?;
+        let sorted_bag = BagComm::new(sorted_comm, sorted_sel_comm, col_bag.num_vars());
+        Self::verify_with_advice(verifier_tracker, col_bag, &sorted_bag, range_bag)
+    }
+
+    pub fn verify_with_advice(
+        verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+        col_bag: &BagComm<E, PCS>,
+        sorted_bag: &BagComm<E, PCS>,
+        range_bag: &BagComm<E, PCS>,
+    ) -> Result<(), PolyIOPErrors> {
+        BagEqIOP::<E, PCS>::verify(verifier_tracker, col_bag, sorted_bag)?;
+        SelectorValidIOP::<E, PCS>::verify(verifier_tracker, &sorted_bag.selector)?;
+        BagStrictSortIOP::<E, PCS>::verify(verifier_tracker, sorted_bag, range_bag)
+    }
+}
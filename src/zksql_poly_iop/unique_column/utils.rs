@@ -0,0 +1,37 @@
+use ark_ec::pairing::Pairing;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{One, Zero};
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::tracker::prelude::*;
+
+// Calculates the sorted column for UniqueColumnIOP, laid out like calc_bag_supp_advice:
+// the selected values sorted ascending at the end, with unselected zeros at the front
+// returns (sorted_poly, sorted_sel), or an error if the selected values have duplicates
+pub fn calc_unique_column_advice<E, PCS>(
+    bag: &Bag<E, PCS>,
+) -> Result<(DenseMultilinearExtension<E::ScalarField>, DenseMultilinearExtension<E::ScalarField>), PolyIOPErrors>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let nv = bag.num_vars();
+    let poly_evals = bag.poly.evaluations();
+    let sel_evals = bag.selector.evaluations();
+    let mut vals = poly_evals.iter().zip(sel_evals.iter()).filter(|(_, sel)| !sel.is_zero()).map(|(val, _)| *val).collect::<Vec<_>>();
+    vals.sort();
+    if vals.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(PolyIOPErrors::InvalidParameters("UniqueColumnIOP Error: the selected values of the column have duplicates".to_string()));
+    }
+
+    let num_fillers = poly_evals.len() - vals.len();
+    let mut sorted_evals = vec![E::ScalarField::zero(); num_fillers];
+    sorted_evals.extend(vals.iter());
+    let mut sorted_sel_evals = vec![E::ScalarField::zero(); num_fillers];
+    sorted_sel_evals.extend(vec![E::ScalarField::one(); vals.len()]);
+
+    Ok((
+        DenseMultilinearExtension::from_evaluations_vec(nv, sorted_evals),
+        DenseMultilinearExtension::from_evaluations_vec(nv, sorted_sel_evals),
+    ))
+}
@@ -0,0 +1,38 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_poly::DenseMultilinearExtension;
+use ark_std::{One, Zero};
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::tracker::prelude::*;
+
+// Shifts the evaluations left by one with wraparound, i.e. the result at index i is the input at index i + 1
+pub fn shift_mle_left<F: PrimeField>(mle: &DenseMultilinearExtension<F>) -> DenseMultilinearExtension<F> {
+    let mut evals = mle.evaluations[1..].to_vec();
+    evals.push(mle.evaluations[0]);
+    DenseMultilinearExtension::from_evaluations_vec(mle.num_vars, evals)
+}
+
+// Calculates the advice for proving the rows of a table are sorted lexicographically:
+// for each column, a marker of the rows where it is the first column that differs from the next row
+pub fn calc_lex_sort_advice<E, PCS>(
+    table: &Table<E, PCS>,
+) -> Vec<DenseMultilinearExtension<E::ScalarField>>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    let nv = table.num_vars();
+    let len = 2_usize.pow(nv as u32);
+    let col_evals = table.col_vals.iter().map(|col| col.evaluations()).collect::<Vec<_>>();
+
+    // the last row is not compared since its next row wraps around
+    let first_diff_cols = (0..len - 1)
+        .map(|i| (0..col_evals.len()).find(|j| col_evals[*j][i] != col_evals[*j][i + 1]))
+        .collect::<Vec<_>>();
+    (0..col_evals.len()).map(|j| {
+        let mut evals = first_diff_cols.iter().map(|first_diff_col| if *first_diff_col == Some(j) { E::ScalarField::one() } else { E::ScalarField::zero() }).collect::<Vec<_>>();
+        evals.push(E::ScalarField::zero());
+        DenseMultilinearExtension::from_evaluations_vec(nv, evals)
+    }).collect()
+}
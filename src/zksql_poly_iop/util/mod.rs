@@ -1,3 +1,5 @@
 pub mod prelude;
 mod intermed_bag_calc;
-mod multiplicity_count;
\ No newline at end of file
+mod lex_sort;
+mod multiplicity_count;
+mod range_check;
\ No newline at end of file
@@ -1,5 +1,7 @@
 
 pub use crate::zksql_poly_iop::util::{
     intermed_bag_calc::{bag_lmr_split, set_lmr_split},
+    lex_sort::{calc_lex_sort_advice, shift_mle_left},
     multiplicity_count::{bag_multiplicity_count, mle_multiplicity_count, vec_multiplicity_count},
+    range_check::{prove_range_check, verify_range_check},
 };
\ No newline at end of file
@@ -0,0 +1,34 @@
+use ark_ec::pairing::Pairing;
+
+use crate::subroutines::pcs::PolynomialCommitmentScheme;
+use crate::tracker::prelude::*;
+use crate::zksql_poly_iop::bag_inclusion::bag_inclusion::BagInclusionIOP;
+
+// Range checks a bag by including it in the range bag, whose selected values are 0, 1, ..., |range_bag| - 1,
+// so every selected value of the bag is shown to be in [0, |range_bag|).
+// Gadgets use it to order values by range checking their differences, e.g. b - a - 1 to show a < b.
+// A difference in the field only has a small representative when the values are ordered and close, so every gadget
+// that takes a range_bag assumes the values it compares differ by less than the size of the range bag
+pub fn prove_range_check<E, PCS>(
+    prover_tracker: &mut ProverTrackerRef<E, PCS>,
+    bag: &Bag<E, PCS>,
+    range_bag: &Bag<E, PCS>, // 0, 1, ..., |range_bag| - 1, bounds the differences of compared values as described above
+) -> Result<(), PolyIOPErrors>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    BagInclusionIOP::<E, PCS>::prove(prover_tracker, bag, range_bag)
+}
+
+pub fn verify_range_check<E, PCS>(
+    verifier_tracker: &mut VerifierTrackerRef<E, PCS>,
+    bag: &BagComm<E, PCS>,
+    range_bag: &BagComm<E, PCS>,
+) -> Result<(), PolyIOPErrors>
+where
+    E: Pairing,
+    PCS: PolynomialCommitmentScheme<E>,
+{
+    BagInclusionIOP::<E, PCS>::verify(verifier_tracker, bag, range_bag)
+}